@@ -32,6 +32,12 @@ pub fn read_i32_le(data: &[u8], offset: usize) -> Option<i32> {
     read_u32_le(data, offset).map(|v| v as i32)
 }
 
+/// Read f64 little-endian from bytes
+pub fn read_f64_le(data: &[u8], offset: usize) -> Option<f64> {
+    let bytes = data.get(offset..offset + 8)?;
+    Some(f64::from_le_bytes(bytes.try_into().ok()?))
+}
+
 /// Read u8 from bytes
 pub fn read_u8(data: &[u8], offset: usize) -> Option<u8> {
     if offset < data.len() {
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-/// HWP unit: 1/7200 inch (1 twip)
+/// HWP unit: 1/7200 inch, so 100 units make a point
 /// Used for margins, font sizes, positions
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct HwpUnit(pub i32);
@@ -15,7 +15,7 @@ impl HwpUnit {
     }
 
     pub fn to_pt(&self) -> f64 {
-        (self.0 as f64) / 20.0
+        (self.0 as f64) / 100.0
     }
 }
 
@@ -27,8 +27,9 @@ impl Color {
         Color(((b as u32) << 16) | ((g as u32) << 8) | (r as u32))
     }
 
+    /// CSS-style #RRGGBB
     pub fn to_hex(&self) -> String {
-        format!("#{:06X}", self.0)
+        format!("#{:02X}{:02X}{:02X}", self.r(), self.g(), self.b())
     }
 
     pub fn r(&self) -> u8 {
@@ -23,9 +23,9 @@ pub fn convert_to_xml(doc: &Document) -> HwpResult<String> {
     // CharShapes
     for char_shape in &doc.char_shapes {
         xml.push_str(&format!(
-            "      <CharShape id=\"{}\" font_id=\"{}\" size=\"{}\" bold=\"{}\" italic=\"{}\" color=\"{}\"/>\n",
+            "      <CharShape id=\"{}\" font_ids=\"{:?}\" size=\"{}\" bold=\"{}\" italic=\"{}\" color=\"{}\"/>\n",
             char_shape.id,
-            char_shape.font_id,
+            char_shape.font_ids,
             char_shape.font_size.0,
             char_shape.bold,
             char_shape.italic,
@@ -124,6 +124,12 @@ fn append_block_xml(xml: &mut String, block: &Block, indent: usize) {
                 indent_str, shape.id, shape.shape_type
             ));
         }
+        Block::Columns(columns) => {
+            xml.push_str(&format!(
+                "{}<Columns count=\"{}\" type=\"{:?}\" gap=\"{}\"/>\n",
+                indent_str, columns.count, columns.column_type, columns.gap.0
+            ));
+        }
     }
 }
 
@@ -144,6 +150,53 @@ fn append_inline_xml(xml: &mut String, inline: &Inline, indent: usize) {
         Inline::Field(field) => {
             xml.push_str(&format!("{}<Field type=\"{:?}\"/>\n", indent_str, field));
         }
+        Inline::FieldRange(field) => {
+            xml.push_str(&format!(
+                "{}<FieldRange id=\"{}\" command=\"{}\">\n",
+                indent_str,
+                field.id,
+                escape_xml(&field.command)
+            ));
+            for inline in &field.inlines {
+                append_inline_xml(xml, inline, indent + 2);
+            }
+            xml.push_str(&format!("{}</FieldRange>\n", indent_str));
+        }
+        Inline::Bookmark(name) => xml.push_str(&format!(
+            "{}<Bookmark name=\"{}\"/>\n",
+            indent_str,
+            escape_xml(name)
+        )),
+        Inline::AutoNumber(number) => xml.push_str(&format!(
+            "{}<AutoNumber kind=\"{:?}\" format=\"{:?}\"/>\n",
+            indent_str, number.kind, number.format
+        )),
+        Inline::Dutmal(dutmal) => xml.push_str(&format!(
+            "{}<Dutmal text=\"{}\" position=\"{:?}\">{}</Dutmal>\n",
+            indent_str,
+            escape_xml(&dutmal.text),
+            dutmal.position,
+            escape_xml(&dutmal.base)
+        )),
+        Inline::CharOverlap(overlap) => xml.push_str(&format!(
+            "{}<CharOverlap border=\"{:?}\">{}</CharOverlap>\n",
+            indent_str,
+            overlap.border,
+            escape_xml(&overlap.chars)
+        )),
+        Inline::PageOverride(page) => xml.push_str(&format!(
+            "{}<PageOverride hide_header=\"{}\" hide_footer=\"{}\" hide_page_number=\"{}\" parity=\"{:?}\"/>\n",
+            indent_str, page.hide_header, page.hide_footer, page.hide_page_number, page.parity
+        )),
+        Inline::NewNumber(number) => xml.push_str(&format!(
+            "{}<NewNumber kind=\"{:?}\" number=\"{}\"/>\n",
+            indent_str, number.kind, number.number
+        )),
+        Inline::Tab => xml.push_str(&format!("{}<Tab/>\n", indent_str)),
+        Inline::LineBreak => xml.push_str(&format!("{}<LineBreak/>\n", indent_str)),
+        Inline::SoftHyphen => xml.push_str(&format!("{}<SoftHyphen/>\n", indent_str)),
+        Inline::NonBreakingSpace => xml.push_str(&format!("{}<NonBreakingSpace/>\n", indent_str)),
+        Inline::FixedWidthSpace => xml.push_str(&format!("{}<FixedWidthSpace/>\n", indent_str)),
     }
 }
 
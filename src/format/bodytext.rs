@@ -0,0 +1,764 @@
+use super::control::{
+    ctrl_id, parse_auto_number, parse_char_overlap, parse_column_def, parse_dutmal,
+    parse_header_footer, parse_hidden_comment, parse_new_number, parse_note, parse_page_hiding,
+    parse_page_number_position, parse_page_odd_even, parse_section_def, read_ctrl_data_string,
+    read_ctrl_id,
+};
+use super::equation::parse_equation;
+use super::field::{is_field_ctrl_id, parse_field, parse_memo_contents};
+use super::shape::parse_shape_object;
+use super::table::parse_table;
+use crate::common::{HwpResult, read_u8, read_u16_le, read_u32_le};
+use crate::model::{
+    Block, ColumnDef, Control, Document, FieldRange, Inline, Memo, NoteKind, NumberKind, Paragraph,
+    Section, TextRun,
+};
+use crate::parser::tags::*;
+use crate::parser::{RecordNode, RecordStream, build_record_tree};
+
+/// Parse BodyText section stream into a section
+pub fn parse_section(data: &[u8], _doc: &mut Document) -> HwpResult<Section> {
+    let mut stream = RecordStream::new(data.to_vec());
+    let mut records = Vec::new();
+    while let Some(record) = stream.next_record()? {
+        records.push(record);
+    }
+
+    let mut ctx = BodyTextContext::new();
+    let mut memo_lists = 0;
+    for node in build_record_tree(records) {
+        if node.tagid() == HWPTAG_PARA_HEADER {
+            ctx.section_start = ctx.section.blocks.is_empty();
+            let blocks = parse_paragraph(&node, &mut ctx)?;
+            ctx.section_start = false;
+            if let Some(columns) = ctx.pending_columns.take() {
+                ctx.section.blocks.push(Block::Columns(columns));
+            }
+            ctx.section.blocks.extend(blocks);
+        } else if node.tagid() == HWPTAG_MEMO_LIST {
+            // Memo contents kept apart from the text. MEMO_LIST is not in
+            // the public spec: a leading nonzero UINT32 is taken as the memo
+            // number, otherwise lists follow the fields' order. MEMO_SHAPE
+            // (the memo box look) is not read.
+            memo_lists += 1;
+            let number = match read_u32_le(node.payload(), 0) {
+                Some(number) if number > 0 => number,
+                _ => memo_lists,
+            };
+            let blocks = parse_paragraph_list(&node.children, &mut ctx)?;
+            if !ctx.section.memos.iter().any(|memo| memo.number == number) {
+                ctx.add_memo(Memo { number, blocks });
+            }
+        }
+    }
+
+    Ok(ctx.section)
+}
+
+/// State shared while walking the record tree of one section
+pub(crate) struct BodyTextContext {
+    section: Section,
+    next_para_id: u32,
+    next_object_id: u32,
+    /// Column layout change found in the current paragraph
+    pending_columns: Option<ColumnDef>,
+    memo_count: u32,
+    /// Whether a top-level paragraph before the section's first block is
+    /// being parsed; cleared within nested paragraph lists
+    section_start: bool,
+    /// Depth of nested paragraph lists (cells, text boxes, notes, ...)
+    list_depth: u32,
+}
+
+impl BodyTextContext {
+    pub(super) fn new() -> Self {
+        BodyTextContext {
+            section: Section::new(),
+            next_para_id: 0,
+            next_object_id: 0,
+            pending_columns: None,
+            memo_count: 0,
+            section_start: false,
+            list_depth: 0,
+        }
+    }
+
+    /// Allocate an ID for a table or drawing object
+    pub(crate) fn next_object_id(&mut self) -> u32 {
+        self.next_object_id += 1;
+        self.next_object_id
+    }
+
+    /// Count a memo field, returning its position in the section
+    pub(crate) fn count_memo(&mut self) -> u32 {
+        self.memo_count += 1;
+        self.memo_count
+    }
+
+    pub(crate) fn add_memo(&mut self, memo: Memo) {
+        self.section.memos.push(memo);
+    }
+}
+
+/// Parse a paragraph list (cell, header/footer, note or text box content)
+pub(crate) fn parse_paragraph_list(
+    nodes: &[RecordNode],
+    ctx: &mut BodyTextContext,
+) -> HwpResult<Vec<Block>> {
+    let section_start = std::mem::take(&mut ctx.section_start);
+    ctx.list_depth += 1;
+    let mut blocks = Vec::new();
+    for node in nodes.iter().filter(|n| n.tagid() == HWPTAG_PARA_HEADER) {
+        blocks.extend(parse_paragraph(node, ctx)?);
+    }
+    ctx.list_depth -= 1;
+    ctx.section_start = section_start;
+    Ok(blocks)
+}
+
+/// Parse a PARA_HEADER record and its child records.
+/// Returns several blocks when the paragraph holds tables.
+fn parse_paragraph(node: &RecordNode, ctx: &mut BodyTextContext) -> HwpResult<Vec<Block>> {
+    let mut para = Paragraph::new(ctx.next_para_id);
+    ctx.next_para_id += 1;
+    parse_para_header(node.payload(), &mut para);
+
+    let chars = node
+        .child(HWPTAG_PARA_TEXT)
+        .map(|n| decode_para_text(n.payload()))
+        .unwrap_or_default();
+    let shapes = node
+        .child(HWPTAG_PARA_CHAR_SHAPE)
+        .map(|n| parse_para_char_shape(n.payload()))
+        .unwrap_or_default();
+
+    // Extended controls pair up with CTRL_HEADER children in order
+    let controls = node
+        .children
+        .iter()
+        .filter(|c| c.tagid() == HWPTAG_CTRL_HEADER);
+
+    para.char_shape_id = shapes.first().map(|&(_, id)| id).unwrap_or(0);
+    para.inlines = build_inlines(&chars, &shapes, controls, ctx)?;
+
+    Ok(split_paragraph(para))
+}
+
+/// Move tables out of the paragraph into blocks of their own, since ODF
+/// paragraphs cannot contain tables. Text around a table stays in
+/// paragraphs with the same shape.
+fn split_paragraph(mut para: Paragraph) -> Vec<Block> {
+    let has_table = para
+        .inlines
+        .iter()
+        .any(|i| matches!(i, Inline::Control(Control::Table(_))));
+    if !has_table {
+        return vec![Block::Paragraph(para)];
+    }
+
+    let inlines = std::mem::take(&mut para.inlines);
+    let mut continuation = para.clone();
+    continuation.page_break = false;
+    continuation.column_break = false;
+
+    let mut blocks = Vec::new();
+    let mut current = para;
+    for inline in inlines {
+        match inline {
+            Inline::Control(Control::Table(table)) => {
+                // Keep an empty leading paragraph only when it carries a break
+                if !current.inlines.is_empty() || current.page_break || current.column_break {
+                    blocks.push(Block::Paragraph(current));
+                }
+                blocks.push(Block::Table(table));
+                current = continuation.clone();
+            }
+            other => current.inlines.push(other),
+        }
+    }
+    if !current.inlines.is_empty() {
+        blocks.push(Block::Paragraph(current));
+    }
+    blocks
+}
+
+/// Parse PARA_HEADER payload
+/// UINT32 nchars, UINT32 control mask, UINT16 para shape ID, UINT8 style ID,
+/// UINT8 break type, UINT16 char shape count, ...
+fn parse_para_header(payload: &[u8], para: &mut Paragraph) {
+    // Bit 31 of nchars is a flag, not part of the count
+    para.char_count = read_u32_le(payload, 0).unwrap_or(0) & 0x7FFF_FFFF;
+    para.control_mask = read_u32_le(payload, 4).unwrap_or(0);
+    para.para_shape_id = read_u16_le(payload, 8).unwrap_or(0) as u32;
+    para.style_id = read_u8(payload, 10).unwrap_or(0) as u32;
+
+    // Break type: 0x01 section, 0x02 multi-column, 0x04 page, 0x08 column
+    let break_type = read_u8(payload, 11).unwrap_or(0);
+    para.page_break = break_type & 0x04 != 0;
+    para.column_break = break_type & 0x08 != 0;
+}
+
+/// Parse PARA_CHAR_SHAPE payload into (position, char shape ID) pairs
+fn parse_para_char_shape(payload: &[u8]) -> Vec<(u32, u32)> {
+    let mut shapes = Vec::with_capacity(payload.len() / 8);
+    let mut offset = 0;
+    while let (Some(pos), Some(id)) = (
+        read_u32_le(payload, offset),
+        read_u32_le(payload, offset + 4),
+    ) {
+        shapes.push((pos, id));
+        offset += 8;
+    }
+    shapes
+}
+
+/// Element of PARA_TEXT after decoding the control character scheme
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum ParaChar {
+    /// Ordinary character
+    Char(char),
+    /// Char control occupying a single WCHAR (line break, para end, ...)
+    CharControl(u16),
+    /// Inline control occupying 8 WCHARs (tab, field end, ...)
+    Inline { code: u16, ctrl_id: u32 },
+    /// Extended control occupying 8 WCHARs, backed by a CTRL_HEADER record
+    Extended { code: u16, ctrl_id: u32 },
+}
+
+/// Size of inline and extended controls in WCHARs
+const CONTROL_WCHARS: usize = 8;
+
+/// HWP control characters used in PARA_TEXT
+pub(crate) mod ctrl_char {
+    // Inline controls (8 WCHARs)
+    pub const FIELD_END: u16 = 4;
+    pub const TAB: u16 = 9;
+
+    // Char controls (1 WCHAR)
+    pub const LINE_BREAK: u16 = 10;
+    pub const HYPHEN: u16 = 24;
+    pub const NON_BREAKING_SPACE: u16 = 30;
+    pub const FIXED_WIDTH_SPACE: u16 = 31;
+}
+
+/// Decode PARA_TEXT payload (UTF-16LE with HWP control characters).
+/// Returns each element with its WCHAR position in the paragraph.
+pub(crate) fn decode_para_text(payload: &[u8]) -> Vec<(u32, ParaChar)> {
+    let wchars: Vec<u16> = payload
+        .chunks_exact(2)
+        .map(|b| u16::from_le_bytes([b[0], b[1]]))
+        .collect();
+
+    let mut result = Vec::with_capacity(wchars.len());
+    let mut i = 0;
+    while i < wchars.len() {
+        let code = wchars[i];
+        let pos = i as u32;
+
+        match code {
+            0 | 10 | 13 | 24..=31 => {
+                result.push((pos, ParaChar::CharControl(code)));
+                i += 1;
+            }
+            1..=31 => {
+                let ctrl_id = match (wchars.get(i + 1), wchars.get(i + 2)) {
+                    (Some(&lo), Some(&hi)) => ((hi as u32) << 16) | lo as u32,
+                    _ => 0,
+                };
+                let item = match code {
+                    4..=9 | 19 | 20 => ParaChar::Inline { code, ctrl_id },
+                    _ => ParaChar::Extended { code, ctrl_id },
+                };
+                result.push((pos, item));
+                i += CONTROL_WCHARS;
+            }
+            _ => {
+                // Combine surrogate pairs; unpaired surrogates become U+FFFD
+                let len = if (0xD800..0xDC00).contains(&code)
+                    && wchars
+                        .get(i + 1)
+                        .is_some_and(|&lo| (0xDC00..0xE000).contains(&lo))
+                {
+                    2
+                } else {
+                    1
+                };
+                let c = char::decode_utf16(wchars[i..i + len].iter().copied())
+                    .next()
+                    .and_then(|r| r.ok())
+                    .unwrap_or(char::REPLACEMENT_CHARACTER);
+                result.push((pos, ParaChar::Char(c)));
+                i += len;
+            }
+        }
+    }
+
+    result
+}
+
+/// Find the char shape ID applied at a WCHAR position
+fn char_shape_at(shapes: &[(u32, u32)], pos: u32) -> u32 {
+    shapes
+        .iter()
+        .take_while(|&&(start, _)| start <= pos)
+        .last()
+        .map(|&(_, id)| id)
+        .unwrap_or(0)
+}
+
+/// Map a char or inline control to its inline node
+fn control_inline(code: u16) -> Option<Inline> {
+    match code {
+        ctrl_char::TAB => Some(Inline::Tab),
+        ctrl_char::LINE_BREAK => Some(Inline::LineBreak),
+        ctrl_char::HYPHEN => Some(Inline::SoftHyphen),
+        ctrl_char::NON_BREAKING_SPACE => Some(Inline::NonBreakingSpace),
+        ctrl_char::FIXED_WIDTH_SPACE => Some(Inline::FixedWidthSpace),
+        _ => None,
+    }
+}
+
+/// Parse the CTRL_HEADER of an extended control, returning its inline node if any
+fn parse_control(node: &RecordNode, ctx: &mut BodyTextContext) -> HwpResult<Option<Inline>> {
+    match read_ctrl_id(node) {
+        ctrl_id::SECTION_DEF => {
+            parse_section_def(node, &mut ctx.section);
+            Ok(None)
+        }
+        ctrl_id::TABLE => {
+            let table = parse_table(node, ctx)?;
+            Ok(Some(Inline::Control(Control::Table(table))))
+        }
+        ctrl_id::SHAPE_OBJECT => Ok(parse_shape_object(node, ctx)?.map(Inline::Control)),
+        ctrl_id::EQUATION => {
+            let equation = parse_equation(node, ctx)?;
+            Ok(Some(Inline::Control(Control::Equation(equation))))
+        }
+        ctrl_id::HEADER => {
+            let header = parse_header_footer(node, ctx)?;
+            ctx.section.headers.push(header);
+            Ok(None)
+        }
+        ctrl_id::FOOTER => {
+            let footer = parse_header_footer(node, ctx)?;
+            ctx.section.footers.push(footer);
+            Ok(None)
+        }
+        ctrl_id::FOOTNOTE | ctrl_id::ENDNOTE => {
+            let kind = if read_ctrl_id(node) == ctrl_id::FOOTNOTE {
+                NoteKind::Footnote
+            } else {
+                NoteKind::Endnote
+            };
+            let note = parse_note(node, kind, ctx)?;
+            Ok(Some(Inline::Control(Control::Note(note))))
+        }
+        ctrl_id::AUTO_NUMBER => Ok(Some(Inline::AutoNumber(parse_auto_number(node)))),
+        ctrl_id::NEW_NUMBER => {
+            // A page number restart before the first block starts the section
+            let new_number = parse_new_number(node);
+            if new_number.kind == NumberKind::Page && ctx.section_start {
+                ctx.section.start_numbers.page = new_number.number;
+                return Ok(None);
+            }
+            Ok(Some(Inline::NewNumber(new_number)))
+        }
+        ctrl_id::PAGE_NUMBER_POSITION => {
+            ctx.section.page_number_position = Some(parse_page_number_position(node));
+            Ok(None)
+        }
+        ctrl_id::PAGE_HIDING | ctrl_id::PAGE_ODD_EVEN => {
+            let page = if read_ctrl_id(node) == ctrl_id::PAGE_HIDING {
+                parse_page_hiding(node)
+            } else {
+                parse_page_odd_even(node)
+            };
+            // On the section's first page they belong to its first page
+            // master; later ones stay with the page they are on
+            if ctx.section_start {
+                ctx.section.apply_first_page(page);
+                Ok(None)
+            } else {
+                Ok(Some(Inline::PageOverride(page)))
+            }
+        }
+        ctrl_id::BOOKMARK => Ok(read_ctrl_data_string(node).map(Inline::Bookmark)),
+        ctrl_id::DUTMAL => Ok(Some(Inline::Dutmal(parse_dutmal(node)))),
+        ctrl_id::CHAR_OVERLAP => Ok(Some(Inline::CharOverlap(parse_char_overlap(node)))),
+        ctrl_id::HIDDEN_COMMENT => {
+            let annotation = parse_hidden_comment(node, ctx)?;
+            Ok(Some(Inline::Control(Control::Annotation(annotation))))
+        }
+        id if is_field_ctrl_id(id) => {
+            let mut field = parse_field(node);
+            parse_memo_contents(node, &mut field, ctx)?;
+            Ok(Some(Inline::FieldRange(field)))
+        }
+        ctrl_id::COLUMN_DEF => {
+            // Columns defined before the first block apply to the whole
+            // page; nested lists cannot change the page's columns
+            let columns = parse_column_def(node);
+            if ctx.list_depth > 0 {
+                return Ok(None);
+            }
+            if ctx.section_start {
+                ctx.section.columns = columns;
+            } else {
+                ctx.pending_columns = Some(columns);
+            }
+            Ok(None)
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Build inline content, splitting text into runs at char shape boundaries
+fn build_inlines<'a>(
+    chars: &[(u32, ParaChar)],
+    shapes: &[(u32, u32)],
+    mut controls: impl Iterator<Item = &'a RecordNode>,
+    ctx: &mut BodyTextContext,
+) -> HwpResult<Vec<Inline>> {
+    let mut inlines = Vec::new();
+    let mut current: Option<TextRun> = None;
+    // Open fields with the inlines collected before each one started
+    let mut fields: Vec<(FieldRange, Vec<Inline>)> = Vec::new();
+
+    for (pos, item) in chars {
+        let shape_id = char_shape_at(shapes, *pos);
+        match item {
+            ParaChar::Char(c) => match current.as_mut() {
+                Some(run) if run.char_shape_id == shape_id => run.text.push(*c),
+                _ => {
+                    flush_run(&mut current, &mut inlines);
+                    current = Some(TextRun {
+                        text: c.to_string(),
+                        char_shape_id: shape_id,
+                        change_id: None,
+                    });
+                }
+            },
+            ParaChar::Inline {
+                code: ctrl_char::FIELD_END,
+                ..
+            } => {
+                flush_run(&mut current, &mut inlines);
+                if let Some((mut field, outer)) = fields.pop() {
+                    field.inlines = std::mem::replace(&mut inlines, outer);
+                    inlines.push(Inline::FieldRange(field));
+                }
+            }
+            ParaChar::CharControl(code) | ParaChar::Inline { code, .. } => {
+                if let Some(inline) = control_inline(*code) {
+                    flush_run(&mut current, &mut inlines);
+                    inlines.push(inline);
+                }
+            }
+            ParaChar::Extended { .. } => {
+                let Some(ctrl) = controls.next() else {
+                    continue;
+                };
+                match parse_control(ctrl, ctx)? {
+                    Some(Inline::FieldRange(field)) => {
+                        flush_run(&mut current, &mut inlines);
+                        fields.push((field, std::mem::take(&mut inlines)));
+                    }
+                    Some(mut inline) => {
+                        flush_run(&mut current, &mut inlines);
+                        // Dutmal and overlapping characters take the shape of their position
+                        match &mut inline {
+                            Inline::Dutmal(dutmal) => dutmal.char_shape_id = shape_id,
+                            Inline::CharOverlap(overlap) => overlap.char_shape_id = shape_id,
+                            _ => {}
+                        }
+                        inlines.push(inline);
+                    }
+                    None => {}
+                }
+            }
+        }
+    }
+
+    flush_run(&mut current, &mut inlines);
+    // Fields left open at the paragraph end are closed there
+    while let Some((mut field, outer)) = fields.pop() {
+        field.inlines = std::mem::replace(&mut inlines, outer);
+        inlines.push(Inline::FieldRange(field));
+    }
+    Ok(inlines)
+}
+
+fn flush_run(current: &mut Option<TextRun>, inlines: &mut Vec<Inline>) {
+    if let Some(run) = current.take() {
+        inlines.push(Inline::Text(run));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Record;
+
+    fn utf16_payload(units: &[u16]) -> Vec<u8> {
+        units.iter().flat_map(|u| u.to_le_bytes()).collect()
+    }
+
+    #[test]
+    fn test_decode_para_text_controls() {
+        // "A", tab (inline, 8 WCHARs), "B", para end
+        let mut units = vec![0x41, 9, 0, 0, 0, 0, 0, 0, 9, 0x42, 13];
+        let chars = decode_para_text(&utf16_payload(&units));
+        assert_eq!(chars.len(), 4);
+        assert_eq!(chars[0], (0, ParaChar::Char('A')));
+        assert_eq!(
+            chars[1],
+            (
+                1,
+                ParaChar::Inline {
+                    code: 9,
+                    ctrl_id: 0
+                }
+            )
+        );
+        assert_eq!(chars[2], (9, ParaChar::Char('B')));
+        assert_eq!(chars[3], (10, ParaChar::CharControl(13)));
+
+        // Surrogate pair counts as two WCHARs
+        units = vec![0xD83D, 0xDE00, 0x41];
+        let chars = decode_para_text(&utf16_payload(&units));
+        assert_eq!(chars[0], (0, ParaChar::Char('\u{1F600}')));
+        assert_eq!(chars[1], (2, ParaChar::Char('A')));
+    }
+
+    #[test]
+    fn test_build_inlines_splits_runs() {
+        let units: Vec<u16> = "가나다라".encode_utf16().chain([13]).collect();
+        let chars = decode_para_text(&utf16_payload(&units));
+        let mut ctx = BodyTextContext::new();
+        let inlines =
+            build_inlines(&chars, &[(0, 3), (2, 7)], std::iter::empty(), &mut ctx).unwrap();
+
+        let runs: Vec<(String, u32)> = inlines
+            .iter()
+            .filter_map(|i| match i {
+                Inline::Text(run) => Some((run.text.clone(), run.char_shape_id)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(runs, vec![("가나".to_string(), 3), ("다라".to_string(), 7)]);
+    }
+
+    #[test]
+    fn test_build_inlines_controls() {
+        // "A", tab, "B", line break, non-breaking space, "C", para end
+        let units = vec![0x41, 9, 0, 0, 0, 0, 0, 0, 9, 0x42, 10, 30, 0x43, 13];
+        let chars = decode_para_text(&utf16_payload(&units));
+        let mut ctx = BodyTextContext::new();
+        let inlines = build_inlines(&chars, &[(0, 0)], std::iter::empty(), &mut ctx).unwrap();
+
+        let kinds: Vec<&str> = inlines
+            .iter()
+            .map(|i| match i {
+                Inline::Text(_) => "text",
+                Inline::Tab => "tab",
+                Inline::LineBreak => "line-break",
+                Inline::NonBreakingSpace => "nbsp",
+                _ => "other",
+            })
+            .collect();
+        assert_eq!(
+            kinds,
+            vec!["text", "tab", "text", "line-break", "nbsp", "text"]
+        );
+    }
+
+    #[test]
+    fn test_build_inlines_field_range() {
+        // "A", field start (%hlk), "B", field end, "C"
+        let units = vec![
+            0x41, 3, 0, 0, 0, 0, 0, 0, 3, 0x42, 4, 0, 0, 0, 0, 0, 0, 4, 0x43,
+        ];
+        let chars = decode_para_text(&utf16_payload(&units));
+
+        let mut payload = u32::from_be_bytes(*b"%hlk").to_le_bytes().to_vec();
+        payload.extend([0, 0, 0, 0, 0]);
+        let command: Vec<u16> = r"http\://a.kr;1;0;0;".encode_utf16().collect();
+        payload.extend((command.len() as u16).to_le_bytes());
+        payload.extend(utf16_payload(&command));
+        let ctrl = RecordNode {
+            record: Record {
+                tagid: HWPTAG_CTRL_HEADER,
+                tagname: String::new(),
+                level: 1,
+                size: payload.len() as u32,
+                payload,
+            },
+            children: Vec::new(),
+        };
+
+        let mut ctx = BodyTextContext::new();
+        let inlines = build_inlines(&chars, &[(0, 0)], std::iter::once(&ctrl), &mut ctx).unwrap();
+        assert_eq!(inlines.len(), 3);
+        let Inline::FieldRange(field) = &inlines[1] else {
+            panic!("expected a field range");
+        };
+        assert_eq!(
+            field.kind,
+            crate::model::FieldKind::Hyperlink {
+                target: "http://a.kr".to_string()
+            }
+        );
+        assert!(matches!(&field.inlines[..], [Inline::Text(run)] if run.text == "B"));
+    }
+
+    #[test]
+    fn test_page_hiding_after_first_page() {
+        // Hide header and page number
+        let mut payload = ctrl_id::PAGE_HIDING.to_le_bytes().to_vec();
+        payload.extend(0x21u32.to_le_bytes());
+        let ctrl = RecordNode {
+            record: Record {
+                tagid: HWPTAG_CTRL_HEADER,
+                tagname: String::new(),
+                level: 1,
+                size: payload.len() as u32,
+                payload,
+            },
+            children: Vec::new(),
+        };
+
+        let mut ctx = BodyTextContext::new();
+        ctx.section_start = true;
+        assert!(parse_control(&ctrl, &mut ctx).unwrap().is_none());
+        assert!(ctx.section.hide_first_header && ctx.section.hide_first_page_number);
+
+        ctx.section_start = false;
+        let Some(Inline::PageOverride(page)) = parse_control(&ctrl, &mut ctx).unwrap() else {
+            panic!("expected a page override");
+        };
+        assert!(page.hide_header && !page.hide_footer && page.hide_page_number);
+    }
+
+    fn record(tag: u16, level: u32, payload: &[u8]) -> Vec<u8> {
+        let header = tag as u32 | (level << 10) | ((payload.len() as u32) << 20);
+        let mut data = header.to_le_bytes().to_vec();
+        data.extend_from_slice(payload);
+        data
+    }
+
+    /// A first paragraph holding a one-cell table whose paragraph has the
+    /// given control
+    fn section_with_cell_control(ctrl: u32, payload: &[u8]) -> Section {
+        let extended = |code: u16, ctrl: u32| {
+            let mut units = vec![code, ctrl as u16, (ctrl >> 16) as u16, 0, 0, 0, 0, code];
+            units.push(13);
+            utf16_payload(&units)
+        };
+        let mut ctrl_payload = ctrl.to_le_bytes().to_vec();
+        ctrl_payload.extend(payload);
+        let mut table = 0u32.to_le_bytes().to_vec();
+        table.extend([1u8, 0, 1, 0]);
+        table.resize(24, 0);
+
+        let mut data = record(HWPTAG_PARA_HEADER, 0, &[0; 22]);
+        data.extend(record(HWPTAG_PARA_TEXT, 1, &extended(11, ctrl_id::TABLE)));
+        data.extend(record(HWPTAG_CTRL_HEADER, 1, &ctrl_id::TABLE.to_le_bytes()));
+        data.extend(record(HWPTAG_TABLE, 2, &table));
+        data.extend(record(HWPTAG_LIST_HEADER, 2, &[0; 34]));
+        data.extend(record(HWPTAG_PARA_HEADER, 2, &[0; 22]));
+        data.extend(record(HWPTAG_PARA_TEXT, 3, &extended(21, ctrl)));
+        data.extend(record(HWPTAG_CTRL_HEADER, 3, &ctrl_payload));
+        parse_section(&data, &mut Document::new()).unwrap()
+    }
+
+    /// Inlines of the paragraph in the table cell
+    fn cell_inlines(section: &Section) -> &[Inline] {
+        let Some(Block::Table(table)) = section.blocks.first() else {
+            panic!("expected a table");
+        };
+        let Some(Block::Paragraph(para)) = table.cells[0].content.first() else {
+            panic!("expected a cell paragraph");
+        };
+        &para.inlines
+    }
+
+    #[test]
+    fn test_memo_field_links_memo_list() {
+        // "A", memo field start, "B", field end, para end
+        let memo = u32::from_be_bytes(*b"%%me");
+        let (lo, hi) = (memo as u16, (memo >> 16) as u16);
+        let units = vec![
+            0x41, 3, lo, hi, 0, 0, 0, 0, 3, 0x42, 4, 0, 0, 0, 0, 0, 0, 4, 13,
+        ];
+        let mut field = memo.to_le_bytes().to_vec();
+        field.extend([0, 0, 0, 0, 0]);
+        let command: Vec<u16> = "Number:wstring:1:2 Author:wstring:3:홍길동 "
+            .encode_utf16()
+            .collect();
+        field.extend((command.len() as u16).to_le_bytes());
+        field.extend(utf16_payload(&command));
+        let text: Vec<u16> = "메모".encode_utf16().chain([13]).collect();
+
+        let mut data = record(HWPTAG_PARA_HEADER, 0, &[0; 22]);
+        data.extend(record(HWPTAG_PARA_TEXT, 1, &utf16_payload(&units)));
+        data.extend(record(HWPTAG_CTRL_HEADER, 1, &field));
+        data.extend(record(HWPTAG_MEMO_LIST, 0, &2u32.to_le_bytes()));
+        data.extend(record(HWPTAG_PARA_HEADER, 1, &[0; 22]));
+        data.extend(record(HWPTAG_PARA_TEXT, 2, &utf16_payload(&text)));
+        let section = parse_section(&data, &mut Document::new()).unwrap();
+
+        let Some(Block::Paragraph(para)) = section.blocks.first() else {
+            panic!("expected a paragraph");
+        };
+        let Some(Inline::FieldRange(range)) = para.inlines.get(1) else {
+            panic!("expected a field range");
+        };
+        let crate::model::FieldKind::Memo { number, author, .. } = &range.kind else {
+            panic!("expected a memo field");
+        };
+        assert_eq!((*number, author.as_str()), (2, "홍길동"));
+
+        let memo = section
+            .memos
+            .iter()
+            .find(|memo| memo.number == *number)
+            .unwrap();
+        assert!(matches!(
+            &memo.blocks[..],
+            [Block::Paragraph(para)]
+                if matches!(&para.inlines[..], [Inline::Text(run)] if run.text == "메모")
+        ));
+    }
+
+    #[test]
+    fn test_columns_in_first_cell_leave_page_columns() {
+        // Two columns
+        let section = section_with_cell_control(ctrl_id::COLUMN_DEF, &[0x08, 0x10, 0, 0]);
+        assert_eq!(section.columns.count, 1);
+        assert!(
+            !section
+                .blocks
+                .iter()
+                .any(|block| matches!(block, Block::Columns(_)))
+        );
+    }
+
+    #[test]
+    fn test_page_controls_in_first_cell_stay_in_cell() {
+        // Page number restart at 5
+        let section = section_with_cell_control(ctrl_id::NEW_NUMBER, &[0, 0, 0, 0, 5, 0]);
+        assert_eq!(section.start_numbers.page, 0);
+        assert!(matches!(
+            cell_inlines(&section),
+            [Inline::NewNumber(number)] if number.number == 5
+        ));
+
+        // Hide the header
+        let section = section_with_cell_control(ctrl_id::PAGE_HIDING, &[0x01, 0, 0, 0]);
+        assert!(!section.hide_first_header);
+        assert!(matches!(
+            cell_inlines(&section),
+            [Inline::PageOverride(page)] if page.hide_header
+        ));
+    }
+}
@@ -0,0 +1,181 @@
+use crate::model::{Chart, ChartGrouping, ChartSeries, ChartType, LegendPosition};
+use crate::parser::XmlElement;
+
+/// Parse the DrawingML chart (c:chartSpace) that newer versions keep in a
+/// chart object's OOXMLChartContents stream. The first chart group of the
+/// plot area decides the chart type.
+///
+/// Charts saved only in HWP's own chart format (the storage's `Contents`
+/// stream, HWPTAG_CHART_DATA) are not parsed; they stay OLE objects with
+/// their storage and the picture cached in it.
+pub(crate) fn parse_ooxml_chart(data: &[u8]) -> Option<Chart> {
+    let root = XmlElement::parse(data).ok()?;
+    let chart = root.child("chart")?;
+    let plot_area = chart.child("plotArea")?;
+    let group = plot_area
+        .children
+        .iter()
+        .find(|child| child.name.ends_with("Chart"))?;
+
+    let value = |element: &XmlElement, name: &str| {
+        element
+            .child(name)
+            .and_then(|child| child.attr("val"))
+            .map(str::to_string)
+    };
+    let chart_type = match group.name.trim_end_matches("Chart").trim_end_matches("3D") {
+        "bar" if value(group, "barDir").as_deref() == Some("bar") => ChartType::Bar,
+        "line" | "stock" => ChartType::Line,
+        "area" => ChartType::Area,
+        "pie" | "ofPie" => ChartType::Pie,
+        "doughnut" => ChartType::Doughnut,
+        "scatter" | "bubble" => ChartType::Scatter,
+        "radar" => ChartType::Radar,
+        _ => ChartType::Column,
+    };
+    let grouping = match value(group, "grouping").as_deref() {
+        Some("stacked") => ChartGrouping::Stacked,
+        Some("percentStacked") => ChartGrouping::PercentStacked,
+        _ => ChartGrouping::Standard,
+    };
+
+    // A deleted automatic title leaves no title element to read
+    let title = match chart.child("title") {
+        Some(title) if title.descendant("rich").is_some() => {
+            let mut runs = Vec::new();
+            collect_text(title, &mut runs);
+            runs.concat()
+        }
+        Some(title) => title
+            .descendant("v")
+            .map(|v| v.text.clone())
+            .unwrap_or_default(),
+        None => String::new(),
+    };
+    let legend = chart
+        .child("legend")
+        .map(|legend| match value(legend, "legendPos").as_deref() {
+            Some("t") => LegendPosition::Top,
+            Some("b") => LegendPosition::Bottom,
+            Some("l") => LegendPosition::Left,
+            _ => LegendPosition::Right,
+        });
+
+    let mut categories = Vec::new();
+    let mut series = Vec::new();
+    for (i, ser) in group.children_named("ser").enumerate() {
+        if categories.is_empty()
+            && let Some(cat) = ser.child("cat").or_else(|| ser.child("xVal"))
+        {
+            categories = points(cat)
+                .into_iter()
+                .map(Option::unwrap_or_default)
+                .collect();
+        }
+        let name = ser
+            .child("tx")
+            .map(|tx| match tx.descendant("v") {
+                Some(v) => v.text.clone(),
+                None => tx.all_text().trim().to_string(),
+            })
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| format!("Series {}", i + 1));
+        let values = ser
+            .child("val")
+            .or_else(|| ser.child("yVal"))
+            .map(points)
+            .unwrap_or_default()
+            .into_iter()
+            // "NaN" and "inf" parse, but are no values for the data table
+            .map(|point| {
+                point
+                    .and_then(|v| v.trim().parse::<f64>().ok())
+                    .filter(|v| v.is_finite())
+            })
+            .collect();
+        series.push(ChartSeries { name, values });
+    }
+
+    Some(Chart {
+        chart_type,
+        grouping,
+        three_d: group.name.contains("3D"),
+        title,
+        legend,
+        categories,
+        series,
+    })
+}
+
+/// Text runs (a:t) below an element, in document order
+fn collect_text(element: &XmlElement, runs: &mut Vec<String>) {
+    for child in &element.children {
+        if child.name == "t" {
+            runs.push(child.text.clone());
+        } else {
+            collect_text(child, runs);
+        }
+    }
+}
+
+/// Cached points of a data reference (str/num cache or literal), placed
+/// by index. Multi-level categories use their first level.
+fn points(reference: &XmlElement) -> Vec<Option<String>> {
+    fn cache(element: &XmlElement) -> Option<&XmlElement> {
+        if element.child("pt").is_some() || element.child("ptCount").is_some() {
+            Some(element)
+        } else {
+            element.children.iter().find_map(cache)
+        }
+    }
+    let Some(cache) = cache(reference) else {
+        return Vec::new();
+    };
+
+    let mut points: Vec<Option<String>> = Vec::new();
+    if let Some(count) = cache
+        .child("ptCount")
+        .and_then(|count| count.attr("val"))
+        .and_then(|val| val.parse::<usize>().ok())
+    {
+        points.resize(count.min(4096), None);
+    }
+    for (i, pt) in cache.children_named("pt").enumerate() {
+        let idx = pt
+            .attr("idx")
+            .and_then(|idx| idx.parse::<usize>().ok())
+            .unwrap_or(i);
+        if idx >= 4096 {
+            continue;
+        }
+        if idx >= points.len() {
+            points.resize(idx + 1, None);
+        }
+        points[idx] = pt.child("v").map(|v| v.text.clone());
+    }
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ooxml_bar_chart() {
+        let xml = br#"<c:chartSpace xmlns:c="c" xmlns:a="a"><c:chart>
+<c:title><c:tx><c:rich><a:p><a:r><a:t>Sales</a:t></a:r></a:p></c:rich></c:tx></c:title>
+<c:plotArea><c:barChart><c:barDir val="col"/><c:grouping val="stacked"/>
+<c:ser><c:tx><c:strRef><c:strCache><c:pt idx="0"><c:v>2024</c:v></c:pt></c:strCache></c:strRef></c:tx>
+<c:cat><c:strRef><c:strCache><c:ptCount val="2"/><c:pt idx="0"><c:v>Q1</c:v></c:pt><c:pt idx="1"><c:v>Q2</c:v></c:pt></c:strCache></c:strRef></c:cat>
+<c:val><c:numRef><c:numCache><c:ptCount val="3"/><c:pt idx="1"><c:v>3.5</c:v></c:pt><c:pt idx="2"><c:v>NaN</c:v></c:pt></c:numCache></c:numRef></c:val></c:ser>
+</c:barChart></c:plotArea><c:legend><c:legendPos val="b"/></c:legend></c:chart></c:chartSpace>"#;
+        let chart = parse_ooxml_chart(xml).unwrap();
+        assert_eq!(chart.chart_type, ChartType::Column);
+        assert_eq!(chart.grouping, ChartGrouping::Stacked);
+        assert_eq!(chart.title, "Sales");
+        assert_eq!(chart.legend, Some(LegendPosition::Bottom));
+        assert_eq!(chart.categories, ["Q1", "Q2"]);
+        assert_eq!(chart.series[0].name, "2024");
+        assert_eq!(chart.series[0].values, [None, Some(3.5), None]);
+    }
+}
@@ -0,0 +1,586 @@
+use super::bodytext::{BodyTextContext, parse_paragraph_list};
+use super::{border_width_from_hwp, line_style_from_hwp};
+use crate::common::{Color, HwpResult, HwpUnit, Margin, read_u8, read_u16_le, read_u32_le};
+use crate::model::{
+    Annotation, AutoNumber, BindingType, Block, Border, Caption, CaptionSide, CharOverlap,
+    ColumnDef, ColumnDirection, ColumnType, Dutmal, DutmalAlign, DutmalPosition, HeaderFooter,
+    NewNumber, Note, NoteKind, NoteNumbering, NoteShape, NumberFormat, NumberKind, ObjectAlign,
+    ObjectPlacement, OverlapBorder, PageApply, PageBorderFill, PageNumberPlace, PageNumberPosition,
+    PageOverride, RelativeTo, Section, SizeRelativeTo, StartNumbers, TextDirection, TextFlow,
+    TextWrap,
+};
+use crate::parser::RecordNode;
+use crate::parser::tags::*;
+
+/// Build a control ID from its 4-character name ('secd' → 0x73656364)
+const fn make_ctrl_id(name: &[u8; 4]) -> u32 {
+    ((name[0] as u32) << 24) | ((name[1] as u32) << 16) | ((name[2] as u32) << 8) | name[3] as u32
+}
+
+/// Control IDs stored in the first 4 bytes of CTRL_HEADER
+pub(crate) mod ctrl_id {
+    use super::make_ctrl_id;
+
+    pub const SECTION_DEF: u32 = make_ctrl_id(b"secd");
+    pub const COLUMN_DEF: u32 = make_ctrl_id(b"cold");
+    pub const TABLE: u32 = make_ctrl_id(b"tbl ");
+    pub const HEADER: u32 = make_ctrl_id(b"head");
+    pub const FOOTER: u32 = make_ctrl_id(b"foot");
+    pub const FOOTNOTE: u32 = make_ctrl_id(b"fn  ");
+    pub const ENDNOTE: u32 = make_ctrl_id(b"en  ");
+    pub const BOOKMARK: u32 = make_ctrl_id(b"bokm");
+    pub const AUTO_NUMBER: u32 = make_ctrl_id(b"atno");
+    pub const NEW_NUMBER: u32 = make_ctrl_id(b"nwno");
+    pub const PAGE_NUMBER_POSITION: u32 = make_ctrl_id(b"pgnp");
+    pub const PAGE_HIDING: u32 = make_ctrl_id(b"pghd");
+    pub const PAGE_ODD_EVEN: u32 = make_ctrl_id(b"pgct");
+    pub const SHAPE_OBJECT: u32 = make_ctrl_id(b"gso ");
+    pub const EQUATION: u32 = make_ctrl_id(b"eqed");
+    pub const HIDDEN_COMMENT: u32 = make_ctrl_id(b"tcmt");
+    pub const DUTMAL: u32 = make_ctrl_id(b"tdut");
+    pub const CHAR_OVERLAP: u32 = make_ctrl_id(b"tcps");
+}
+
+/// Read the control ID of a CTRL_HEADER record
+pub(crate) fn read_ctrl_id(node: &RecordNode) -> u32 {
+    read_u32_le(node.payload(), 0).unwrap_or(0)
+}
+
+/// Read HWPUNIT16 (signed 16-bit) value
+pub(crate) fn read_hwpunit16(data: &[u8], offset: usize) -> HwpUnit {
+    HwpUnit(read_u16_le(data, offset).unwrap_or(0) as i16 as i32)
+}
+
+/// Read HWPUNIT (32-bit) value
+pub(crate) fn read_hwpunit(data: &[u8], offset: usize) -> HwpUnit {
+    HwpUnit(read_u32_le(data, offset).unwrap_or(0) as i32)
+}
+
+/// Read a WORD-length-prefixed WCHAR string; returns the offset after it
+pub(crate) fn read_string(data: &[u8], offset: usize) -> (String, usize) {
+    let len = read_u16_le(data, offset).unwrap_or(0) as usize;
+    let units: Vec<u16> = (0..len)
+        .map_while(|i| read_u16_le(data, offset + 2 + i * 2))
+        .collect();
+    (String::from_utf16_lossy(&units), offset + 2 + len * 2)
+}
+
+/// Read a WCHAR, treating 0 as absent
+fn read_wchar(data: &[u8], offset: usize) -> Option<char> {
+    read_u16_le(data, offset)
+        .filter(|&c| c != 0)
+        .and_then(|c| char::from_u32(c as u32))
+}
+
+/// Read the first string item of a control's CTRL_DATA parameter set
+/// (bookmark and field names are stored there)
+pub(crate) fn read_ctrl_data_string(node: &RecordNode) -> Option<String> {
+    let payload = node.child(HWPTAG_CTRL_DATA)?.payload();
+
+    // WORD set ID, INT16 item count, then items of WORD ID, WORD type, data
+    let count = read_u16_le(payload, 2)? as i16;
+    let mut offset = 4;
+    for _ in 0..count.max(0) {
+        let item_type = read_u16_le(payload, offset + 2)?;
+        offset += 4;
+        let size = match item_type {
+            0 => 0, // PIT_NULL
+            1 => {
+                // PIT_BSTR: WORD length, WCHAR[length]
+                let len = read_u16_le(payload, offset)? as usize;
+                let units: Vec<u16> = (0..len)
+                    .map(|i| read_u16_le(payload, offset + 2 + i * 2))
+                    .collect::<Option<_>>()?;
+                return Some(String::from_utf16_lossy(&units));
+            }
+            2 | 6 => 1,         // PIT_I1, PIT_UI1
+            3 | 7 => 2,         // PIT_I2, PIT_UI2
+            4 | 5 | 8 | 9 => 4, // PIT_I4, PIT_I, PIT_UI4, PIT_UI
+            _ => return None,   // Nested sets and arrays
+        };
+        offset += size;
+    }
+    None
+}
+
+/// Parse section definition control (secd) and its PAGE_DEF,
+/// FOOTNOTE_SHAPE and PAGE_BORDER_FILL child records into the section
+pub(crate) fn parse_section_def(node: &RecordNode, section: &mut Section) {
+    let payload = node.payload();
+
+    // Offsets include the 4-byte control ID
+    let attr = read_u32_le(payload, 4).unwrap_or(0);
+    section.text_direction =
+        TextDirection::from_u32((attr >> 16) & 0x07).unwrap_or(TextDirection::Horizontal);
+    section.column_gap = read_hwpunit16(payload, 8);
+    section.grid_vertical = read_hwpunit16(payload, 10);
+    section.grid_horizontal = read_hwpunit16(payload, 12);
+    section.default_tab_spacing = read_hwpunit(payload, 14);
+    section.start_numbers = StartNumbers {
+        page: read_u16_le(payload, 20).unwrap_or(0),
+        figure: read_u16_le(payload, 22).unwrap_or(0),
+        table: read_u16_le(payload, 24).unwrap_or(0),
+        equation: read_u16_le(payload, 26).unwrap_or(0),
+    };
+
+    let mut note_shapes = 0;
+    for child in &node.children {
+        match child.tagid() {
+            HWPTAG_PAGE_DEF => parse_page_def(child.payload(), section),
+            HWPTAG_FOOTNOTE_SHAPE => {
+                // First record is the footnote shape, second the endnote shape
+                let shape = parse_footnote_shape(child.payload());
+                if note_shapes == 0 {
+                    section.footnote_shape = shape;
+                } else {
+                    section.endnote_shape = shape;
+                }
+                note_shapes += 1;
+            }
+            HWPTAG_PAGE_BORDER_FILL => {
+                section
+                    .page_border_fills
+                    .push(parse_page_border_fill(child.payload()));
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Parse PAGE_DEF (용지 설정)
+fn parse_page_def(payload: &[u8], section: &mut Section) {
+    section.page_width = read_hwpunit(payload, 0);
+    section.page_height = read_hwpunit(payload, 4);
+    section.margin_left = read_hwpunit(payload, 8);
+    section.margin_right = read_hwpunit(payload, 12);
+    section.margin_top = read_hwpunit(payload, 16);
+    section.margin_bottom = read_hwpunit(payload, 20);
+    section.margin_header = read_hwpunit(payload, 24);
+    section.margin_footer = read_hwpunit(payload, 28);
+    section.margin_gutter = read_hwpunit(payload, 32);
+
+    let attr = read_u32_le(payload, 36).unwrap_or(0);
+    section.landscape = attr & 0x01 != 0;
+    section.binding = BindingType::from_u32((attr >> 1) & 0x03).unwrap_or(BindingType::SingleSided);
+}
+
+/// Parse FOOTNOTE_SHAPE (각주/미주 모양)
+fn parse_footnote_shape(payload: &[u8]) -> NoteShape {
+    let attr = read_u32_le(payload, 0).unwrap_or(0);
+
+    NoteShape {
+        number_format: NumberFormat::from_u8((attr & 0xFF) as u8).unwrap_or(NumberFormat::Digit),
+        placement: ((attr >> 8) & 0x03) as u8,
+        numbering: NoteNumbering::from_u32((attr >> 10) & 0x03).unwrap_or(NoteNumbering::Continue),
+        superscript: attr & (1 << 12) != 0,
+        user_symbol: read_wchar(payload, 4),
+        prefix: read_wchar(payload, 6),
+        suffix: read_wchar(payload, 8),
+        start_number: read_u16_le(payload, 10).unwrap_or(1),
+        separator_length: read_hwpunit16(payload, 12),
+        separator_margin_top: read_hwpunit16(payload, 14),
+        separator_margin_bottom: read_hwpunit16(payload, 16),
+        note_spacing: read_hwpunit16(payload, 18),
+        separator_line: Border {
+            style: line_style_from_hwp(read_u8(payload, 20).unwrap_or(0)),
+            width: border_width_from_hwp(read_u8(payload, 21).unwrap_or(0)),
+            color: Color(read_u32_le(payload, 22).unwrap_or(0)),
+        },
+    }
+}
+
+/// Parse PAGE_BORDER_FILL (쪽 테두리/배경)
+fn parse_page_border_fill(payload: &[u8]) -> PageBorderFill {
+    let attr = read_u32_le(payload, 0).unwrap_or(0);
+
+    PageBorderFill {
+        relative_to_paper: attr & 0x01 != 0,
+        include_header: attr & 0x02 != 0,
+        include_footer: attr & 0x04 != 0,
+        fill_area: ((attr >> 3) & 0x03) as u8,
+        spacing: Margin::new(
+            read_hwpunit16(payload, 4),
+            read_hwpunit16(payload, 8),
+            read_hwpunit16(payload, 6),
+            read_hwpunit16(payload, 10),
+        ),
+        border_fill_id: read_u16_le(payload, 12).unwrap_or(0) as u32,
+    }
+}
+
+/// Parse column definition control (cold)
+pub(crate) fn parse_column_def(node: &RecordNode) -> ColumnDef {
+    let payload = node.payload();
+
+    // Offsets include the 4-byte control ID
+    let attr = read_u16_le(payload, 4).unwrap_or(0);
+    let count = ((attr >> 2) & 0xFF).max(1) as u8;
+    let same_width = attr & (1 << 12) != 0;
+    let gap = read_hwpunit16(payload, 6);
+
+    let mut offset = 8;
+    let mut widths = Vec::new();
+    if !same_width {
+        for _ in 0..count {
+            widths.push(read_hwpunit16(payload, offset));
+            offset += 2;
+        }
+    }
+
+    // Upper 16 bits of the attribute, then the separator line
+    offset += 2;
+    let separator = Border {
+        style: line_style_from_hwp(read_u8(payload, offset).unwrap_or(0)),
+        width: border_width_from_hwp(read_u8(payload, offset + 1).unwrap_or(0)),
+        color: Color(read_u32_le(payload, offset + 2).unwrap_or(0)),
+    };
+
+    ColumnDef {
+        column_type: ColumnType::from_u16(attr & 0x03).unwrap_or(ColumnType::Normal),
+        count,
+        direction: ColumnDirection::from_u16((attr >> 10) & 0x03)
+            .unwrap_or(ColumnDirection::LeftToRight),
+        same_width,
+        gap,
+        widths,
+        separator,
+    }
+}
+
+/// Parse header/footer control (head, foot): apply-to attribute, then a
+/// paragraph list
+pub(crate) fn parse_header_footer(
+    node: &RecordNode,
+    ctx: &mut BodyTextContext,
+) -> HwpResult<HeaderFooter> {
+    let attr = read_u32_le(node.payload(), 4).unwrap_or(0);
+    let (list_header, blocks) = parse_list(node, ctx)?;
+
+    // Text area size follows the common list header fields
+    Ok(HeaderFooter {
+        apply_to: PageApply::from_u32(attr & 0x03).unwrap_or(PageApply::Both),
+        text_width: read_hwpunit(list_header, 8),
+        text_height: read_hwpunit(list_header, 12),
+        blocks,
+    })
+}
+
+/// Parse footnote/endnote control (fn, en) and its paragraph list
+pub(crate) fn parse_note(
+    node: &RecordNode,
+    kind: NoteKind,
+    ctx: &mut BodyTextContext,
+) -> HwpResult<Note> {
+    let id = ctx.next_object_id();
+    let number = read_u32_le(node.payload(), 4).unwrap_or(0);
+    let (_, blocks) = parse_list(node, ctx)?;
+
+    Ok(Note {
+        id,
+        kind,
+        number,
+        blocks,
+    })
+}
+
+/// Parse hidden comment control (tcmt): a paragraph list shown as a comment
+pub(crate) fn parse_hidden_comment(
+    node: &RecordNode,
+    ctx: &mut BodyTextContext,
+) -> HwpResult<Annotation> {
+    let id = ctx.next_object_id();
+    let (_, blocks) = parse_list(node, ctx)?;
+    Ok(Annotation {
+        id,
+        author: String::new(),
+        date: String::new(),
+        blocks,
+    })
+}
+
+/// Parse a control's LIST_HEADER child and the paragraphs following it as
+/// sibling records. Returns the LIST_HEADER payload and the paragraphs.
+pub(crate) fn parse_list<'a>(
+    node: &'a RecordNode,
+    ctx: &mut BodyTextContext,
+) -> HwpResult<(&'a [u8], Vec<Block>)> {
+    let Some(idx) = node
+        .children
+        .iter()
+        .position(|c| c.tagid() == HWPTAG_LIST_HEADER)
+    else {
+        return Ok((&[], Vec::new()));
+    };
+
+    let blocks = parse_paragraph_list(&node.children[idx + 1..], ctx)?;
+    Ok((node.children[idx].payload(), blocks))
+}
+
+/// Parse auto number control (atno)
+/// UINT32 attributes, UINT16 number, WCHAR user symbol, prefix, suffix
+pub(crate) fn parse_auto_number(node: &RecordNode) -> AutoNumber {
+    let payload = node.payload();
+    let attr = read_u32_le(payload, 4).unwrap_or(0);
+
+    AutoNumber {
+        kind: NumberKind::from_u32(attr & 0x0F).unwrap_or(NumberKind::Page),
+        format: NumberFormat::from_u8(((attr >> 4) & 0xFF) as u8).unwrap_or(NumberFormat::Digit),
+        superscript: attr & (1 << 12) != 0,
+        number: read_u16_le(payload, 8).unwrap_or(0),
+        user_symbol: read_wchar(payload, 10),
+        prefix: read_wchar(payload, 12),
+        suffix: read_wchar(payload, 14),
+    }
+}
+
+/// Parse new number control (nwno): UINT32 attributes, UINT16 number
+pub(crate) fn parse_new_number(node: &RecordNode) -> NewNumber {
+    let payload = node.payload();
+    let attr = read_u32_le(payload, 4).unwrap_or(0);
+
+    NewNumber {
+        kind: NumberKind::from_u32(attr & 0x0F).unwrap_or(NumberKind::Page),
+        number: read_u16_le(payload, 8).unwrap_or(1),
+    }
+}
+
+/// Parse dutmal control (tdut): base text and dutmal as WCHAR strings,
+/// then UINT32 position, size ratio, options, style ID and alignment
+pub(crate) fn parse_dutmal(node: &RecordNode) -> Dutmal {
+    let payload = node.payload();
+    let (base, offset) = read_string(payload, 4);
+    let (text, offset) = read_string(payload, offset);
+    let ratio = read_u32_le(payload, offset + 4).unwrap_or(0);
+
+    Dutmal {
+        base,
+        text,
+        position: DutmalPosition::from_u32(read_u32_le(payload, offset).unwrap_or(0))
+            .unwrap_or(DutmalPosition::Above),
+        size_ratio: if ratio == 0 { 50 } else { ratio },
+        align: DutmalAlign::from_u32(read_u32_le(payload, offset + 16).unwrap_or(3))
+            .unwrap_or(DutmalAlign::Center),
+        char_shape_id: 0,
+    }
+}
+
+/// Parse overlapping characters control (tcps): the characters as a
+/// WCHAR string, UINT8 border type, INT8 inner size, then char shapes of
+/// the border characters, which we do not use
+pub(crate) fn parse_char_overlap(node: &RecordNode) -> CharOverlap {
+    let payload = node.payload();
+    let (chars, offset) = read_string(payload, 4);
+
+    CharOverlap {
+        chars,
+        border: OverlapBorder::from_u8(read_u8(payload, offset).unwrap_or(0))
+            .unwrap_or(OverlapBorder::None),
+        inner_size: read_u8(payload, offset + 1).unwrap_or(0) as i8,
+        char_shape_id: 0,
+    }
+}
+
+/// Parse page number position control (pgnp)
+/// UINT32 attributes, WCHAR user symbol, prefix, suffix, dash
+pub(crate) fn parse_page_number_position(node: &RecordNode) -> PageNumberPosition {
+    let payload = node.payload();
+    let attr = read_u32_le(payload, 4).unwrap_or(0);
+
+    PageNumberPosition {
+        format: NumberFormat::from_u8((attr & 0xFF) as u8).unwrap_or(NumberFormat::Digit),
+        place: PageNumberPlace::from_u32((attr >> 8) & 0x0F).unwrap_or(PageNumberPlace::None),
+        user_symbol: read_wchar(payload, 8),
+        prefix: read_wchar(payload, 10),
+        suffix: read_wchar(payload, 12),
+        dash: read_wchar(payload, 14),
+    }
+}
+
+/// Parse page hiding control (pghd): UINT32 attributes
+pub(crate) fn parse_page_hiding(node: &RecordNode) -> PageOverride {
+    let attr = read_u32_le(node.payload(), 4).unwrap_or(0);
+    PageOverride {
+        hide_header: attr & 0x01 != 0,
+        hide_footer: attr & 0x02 != 0,
+        hide_page_number: attr & 0x20 != 0,
+        ..PageOverride::new()
+    }
+}
+
+/// Parse odd/even page adjust control (pgct): UINT32 attributes
+pub(crate) fn parse_page_odd_even(node: &RecordNode) -> PageOverride {
+    let attr = read_u32_le(node.payload(), 4).unwrap_or(0);
+    PageOverride {
+        parity: PageApply::from_u32(attr & 0x03).unwrap_or(PageApply::Both),
+        ..PageOverride::new()
+    }
+}
+
+/// Parse common object attributes (개체 공통 속성) following the control ID
+/// of a CTRL_HEADER; the caption, if any, is parsed separately
+pub(crate) fn parse_object_placement(payload: &[u8]) -> ObjectPlacement {
+    let attr = read_u32_le(payload, 4).unwrap_or(0);
+    let size_rel = |val: u32| match val {
+        0 => SizeRelativeTo::Paper,
+        1 => SizeRelativeTo::Page,
+        2 => SizeRelativeTo::Column,
+        3 => SizeRelativeTo::Paragraph,
+        _ => SizeRelativeTo::Absolute,
+    };
+
+    ObjectPlacement {
+        treat_as_char: attr & 0x01 != 0,
+        affect_line_spacing: attr & 0x04 != 0,
+        // Vertical: paper, page, paragraph; horizontal: paper, page, column, paragraph
+        vert_rel: match (attr >> 3) & 0x03 {
+            0 => RelativeTo::Paper,
+            1 => RelativeTo::Page,
+            _ => RelativeTo::Paragraph,
+        },
+        vert_align: ObjectAlign::from_u32((attr >> 5) & 0x07).unwrap_or(ObjectAlign::Start),
+        horz_rel: match (attr >> 8) & 0x03 {
+            0 => RelativeTo::Paper,
+            1 => RelativeTo::Page,
+            2 => RelativeTo::Column,
+            _ => RelativeTo::Paragraph,
+        },
+        horz_align: ObjectAlign::from_u32((attr >> 10) & 0x07).unwrap_or(ObjectAlign::Start),
+        flow_with_text: attr & (1 << 13) != 0,
+        allow_overlap: attr & (1 << 14) != 0,
+        width_rel: size_rel((attr >> 15) & 0x07),
+        // Height: paper, page, absolute
+        height_rel: match (attr >> 18) & 0x03 {
+            0 => SizeRelativeTo::Paper,
+            1 => SizeRelativeTo::Page,
+            _ => SizeRelativeTo::Absolute,
+        },
+        wrap: TextWrap::from_u32((attr >> 21) & 0x07).unwrap_or(TextWrap::Square),
+        text_flow: TextFlow::from_u32((attr >> 24) & 0x03).unwrap_or(TextFlow::BothSides),
+        offset_y: read_hwpunit(payload, 8),
+        offset_x: read_hwpunit(payload, 12),
+        width: read_hwpunit(payload, 16),
+        height: read_hwpunit(payload, 20),
+        z_order: read_u32_le(payload, 24).unwrap_or(0) as i32,
+        outer_margin: Margin::new(
+            read_hwpunit16(payload, 28),
+            read_hwpunit16(payload, 32),
+            read_hwpunit16(payload, 30),
+            read_hwpunit16(payload, 34),
+        ),
+        caption: None,
+    }
+}
+
+/// Parse an object's caption: a LIST_HEADER among the control's children
+/// ahead of the object's own record, followed by the caption paragraphs
+pub(crate) fn parse_caption(
+    node: &RecordNode,
+    object_tag: u16,
+    ctx: &mut BodyTextContext,
+) -> HwpResult<Option<Caption>> {
+    let children = &node.children;
+    let Some(idx) = children
+        .iter()
+        .take_while(|c| c.tagid() != object_tag)
+        .position(|c| c.tagid() == HWPTAG_LIST_HEADER)
+    else {
+        return Ok(None);
+    };
+    let end = children[idx + 1..]
+        .iter()
+        .position(|c| c.tagid() != HWPTAG_PARA_HEADER)
+        .map_or(children.len(), |n| idx + 1 + n);
+
+    // Caption attributes follow the common list header fields:
+    // UINT32 attributes (bits 0-1 side, bit 2 full width), HWPUNIT width,
+    // HWPUNIT16 gap, HWPUNIT maximum text width
+    let payload = children[idx].payload();
+    let attr = read_u32_le(payload, 8).unwrap_or(0);
+    Ok(Some(Caption {
+        side: CaptionSide::from_u32(attr & 0x03).unwrap_or(CaptionSide::Bottom),
+        full_width: attr & 0x04 != 0,
+        width: read_hwpunit(payload, 12),
+        gap: read_hwpunit16(payload, 16),
+        blocks: parse_paragraph_list(&children[idx + 1..end], ctx)?,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Record;
+
+    fn node(tagid: u16, payload: Vec<u8>, children: Vec<RecordNode>) -> RecordNode {
+        RecordNode {
+            record: Record {
+                tagid,
+                tagname: String::new(),
+                level: 0,
+                size: payload.len() as u32,
+                payload,
+            },
+            children,
+        }
+    }
+
+    #[test]
+    fn test_read_ctrl_data_string() {
+        // Set 0x021B with an integer item followed by the name string
+        let mut data = vec![0x1B, 0x02, 2, 0];
+        data.extend([0x00, 0x40, 4, 0, 7, 0, 0, 0]);
+        data.extend([0x00, 0x40, 1, 0, 2, 0]);
+        data.extend("책갈".encode_utf16().flat_map(|u| u.to_le_bytes()));
+
+        let ctrl = node(
+            HWPTAG_CTRL_HEADER,
+            ctrl_id::BOOKMARK.to_le_bytes().to_vec(),
+            vec![node(HWPTAG_CTRL_DATA, data, Vec::new())],
+        );
+        assert_eq!(read_ctrl_data_string(&ctrl), Some("책갈".to_string()));
+    }
+
+    #[test]
+    fn test_parse_auto_number() {
+        // Table number, circled digits, superscript, number 3, suffix ')'
+        let mut payload = ctrl_id::AUTO_NUMBER.to_le_bytes().to_vec();
+        payload.extend((4u32 | (1 << 4) | (1 << 12)).to_le_bytes());
+        payload.extend([3, 0, 0, 0, 0, 0, b')', 0]);
+
+        let number = parse_auto_number(&node(HWPTAG_CTRL_HEADER, payload, Vec::new()));
+        assert_eq!(number.kind, NumberKind::Table);
+        assert_eq!(number.format, NumberFormat::CircledDigit);
+        assert!(number.superscript);
+        assert_eq!(number.number, 3);
+        assert_eq!((number.prefix, number.suffix), (None, Some(')')));
+    }
+
+    #[test]
+    fn test_parse_object_placement() {
+        // Page-relative, centered horizontally, in front of text, right side only
+        let attr: u32 = (1 << 3) | (1 << 10) | (1 << 13) | (5 << 21) | (2 << 24);
+        let mut payload = ctrl_id::SHAPE_OBJECT.to_le_bytes().to_vec();
+        payload.extend(attr.to_le_bytes());
+        for value in [1440i32, 720, 7200, 3600, 3] {
+            payload.extend(value.to_le_bytes());
+        }
+        payload.extend([100u16, 200, 0, 0].iter().flat_map(|m| m.to_le_bytes()));
+
+        let placement = parse_object_placement(&payload);
+        assert!(!placement.treat_as_char);
+        assert_eq!(placement.vert_rel, RelativeTo::Page);
+        assert_eq!(placement.horz_align, ObjectAlign::Center);
+        assert!(placement.flow_with_text);
+        assert_eq!(placement.wrap, TextWrap::InFrontOfText);
+        assert_eq!(placement.text_flow, TextFlow::RightOnly);
+        assert_eq!((placement.offset_y.0, placement.offset_x.0), (1440, 720));
+        assert_eq!((placement.width.0, placement.height.0), (7200, 3600));
+        assert_eq!(placement.z_order, 3);
+        assert_eq!(
+            (
+                placement.outer_margin.left.0,
+                placement.outer_margin.right.0
+            ),
+            (100, 200)
+        );
+    }
+}
@@ -0,0 +1,788 @@
+use super::bodytext::BodyTextContext;
+use super::control::{parse_caption, parse_object_placement, read_hwpunit, read_string};
+use crate::common::{Color, HwpResult, read_u16_le, read_u32_le};
+use crate::model::{Equation, EquationAlign, EquationNode, EquationSpace, EquationStyle};
+use crate::parser::RecordNode;
+use crate::parser::tags::*;
+
+/// Parse equation control (eqed): common object attributes and an EQEDIT
+/// record holding the equation script
+pub(crate) fn parse_equation(node: &RecordNode, ctx: &mut BodyTextContext) -> HwpResult<Equation> {
+    let mut placement = parse_object_placement(node.payload());
+    placement.caption = parse_caption(node, HWPTAG_EQEDIT, ctx)?;
+    let payload = node.child(HWPTAG_EQEDIT).map_or(&[][..], |n| n.payload());
+
+    // UINT32 attributes, script string, HWPUNIT character size, COLORREF
+    // color, INT16 baseline, then the version and font name strings
+    let (script, offset) = read_string(payload, 4);
+    let (version, end) = read_string(payload, offset + 10);
+    let (font_name, _) = read_string(payload, end);
+    Ok(Equation {
+        id: ctx.next_object_id(),
+        root: parse_script(&script),
+        script,
+        font_size: read_hwpunit(payload, offset),
+        color: Color(read_u32_le(payload, offset + 4).unwrap_or(0)),
+        baseline: read_u16_le(payload, offset + 8).unwrap_or(0) as i16,
+        version,
+        font_name,
+        placement,
+    })
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Open,  // {
+    Close, // }
+    RowSeparator,
+    ColumnSeparator,
+    Superscript,
+    Subscript,
+    Space(EquationSpace),
+    Word(String),
+    Number(String),
+    Symbol(String),
+    Text(String),
+}
+
+/// Operators written with several characters, longest first
+const MULTI_CHAR_SYMBOLS: &[(&str, &str)] = &[
+    ("<=>", "⇔"),
+    ("<->", "↔"),
+    ("->", "→"),
+    ("<-", "←"),
+    ("=>", "⇒"),
+    ("<=", "≤"),
+    (">=", "≥"),
+    ("!=", "≠"),
+    ("==", "≡"),
+    ("+-", "±"),
+    ("-+", "∓"),
+    ("<<", "≪"),
+    (">>", "≫"),
+];
+
+fn tokenize(script: &str) -> Vec<Token> {
+    let chars: Vec<char> = script.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let rest: String = chars[i..chars.len().min(i + 3)].iter().collect();
+        if let Some((text, symbol)) = MULTI_CHAR_SYMBOLS
+            .iter()
+            .find(|(text, _)| rest.starts_with(text))
+        {
+            tokens.push(Token::Symbol(symbol.to_string()));
+            i += text.chars().count();
+            continue;
+        }
+
+        let start = i;
+        i += 1;
+        match c {
+            _ if c.is_whitespace() => {}
+            '{' => tokens.push(Token::Open),
+            '}' => tokens.push(Token::Close),
+            '#' => tokens.push(Token::RowSeparator),
+            '&' => tokens.push(Token::ColumnSeparator),
+            '^' => tokens.push(Token::Superscript),
+            '_' => tokens.push(Token::Subscript),
+            '~' => tokens.push(Token::Space(EquationSpace::Normal)),
+            '`' => tokens.push(Token::Space(EquationSpace::Thin)),
+            '"' => {
+                while i < chars.len() && chars[i] != '"' {
+                    i += 1;
+                }
+                tokens.push(Token::Text(chars[start + 1..i].iter().collect()));
+                i += 1;
+            }
+            // Escaped braces and other characters stand for themselves
+            '\\' if i < chars.len() => {
+                tokens.push(Token::Symbol(chars[i].to_string()));
+                i += 1;
+            }
+            _ if c.is_ascii_alphabetic() => {
+                while i < chars.len() && chars[i].is_ascii_alphabetic() {
+                    i += 1;
+                }
+                tokens.push(Token::Word(chars[start..i].iter().collect()));
+            }
+            _ if c.is_ascii_digit()
+                || (c == '.' && chars.get(i).is_some_and(char::is_ascii_digit)) =>
+            {
+                while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                    i += 1;
+                }
+                tokens.push(Token::Number(chars[start..i].iter().collect()));
+            }
+            // Hangul and other letters are plain text
+            _ if c.is_alphabetic() => {
+                while i < chars.len() && chars[i].is_alphabetic() && !chars[i].is_ascii() {
+                    i += 1;
+                }
+                tokens.push(Token::Text(chars[start..i].iter().collect()));
+            }
+            '-' => tokens.push(Token::Symbol("−".to_string())),
+            '\'' => tokens.push(Token::Symbol("′".to_string())),
+            _ => tokens.push(Token::Symbol(c.to_string())),
+        }
+    }
+    tokens
+}
+
+const GREEK: &[(&str, char, char)] = &[
+    ("alpha", 'α', 'Α'),
+    ("beta", 'β', 'Β'),
+    ("gamma", 'γ', 'Γ'),
+    ("delta", 'δ', 'Δ'),
+    ("epsilon", 'ε', 'Ε'),
+    ("zeta", 'ζ', 'Ζ'),
+    ("eta", 'η', 'Η'),
+    ("theta", 'θ', 'Θ'),
+    ("iota", 'ι', 'Ι'),
+    ("kappa", 'κ', 'Κ'),
+    ("lambda", 'λ', 'Λ'),
+    ("mu", 'μ', 'Μ'),
+    ("nu", 'ν', 'Ν'),
+    ("xi", 'ξ', 'Ξ'),
+    ("omicron", 'ο', 'Ο'),
+    ("pi", 'π', 'Π'),
+    ("rho", 'ρ', 'Ρ'),
+    ("sigma", 'σ', 'Σ'),
+    ("tau", 'τ', 'Τ'),
+    ("upsilon", 'υ', 'Υ'),
+    ("phi", 'φ', 'Φ'),
+    ("chi", 'χ', 'Χ'),
+    ("psi", 'ψ', 'Ψ'),
+    ("omega", 'ω', 'Ω'),
+];
+
+/// Greek letter: lowercase name for the small letter, capitalized or
+/// uppercase name for the capital
+fn greek_letter(word: &str) -> Option<char> {
+    let lower = word.to_ascii_lowercase();
+    let &(_, small, capital) = GREEK.iter().find(|(name, _, _)| *name == lower)?;
+    if word == lower {
+        Some(small)
+    } else {
+        Some(capital)
+    }
+}
+
+/// Arrows; the uppercase names are double arrows
+const ARROWS: &[(&str, &str)] = &[
+    ("rarrow", "→"),
+    ("larrow", "←"),
+    ("lrarrow", "↔"),
+    ("uparrow", "↑"),
+    ("downarrow", "↓"),
+    ("RARROW", "⇒"),
+    ("LARROW", "⇐"),
+    ("LRARROW", "⇔"),
+    ("UPARROW", "⇑"),
+    ("DOWNARROW", "⇓"),
+    ("mapsto", "↦"),
+];
+
+/// Symbol keywords, matched case-insensitively
+const SYMBOLS: &[(&str, &str)] = &[
+    ("times", "×"),
+    ("div", "÷"),
+    ("divide", "÷"),
+    ("cdot", "⋅"),
+    ("pm", "±"),
+    ("mp", "∓"),
+    ("le", "≤"),
+    ("leq", "≤"),
+    ("ge", "≥"),
+    ("geq", "≥"),
+    ("ne", "≠"),
+    ("neq", "≠"),
+    ("approx", "≈"),
+    ("sim", "∼"),
+    ("simeq", "≃"),
+    ("cong", "≅"),
+    ("equiv", "≡"),
+    ("propto", "∝"),
+    ("inf", "∞"),
+    ("infty", "∞"),
+    ("partial", "∂"),
+    ("nabla", "∇"),
+    ("therefore", "∴"),
+    ("because", "∵"),
+    ("in", "∈"),
+    ("notin", "∉"),
+    ("owns", "∋"),
+    ("subset", "⊂"),
+    ("supset", "⊃"),
+    ("subseteq", "⊆"),
+    ("supseteq", "⊇"),
+    ("cup", "∪"),
+    ("cap", "∩"),
+    ("emptyset", "∅"),
+    ("forall", "∀"),
+    ("exist", "∃"),
+    ("exists", "∃"),
+    ("neg", "¬"),
+    ("wedge", "∧"),
+    ("vee", "∨"),
+    ("oplus", "⊕"),
+    ("otimes", "⊗"),
+    ("odot", "⊙"),
+    ("circ", "∘"),
+    ("bullet", "•"),
+    ("star", "⋆"),
+    ("cdots", "⋯"),
+    ("ldots", "…"),
+    ("vdots", "⋮"),
+    ("ddots", "⋱"),
+    ("angle", "∠"),
+    ("perp", "⊥"),
+    ("triangle", "△"),
+    ("deg", "°"),
+    ("prime", "′"),
+    ("aleph", "ℵ"),
+    ("hbar", "ℏ"),
+    ("ell", "ℓ"),
+    ("dagger", "†"),
+];
+
+/// Large operators taking `from` and `to` limits
+const LARGE_OPERATORS: &[(&str, &str)] = &[
+    ("sum", "∑"),
+    ("prod", "∏"),
+    ("coprod", "∐"),
+    ("int", "∫"),
+    ("dint", "∬"),
+    ("tint", "∭"),
+    ("oint", "∮"),
+    ("odint", "∯"),
+    ("otint", "∰"),
+    ("union", "⋃"),
+    ("bigcup", "⋃"),
+    ("inter", "⋂"),
+    ("bigcap", "⋂"),
+    ("lim", "lim"),
+];
+
+const FUNCTIONS: &[&str] = &[
+    "sin", "cos", "tan", "cot", "sec", "csc", "arcsin", "arccos", "arctan", "sinh", "cosh", "tanh",
+    "coth", "log", "ln", "lg", "exp", "det", "max", "min", "mod", "gcd", "lcm", "dim", "ker",
+    "arg", "hom",
+];
+
+/// Accents over (or, for `under`, below) their argument
+const ACCENTS: &[(&str, &str)] = &[
+    ("bar", "¯"),
+    ("hat", "^"),
+    ("vec", "→"),
+    ("dyad", "↔"),
+    ("dot", "˙"),
+    ("ddot", "¨"),
+    ("tilde", "˜"),
+    ("acute", "´"),
+    ("grave", "`"),
+    ("check", "ˇ"),
+    ("arch", "⌒"),
+    ("under", "_"),
+];
+
+/// Delimiter names accepted after `left` and `right`
+fn delimiter(word: &str) -> Option<&'static str> {
+    Some(match word.to_ascii_lowercase().as_str() {
+        "lbrace" => "{",
+        "rbrace" => "}",
+        "langle" => "⟨",
+        "rangle" => "⟩",
+        "lfloor" => "⌊",
+        "rfloor" => "⌋",
+        "lceil" => "⌈",
+        "rceil" => "⌉",
+        "line" => "|",
+        "dline" => "‖",
+        _ => return None,
+    })
+}
+
+/// Nesting depth past which a script is kept as plain text, so that
+/// deeply nested scripts cannot exhaust the stack
+const MAX_DEPTH: usize = 64;
+
+/// Recursive descent parser over script tokens
+struct ScriptParser {
+    tokens: Vec<Token>,
+    pos: usize,
+    left_depth: usize, // Open `left` delimiters awaiting `right`
+    depth: usize,      // Nesting of the node being parsed
+    too_deep: bool,    // MAX_DEPTH was reached and the rest skipped
+}
+
+impl ScriptParser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn peek_word(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some(Token::Word(word)) if word.eq_ignore_ascii_case(keyword))
+    }
+
+    /// Enter one more level of nesting; past MAX_DEPTH the rest of the
+    /// script is skipped
+    fn descend(&mut self) -> bool {
+        if self.depth == MAX_DEPTH {
+            self.too_deep = true;
+            self.pos = self.tokens.len();
+            return false;
+        }
+        self.depth += 1;
+        true
+    }
+
+    /// Parse a nested item, or give `empty` when nested too deeply
+    fn nested<T>(&mut self, empty: T, parse: impl FnOnce(&mut Self) -> T) -> T {
+        if !self.descend() {
+            return empty;
+        }
+        let result = parse(self);
+        self.depth -= 1;
+        result
+    }
+
+    /// Whether the next token ends the current row
+    fn at_row_end(&self) -> bool {
+        match self.peek() {
+            None
+            | Some(Token::Close)
+            | Some(Token::RowSeparator)
+            | Some(Token::ColumnSeparator) => true,
+            Some(_) => self.left_depth > 0 && self.peek_word("right"),
+        }
+    }
+
+    /// Items up to the end of the row; `over` and `atop` take the item
+    /// before and after them, font commands apply to the rest of the row
+    fn parse_row(&mut self) -> Vec<EquationNode> {
+        let mut items = Vec::new();
+        let mut fractions = 0; // Length of the `a over b over c` chain last parsed
+        while !self.at_row_end() {
+            if self.peek_word("over") || self.peek_word("atop") {
+                let numerator = items.pop().unwrap_or(EquationNode::Row(Vec::new()));
+                // Only a fraction as numerator nests; others start a new chain
+                if !matches!(numerator, EquationNode::Fraction { .. }) {
+                    self.depth -= fractions;
+                    fractions = 0;
+                }
+                if !self.descend() {
+                    break;
+                }
+                fractions += 1;
+                let line = self.peek_word("over");
+                self.pos += 1;
+                let denominator = self.parse_term();
+                items.push(EquationNode::Fraction {
+                    numerator: Box::new(numerator),
+                    denominator: Box::new(denominator),
+                    line,
+                });
+                continue;
+            }
+            let style = match self.peek() {
+                Some(Token::Word(word)) => match word.to_ascii_lowercase().as_str() {
+                    "rm" => Some(EquationStyle::Roman),
+                    "it" => Some(EquationStyle::Italic),
+                    "bold" => Some(EquationStyle::Bold),
+                    _ => None,
+                },
+                _ => None,
+            };
+            if let Some(style) = style {
+                self.pos += 1;
+                let rest = self.nested(Vec::new(), Self::parse_row);
+                items.push(EquationNode::Styled {
+                    style,
+                    body: Box::new(row(rest)),
+                });
+                break;
+            }
+            items.push(self.parse_term());
+        }
+        self.depth -= fractions;
+        items
+    }
+
+    /// Rows of cells separated by `#` and `&`, up to a closing brace (left
+    /// for the caller) or the end of the script
+    fn parse_table(&mut self) -> Vec<Vec<EquationNode>> {
+        let mut rows = Vec::new();
+        let mut cells = Vec::new();
+        loop {
+            cells.push(row(self.parse_row()));
+            match self.peek() {
+                Some(Token::ColumnSeparator) => self.pos += 1,
+                Some(Token::RowSeparator) => {
+                    self.pos += 1;
+                    rows.push(std::mem::take(&mut cells));
+                }
+                _ => break,
+            }
+        }
+        rows.push(cells);
+        rows
+    }
+
+    /// A braced table argument of matrix, cases and pile
+    fn parse_braced_table(&mut self) -> Vec<Vec<EquationNode>> {
+        if self.peek() != Some(&Token::Open) {
+            return vec![vec![self.parse_atom()]];
+        }
+        self.pos += 1;
+        let rows = self.parse_table();
+        if self.peek() == Some(&Token::Close) {
+            self.pos += 1;
+        }
+        rows
+    }
+
+    /// An atom with optional subscript and superscript
+    fn parse_term(&mut self) -> EquationNode {
+        let base = self.parse_atom();
+        let (mut sub, mut sup) = (None, None);
+        loop {
+            let is_sub = match self.peek() {
+                Some(Token::Subscript) => true,
+                Some(Token::Superscript) => false,
+                _ if self.peek_word("sub") => true,
+                _ if self.peek_word("sup") => false,
+                _ => break,
+            };
+            self.pos += 1;
+            let script = Some(Box::new(self.parse_atom()));
+            if is_sub {
+                sub = script;
+            } else {
+                sup = script;
+            }
+        }
+        if sub.is_none() && sup.is_none() {
+            return base;
+        }
+        EquationNode::Script {
+            base: Box::new(base),
+            sub,
+            sup,
+        }
+    }
+
+    /// A single item; separators and closing braces are left in place
+    fn parse_atom(&mut self) -> EquationNode {
+        match self.peek() {
+            None
+            | Some(Token::Close)
+            | Some(Token::RowSeparator)
+            | Some(Token::ColumnSeparator)
+            | Some(Token::Superscript)
+            | Some(Token::Subscript) => return EquationNode::Row(Vec::new()),
+            _ => {}
+        }
+        let empty = EquationNode::Row(Vec::new());
+        match self.next() {
+            Some(Token::Open) => self.nested(empty, |parser| {
+                let rows = parser.parse_table();
+                if parser.peek() == Some(&Token::Close) {
+                    parser.pos += 1;
+                }
+                table(EquationAlign::Center, rows)
+            }),
+            Some(Token::Number(number)) => EquationNode::Number(number),
+            Some(Token::Symbol(symbol)) => EquationNode::Operator(symbol),
+            Some(Token::Text(text)) => EquationNode::Text(text),
+            Some(Token::Space(space)) => EquationNode::Space(space),
+            Some(Token::Word(word)) => self.nested(empty, |parser| parser.parse_word(&word)),
+            _ => empty,
+        }
+    }
+
+    fn parse_word(&mut self, word: &str) -> EquationNode {
+        if let Some(letter) = greek_letter(word) {
+            return EquationNode::Identifier(letter.to_string());
+        }
+        if let Some((_, arrow)) = ARROWS.iter().find(|(name, _)| *name == word) {
+            return EquationNode::Operator(arrow.to_string());
+        }
+
+        let lower = word.to_ascii_lowercase();
+        let lookup = |table: &[(&str, &'static str)]| {
+            table
+                .iter()
+                .find(|(name, _)| *name == lower)
+                .map(|(_, value)| *value)
+        };
+        if let Some(symbol) = lookup(SYMBOLS) {
+            return EquationNode::Operator(symbol.to_string());
+        }
+        if let Some(operator) = lookup(LARGE_OPERATORS) {
+            let mut limit = |keyword: &str| {
+                self.peek_word(keyword).then(|| {
+                    self.pos += 1;
+                    Box::new(self.parse_atom())
+                })
+            };
+            let from = limit("from");
+            let to = limit("to");
+            return EquationNode::LargeOperator {
+                operator: operator.to_string(),
+                from,
+                to,
+            };
+        }
+        if let Some(accent) = lookup(ACCENTS) {
+            return EquationNode::Accent {
+                accent: accent.to_string(),
+                under: lower == "under",
+                body: Box::new(self.parse_atom()),
+            };
+        }
+        if FUNCTIONS.contains(&lower.as_str()) {
+            return EquationNode::Function(lower);
+        }
+
+        match lower.as_str() {
+            "sqrt" => EquationNode::Root {
+                index: None,
+                radicand: Box::new(self.parse_atom()),
+            },
+            "root" => {
+                let index = self.parse_atom();
+                if self.peek_word("of") {
+                    self.pos += 1;
+                }
+                EquationNode::Root {
+                    index: Some(Box::new(index)),
+                    radicand: Box::new(self.parse_atom()),
+                }
+            }
+            "matrix" | "pmatrix" | "bmatrix" | "dmatrix" => {
+                let matrix = EquationNode::Table {
+                    align: EquationAlign::Center,
+                    rows: self.parse_braced_table(),
+                };
+                let (open, close) = match lower.as_str() {
+                    "pmatrix" => ("(", ")"),
+                    "bmatrix" => ("[", "]"),
+                    "dmatrix" => ("|", "|"),
+                    _ => return matrix,
+                };
+                fenced(open, close, matrix)
+            }
+            "cases" => {
+                let cases = EquationNode::Table {
+                    align: EquationAlign::Left,
+                    rows: self.parse_braced_table(),
+                };
+                fenced("{", "", cases)
+            }
+            "pile" | "lpile" | "rpile" => {
+                let align = match lower.as_str() {
+                    "lpile" => EquationAlign::Left,
+                    "rpile" => EquationAlign::Right,
+                    _ => EquationAlign::Center,
+                };
+                EquationNode::Table {
+                    align,
+                    rows: self.parse_braced_table(),
+                }
+            }
+            "left" => {
+                let open = self.parse_delimiter();
+                self.left_depth += 1;
+                let body = row(self.parse_row());
+                self.left_depth -= 1;
+                let close = if self.peek_word("right") {
+                    self.pos += 1;
+                    self.parse_delimiter()
+                } else {
+                    String::new()
+                };
+                fenced(&open, &close, body)
+            }
+            // `right` without a matching `left`
+            "right" => EquationNode::Row(Vec::new()),
+            _ => EquationNode::Identifier(word.to_string()),
+        }
+    }
+
+    /// Delimiter after `left` or `right`; `.` stands for none
+    fn parse_delimiter(&mut self) -> String {
+        let delimiter = match self.peek() {
+            Some(Token::Symbol(symbol)) if symbol == "." => String::new(),
+            Some(Token::Symbol(symbol)) => symbol.replace('−', "-"),
+            Some(Token::Word(word)) => match delimiter(word) {
+                Some(delimiter) => delimiter.to_string(),
+                None => return String::new(),
+            },
+            _ => return String::new(),
+        };
+        self.pos += 1;
+        delimiter
+    }
+}
+
+/// A row, unwrapped when it holds a single item
+fn row(mut items: Vec<EquationNode>) -> EquationNode {
+    if items.len() == 1 {
+        items.pop().unwrap()
+    } else {
+        EquationNode::Row(items)
+    }
+}
+
+/// A table, unwrapped when it holds a single cell
+fn table(align: EquationAlign, mut rows: Vec<Vec<EquationNode>>) -> EquationNode {
+    if rows.len() == 1 && rows[0].len() == 1 {
+        rows[0].pop().unwrap()
+    } else {
+        EquationNode::Table { align, rows }
+    }
+}
+
+fn fenced(open: &str, close: &str, body: EquationNode) -> EquationNode {
+    EquationNode::Fenced {
+        open: open.to_string(),
+        close: close.to_string(),
+        body: Box::new(body),
+    }
+}
+
+/// Parse an HWP equation script into its node tree. Lines separated by `#`
+/// at the top level become a centered pile; a script nested too deeply is
+/// kept as plain text.
+pub(crate) fn parse_script(script: &str) -> EquationNode {
+    let mut parser = ScriptParser {
+        tokens: tokenize(script),
+        pos: 0,
+        left_depth: 0,
+        depth: 0,
+        too_deep: false,
+    };
+    let mut rows = parser.parse_table();
+    // Skip stray closing braces, continuing the current line
+    while parser.peek().is_some() {
+        parser.pos += 1;
+        let mut more = parser.parse_table().into_iter();
+        if let (Some(last), Some(first)) = (rows.last_mut(), more.next()) {
+            let empty = EquationNode::Row(Vec::new());
+            last.extend(first.into_iter().filter(|cell| *cell != empty));
+        }
+        rows.extend(more);
+    }
+    if parser.too_deep {
+        return EquationNode::Text(script.to_string());
+    }
+    // `&` only marks alignment points at the top level; lines stay whole
+    let rows = rows.into_iter().map(|cells| vec![row(cells)]).collect();
+    table(EquationAlign::Center, rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ident(name: &str) -> EquationNode {
+        EquationNode::Identifier(name.to_string())
+    }
+
+    #[test]
+    fn test_parse_script_fraction_root_and_sum() {
+        // `over` takes the items next to it, not the whole row
+        assert_eq!(
+            parse_script("a + {b} over c"),
+            EquationNode::Row(vec![
+                ident("a"),
+                EquationNode::Operator("+".to_string()),
+                EquationNode::Fraction {
+                    numerator: Box::new(ident("b")),
+                    denominator: Box::new(ident("c")),
+                    line: true,
+                },
+            ])
+        );
+        assert_eq!(
+            parse_script("SQRT {x^2}"),
+            EquationNode::Root {
+                index: None,
+                radicand: Box::new(EquationNode::Script {
+                    base: Box::new(ident("x")),
+                    sub: None,
+                    sup: Some(Box::new(EquationNode::Number("2".to_string()))),
+                }),
+            }
+        );
+        assert_eq!(
+            parse_script("sum from {i=1} to n alpha"),
+            EquationNode::Row(vec![
+                EquationNode::LargeOperator {
+                    operator: "∑".to_string(),
+                    from: Some(Box::new(EquationNode::Row(vec![
+                        ident("i"),
+                        EquationNode::Operator("=".to_string()),
+                        EquationNode::Number("1".to_string()),
+                    ]))),
+                    to: Some(Box::new(ident("n"))),
+                },
+                ident("α"),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_script_matrix_and_fences() {
+        let EquationNode::Fenced { open, close, body } = parse_script("pmatrix{1 & 0 # 0 & 1}")
+        else {
+            panic!("expected a fenced matrix");
+        };
+        assert_eq!((open.as_str(), close.as_str()), ("(", ")"));
+        let EquationNode::Table { rows, .. } = *body else {
+            panic!("expected a table");
+        };
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[1][1], EquationNode::Number("1".to_string()));
+
+        assert_eq!(
+            parse_script("LEFT lbrace x RIGHT ."),
+            fenced("{", "", ident("x"))
+        );
+    }
+
+    #[test]
+    fn test_parse_script_too_deep() {
+        let braces = "{".repeat(10_000) + "x";
+        assert_eq!(parse_script(&braces), EquationNode::Text(braces.clone()));
+        let fractions = "a over ".repeat(10_000) + "b";
+        assert_eq!(
+            parse_script(&fractions),
+            EquationNode::Text(fractions.clone())
+        );
+
+        // Sibling fractions do not nest
+        let EquationNode::Row(items) = parse_script(&vec!["a over b"; 100].join(" + ")) else {
+            panic!("expected a row");
+        };
+        assert_eq!(items.len(), 199);
+        assert!(
+            items
+                .iter()
+                .step_by(2)
+                .all(|item| matches!(item, EquationNode::Fraction { .. }))
+        );
+
+        // Nesting within the limit still parses
+        assert_eq!(parse_script(&("{".repeat(10) + "x")), ident("x"));
+    }
+}
@@ -0,0 +1,170 @@
+use super::bodytext::BodyTextContext;
+use super::control::{parse_list, read_ctrl_data_string};
+use crate::common::{HwpResult, read_u16_le, read_u32_le};
+use crate::model::{FieldKind, FieldRange, Memo};
+use crate::parser::RecordNode;
+
+/// Whether a control ID names a field ('%hlk', '%clk', ...)
+pub(crate) fn is_field_ctrl_id(ctrl_id: u32) -> bool {
+    (ctrl_id >> 24) as u8 == b'%'
+}
+
+/// Parse field start control (CTRL_HEADER of a field)
+/// UINT32 ctrl ID, UINT32 attributes, BYTE extra attributes,
+/// WORD command length, WCHAR[] command, UINT32 field ID
+pub(crate) fn parse_field(node: &RecordNode) -> FieldRange {
+    let payload = node.payload();
+    let ctrl_id = read_u32_le(payload, 0).unwrap_or(0);
+
+    let len = read_u16_le(payload, 9).unwrap_or(0) as usize;
+    let units: Vec<u16> = (0..len)
+        .map_while(|i| read_u16_le(payload, 11 + i * 2))
+        .collect();
+    let command = String::from_utf16_lossy(&units);
+    let id = read_u32_le(payload, 11 + len * 2).unwrap_or(0);
+
+    // Range bookmarks keep their name in CTRL_DATA rather than the command
+    let mut kind = field_kind(ctrl_id, &command);
+    if let FieldKind::Bookmark { name } = &mut kind
+        && let Some(data_name) = read_ctrl_data_string(node)
+    {
+        *name = data_name;
+    }
+
+    FieldRange {
+        id,
+        kind,
+        command,
+        inlines: Vec::new(),
+    }
+}
+
+fn field_kind(ctrl_id: u32, command: &str) -> FieldKind {
+    let name: String = ctrl_id.to_be_bytes().iter().map(|&b| b as char).collect();
+    match name.as_str() {
+        "%hlk" => FieldKind::Hyperlink {
+            target: hyperlink_target(command),
+        },
+        "%clk" => FieldKind::ClickHere {
+            direction: click_here_direction(command),
+        },
+        "%xrf" => FieldKind::CrossReference {
+            target: first_argument(command)
+                .trim_start_matches(['?', '#'])
+                .to_string(),
+        },
+        "%mmg" => FieldKind::MailMerge {
+            name: first_argument(command),
+        },
+        "%bmk" => FieldKind::Bookmark {
+            name: first_argument(command),
+        },
+        "%dte" => FieldKind::Date,
+        "%ddt" => FieldKind::DocumentDate,
+        "%pat" => FieldKind::FilePath,
+        "%fmu" => FieldKind::Formula,
+        "%smr" => FieldKind::Summary,
+        "%usr" => FieldKind::UserInfo,
+        "%%me" => FieldKind::Memo {
+            number: command_parameter(command, "Number")
+                .and_then(|number| number.trim().parse().ok())
+                .unwrap_or(0),
+            author: command_parameter(command, "Author").unwrap_or_default(),
+            date: command_parameter(command, "CreateDateTime").unwrap_or_default(),
+        },
+        _ => FieldKind::Other(name),
+    }
+}
+
+/// First `;`-separated argument of a command, with `\` escapes removed
+/// ("http\://example.com;1;0;0;" → "http://example.com")
+fn first_argument(command: &str) -> String {
+    let mut result = String::new();
+    let mut chars = command.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => result.extend(chars.next()),
+            ';' => break,
+            c => result.push(c),
+        }
+    }
+    result
+}
+
+/// Hyperlink target; links into the document itself start with '?' and
+/// become ODF fragment links to the bookmark
+fn hyperlink_target(command: &str) -> String {
+    let target = first_argument(command);
+    match target.strip_prefix('?') {
+        Some(bookmark) => format!("#{}", bookmark.trim_start_matches('#')),
+        None => target,
+    }
+}
+
+/// Number memo fields in order, and keep the memo's paragraph list when
+/// the field carries one
+pub(crate) fn parse_memo_contents(
+    node: &RecordNode,
+    field: &mut FieldRange,
+    ctx: &mut BodyTextContext,
+) -> HwpResult<()> {
+    let FieldKind::Memo { number, .. } = &mut field.kind else {
+        return Ok(());
+    };
+    let count = ctx.count_memo();
+    if *number == 0 {
+        *number = count;
+    }
+    let number = *number;
+    let (_, blocks) = parse_list(node, ctx)?;
+    if !blocks.is_empty() {
+        ctx.add_memo(Memo { number, blocks });
+    }
+    Ok(())
+}
+
+/// Guide text of a click-here field
+/// ("Clickhere:set:43:Direction:wstring:9:여기에 입력 HelpState:wstring:0: ")
+fn click_here_direction(command: &str) -> String {
+    command_parameter(command, "Direction").unwrap_or_default()
+}
+
+/// Value of a `Key:wstring:length:value` parameter in a field command
+fn command_parameter(command: &str, key: &str) -> Option<String> {
+    let prefix = format!("{}:wstring:", key);
+    let start = command.find(&prefix)?;
+    let rest = &command[start + prefix.len()..];
+    let (len, text) = rest.split_once(':')?;
+    let len = len.trim().parse().unwrap_or(0);
+    Some(text.chars().take(len).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_field_commands() {
+        assert_eq!(
+            first_argument(r"http\://www.hancom.com;1;0;0;"),
+            "http://www.hancom.com"
+        );
+        assert_eq!(
+            click_here_direction(
+                "Clickhere:set:43:Direction:wstring:6:이름을 입력 HelpState:wstring:0: "
+            ),
+            "이름을 입력"
+        );
+        assert_eq!(
+            field_kind(
+                u32::from_be_bytes(*b"%%me"),
+                "Number:wstring:1:2 Author:wstring:3:홍길동 CreateDateTime:wstring:19:2024-03-05 14:20:00 "
+            ),
+            FieldKind::Memo {
+                number: 2,
+                author: "홍길동".to_string(),
+                date: "2024-03-05 14:20:00".to_string(),
+            }
+        );
+    }
+}
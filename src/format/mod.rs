@@ -1,6 +1,18 @@
+use crate::common::{Color, HwpUnit, read_u16_le, read_u32_le};
 use crate::common::{HwpError, HwpResult, check_signature};
-use crate::model::Document;
-use crate::parser::{Ole2, RecordStream};
+use crate::model::{BinData, BinDataKind, CharShadow, Document, EmphasisMark, Fill, FillType};
+use crate::parser::tags::*;
+use crate::parser::{Ole2, RecordStream, inflate};
+use control::read_string;
+
+mod bodytext;
+mod chart;
+mod control;
+mod equation;
+mod field;
+mod ole;
+mod shape;
+mod table;
 
 /// Detect file format (HWP or HWPX)
 pub fn detect_format(data: &[u8]) -> HwpResult<FileFormat> {
@@ -32,22 +44,28 @@ pub fn parse_hwp(data: &[u8]) -> HwpResult<Document> {
     let ole2 = Ole2::parse(data.to_vec())?;
 
     // Get FileHeader to validate signature
-    let _file_header = ole2.get_stream("FileHeader")?;
+    let file_header = ole2.get_stream("FileHeader")?;
+    // Properties bit 0: streams are compressed
+    let compressed = read_u32_le(&file_header, 36).unwrap_or(0) & 0x01 != 0;
 
     let mut doc = Document::new();
 
     // Parse DocInfo (contains styles, fonts, etc.)
     if let Ok(docinfo_data) = ole2.get_stream("DocInfo") {
-        parse_docinfo(&docinfo_data, &mut doc)?;
+        parse_docinfo(&record_data(docinfo_data, compressed)?, &mut doc)?;
     }
 
+    // Binary data referenced by BIN_DATA records
+    load_bin_data(&ole2, compressed, &mut doc);
+
     // Parse BodyText sections
     let mut section_idx = 0;
     loop {
         let stream_name = format!("BodyText/Section{}", section_idx);
         match ole2.get_stream(&stream_name) {
             Ok(bodytext_data) => {
-                let section = parse_bodytext(&bodytext_data, &mut doc)?;
+                let data = record_data(bodytext_data, compressed)?;
+                let section = bodytext::parse_section(&data, &mut doc)?;
                 doc.sections.push(section);
                 section_idx += 1;
             }
@@ -58,6 +76,16 @@ pub fn parse_hwp(data: &[u8]) -> HwpResult<Document> {
     Ok(doc)
 }
 
+/// Records of the DocInfo or a BodyText stream, which are deflated
+/// (without a zlib header) when the file is compressed
+fn record_data(stream: Vec<u8>, compressed: bool) -> HwpResult<Vec<u8>> {
+    if compressed {
+        inflate(&stream)
+    } else {
+        Ok(stream)
+    }
+}
+
 /// Parse HWPX file into document model
 pub fn parse_hwpx(_data: &[u8]) -> HwpResult<Document> {
     // HWPX is a ZIP format with content.xml inside
@@ -74,32 +102,39 @@ fn parse_docinfo(data: &[u8], doc: &mut Document) -> HwpResult<()> {
 
     while let Some(record) = stream.next_record()? {
         match record.tagid {
-            19 => {
-                // HWPTAG_FACE_NAME
+            HWPTAG_ID_MAPPINGS => {
+                // Binary data count, then the face name count of each language group
+                doc.font_counts = (1..8)
+                    .map(|i| read_u32_le(&record.payload, i * 4).unwrap_or(0))
+                    .collect();
+            }
+            HWPTAG_FACE_NAME => {
                 let name = parse_face_name(&record.payload)?;
                 doc.fonts.push(name);
             }
-            21 => {
-                // HWPTAG_CHAR_SHAPE
+            HWPTAG_CHAR_SHAPE => {
                 let char_shape = parse_char_shape(&record.payload, doc.char_shapes.len() as u32)?;
                 doc.char_shapes.push(char_shape);
             }
-            25 => {
-                // HWPTAG_PARA_SHAPE
+            HWPTAG_PARA_SHAPE => {
                 let para_shape = parse_para_shape(&record.payload, doc.para_shapes.len() as u32)?;
                 doc.para_shapes.push(para_shape);
             }
-            26 => {
-                // HWPTAG_STYLE
+            HWPTAG_STYLE => {
                 let style = parse_style(&record.payload, doc.styles.len() as u32)?;
                 doc.styles.push(style);
             }
-            20 => {
-                // HWPTAG_BORDER_FILL
+            HWPTAG_BORDER_FILL => {
+                // Border fill IDs are 1-based in references
                 let border_fill =
-                    parse_border_fill(&record.payload, doc.border_fills.len() as u32)?;
+                    parse_border_fill(&record.payload, doc.border_fills.len() as u32 + 1)?;
                 doc.border_fills.push(border_fill);
             }
+            HWPTAG_BIN_DATA => {
+                // Binary data IDs are 1-based in references
+                let bin_data = parse_bin_data(&record.payload, doc.bin_data.len() as u32 + 1);
+                doc.bin_data.push(bin_data);
+            }
             _ => {}
         }
     }
@@ -107,56 +142,128 @@ fn parse_docinfo(data: &[u8], doc: &mut Document) -> HwpResult<()> {
     Ok(())
 }
 
-/// Parse BodyText stream
-fn parse_bodytext(data: &[u8], _doc: &mut Document) -> HwpResult<crate::model::Section> {
-    let section = crate::model::Section::new();
-    let mut stream = RecordStream::new(data.to_vec());
+/// Parse binary data item (바이너리 데이터)
+fn parse_bin_data(payload: &[u8], id: u32) -> BinData {
+    let attr = read_u16_le(payload, 0).unwrap_or(0) as u32;
+    let kind = BinDataKind::from_u32(attr & 0x0F).unwrap_or(BinDataKind::Embedding);
 
-    while let Some(_record) = stream.next_record()? {
-        // TODO: Parse paragraph, table, etc.
+    let mut bin_data = BinData {
+        id,
+        kind,
+        extension: String::new(),
+        link_path: String::new(),
+        compressed: match (attr >> 4) & 0x03 {
+            1 => Some(true),
+            2 => Some(false),
+            _ => None,
+        },
+        data: Vec::new(),
+        preview: None,
+        chart: None,
+    };
+    match kind {
+        // Absolute path, then relative path
+        BinDataKind::Link => bin_data.link_path = read_string(payload, 2).0,
+        // WORD storage ID, then the extension
+        BinDataKind::Embedding | BinDataKind::Storage => {
+            bin_data.id = read_u16_le(payload, 2).map_or(id, |id| id as u32);
+            bin_data.extension = read_string(payload, 4).0.to_lowercase();
+        }
     }
+    bin_data
+}
 
-    Ok(section)
+/// Read embedded binary data from the BinData storage.
+/// Data that does not inflate is kept as stored.
+fn load_bin_data(ole2: &Ole2, compressed: bool, doc: &mut Document) {
+    for bin_data in doc
+        .bin_data
+        .iter_mut()
+        .filter(|b| b.kind != BinDataKind::Link)
+    {
+        let name = format!("BinData/BIN{:04X}.{}", bin_data.id, bin_data.extension);
+        let Ok(stream) = ole2.get_stream(&name) else {
+            continue;
+        };
+        bin_data.data = if bin_data.compressed.unwrap_or(compressed) {
+            inflate(&stream).unwrap_or(stream)
+        } else {
+            stream
+        };
+        // OLE objects keep their own compound file
+        if bin_data.is_ole() {
+            ole::load_ole_storage(bin_data);
+        }
+    }
 }
 
 /// Parse face name (글꼴 이름)
+/// BYTE attributes, then the name as a WORD-length-prefixed WCHAR string;
+/// substitute font and type info follow, which we do not use
 fn parse_face_name(payload: &[u8]) -> HwpResult<String> {
-    if payload.is_empty() {
-        return Ok(String::new());
-    }
-
-    // HWP face name is UTF-16LE encoded
-    let utf16: Result<Vec<u16>, ()> = (0..payload.len())
-        .step_by(2)
-        .map(|i| {
-            let b0 = payload.get(i).copied().ok_or(())?;
-            let b1 = payload.get(i + 1).copied().ok_or(())?;
-            Ok::<u16, ()>(u16::from_le_bytes([b0, b1]))
-        })
-        .collect();
-
-    match utf16 {
-        Ok(utf16_vec) => {
-            let s = String::from_utf16_lossy(&utf16_vec);
-            Ok(s.trim_end_matches('\0').to_string())
-        }
-        Err(_) => Ok(String::new()),
-    }
+    Ok(read_string(payload, 1).0.trim_end_matches('\0').to_string())
 }
 
-/// Parse char shape (문자 모양)
-fn parse_char_shape(_payload: &[u8], id: u32) -> HwpResult<crate::model::CharShape> {
-    let char_shape = crate::model::CharShape::new(id);
+/// Parse char shape (글자 모양)
+/// WORD[7] font IDs, UINT8[7] ratios, INT8[7] spacings, UINT8[7] relative
+/// sizes, INT8[7] offsets (one per language group), INT32 base size,
+/// UINT32 attributes, INT8 shadow gaps, COLORREF text, underline, shade
+/// and shadow colors, WORD border fill ID, then COLORREF strike color
+fn parse_char_shape(payload: &[u8], id: u32) -> HwpResult<crate::model::CharShape> {
+    use crate::common::read_u8;
 
-    if _payload.len() >= 28 {
-        // Parse basic char shape fields
-        // font_id, font_size, attributes, color, etc.
-        // This is simplified; full parsing requires understanding HWP binary format
+    let mut char_shape = crate::model::CharShape::new(id);
+    if payload.len() < 56 {
+        return Ok(char_shape);
+    }
+
+    for lang in 0..7 {
+        char_shape.font_ids[lang] = read_u16_le(payload, lang * 2).unwrap_or(0) as u32;
+        char_shape.ratios[lang] = read_u8(payload, 14 + lang).unwrap_or(100);
+        char_shape.spacings[lang] = read_u8(payload, 21 + lang).unwrap_or(0) as i8;
+        char_shape.relative_sizes[lang] = read_u8(payload, 28 + lang).unwrap_or(100);
+        char_shape.offsets[lang] = read_u8(payload, 35 + lang).unwrap_or(0) as i8;
     }
+    char_shape.font_size = HwpUnit(read_u32_le(payload, 42).unwrap_or(1000) as i32);
 
+    let attr = read_u32_le(payload, 46).unwrap_or(0);
+    char_shape.italic = attr & 0x01 != 0;
+    char_shape.bold = attr & 0x02 != 0;
+    let underline = (attr >> 2) & 0x03;
+    if underline != 0 {
+        char_shape.underline = char_line_style((attr >> 4) & 0x0F);
+        char_shape.underline_above = underline == 3;
+    }
+    char_shape.outline = (attr >> 8) & 0x07 != 0;
+    char_shape.emboss = attr & (1 << 13) != 0;
+    char_shape.engrave = attr & (1 << 14) != 0;
+    char_shape.superscript = attr & (1 << 15) != 0;
+    char_shape.subscript = attr & (1 << 16) != 0;
+    char_shape.strikethrough = (attr >> 18) & 0x07 != 0;
+    char_shape.emphasis = EmphasisMark::from_u32((attr >> 21) & 0x0F).unwrap_or(EmphasisMark::None);
+    char_shape.strike_style = char_line_style((attr >> 26) & 0x0F);
+
+    char_shape.color = Color(read_u32_le(payload, 52).unwrap_or(0));
+    char_shape.underline_color = Color(read_u32_le(payload, 56).unwrap_or(0));
+    char_shape.background_color = Color(read_u32_le(payload, 60).unwrap_or(0xFFFFFF));
+    if (attr >> 11) & 0x03 != 0 {
+        char_shape.shadow = Some(CharShadow {
+            offset_x: read_u8(payload, 50).unwrap_or(0) as i8,
+            offset_y: read_u8(payload, 51).unwrap_or(0) as i8,
+            color: Color(read_u32_le(payload, 64).unwrap_or(0xB2B2B2)),
+        });
+    }
+    // Older versions draw the strike line in the text color
+    char_shape.strike_color = read_u32_le(payload, 70).map_or(char_shape.color, Color);
     Ok(char_shape)
 }
 
+/// Map the line type of an underline or strike line. These number the
+/// border line types without "none": 0 is a solid line.
+fn char_line_style(val: u32) -> crate::common::LineStyle {
+    line_style_from_hwp(val as u8 + 1)
+}
+
 /// Parse para shape (문단 모양)
 fn parse_para_shape(_payload: &[u8], id: u32) -> HwpResult<crate::model::ParaShape> {
     let para_shape = crate::model::ParaShape::new(id);
@@ -184,18 +291,137 @@ fn parse_style(_payload: &[u8], id: u32) -> HwpResult<crate::model::Style> {
 }
 
 /// Parse border fill (테두리/배경)
-fn parse_border_fill(_payload: &[u8], id: u32) -> HwpResult<crate::model::BorderFill> {
-    let border_fill = crate::model::BorderFill {
-        id,
-        left: crate::model::Border::none(),
-        right: crate::model::Border::none(),
-        top: crate::model::Border::none(),
-        bottom: crate::model::Border::none(),
-        diagonal: crate::model::Border::none(),
-        fill_type: crate::model::FillType::None,
-        fill_color: crate::common::Color(0),
-        background_color: crate::common::Color(0xFFFFFF),
+fn parse_border_fill(payload: &[u8], id: u32) -> HwpResult<crate::model::BorderFill> {
+    use crate::common::read_u8;
+    use crate::model::Border;
+
+    // Left, right, top, bottom and diagonal: UINT8 type, UINT8 width, COLORREF color
+    let read_border = |offset: usize| Border {
+        style: line_style_from_hwp(read_u8(payload, offset).unwrap_or(0)),
+        width: border_width_from_hwp(read_u8(payload, offset + 1).unwrap_or(0)),
+        color: Color(read_u32_le(payload, offset + 2).unwrap_or(0)),
     };
 
-    Ok(border_fill)
+    let fill = parse_fill(payload, 32);
+    Ok(crate::model::BorderFill {
+        id,
+        left: read_border(2),
+        right: read_border(8),
+        top: read_border(14),
+        bottom: read_border(20),
+        diagonal: read_border(26),
+        fill_type: fill.fill_type,
+        fill_color: fill.fill_color,
+        background_color: fill.background_color,
+    })
+}
+
+/// Parse fill info (채우기 정보) shared by border fills and drawing objects
+pub(crate) fn parse_fill(payload: &[u8], offset: usize) -> Fill {
+    let mut fill = Fill::none();
+
+    // Fill info: bit 0 color fill, bit 1 image fill, bit 2 gradient fill
+    let fill_bits = read_u32_le(payload, offset).unwrap_or(0);
+    let mut offset = offset + 4;
+    if fill_bits & 0x01 != 0 {
+        fill.background_color = Color(read_u32_le(payload, offset).unwrap_or(0xFFFFFF));
+        fill.fill_color = Color(read_u32_le(payload, offset + 4).unwrap_or(0));
+        let pattern = read_u32_le(payload, offset + 8).unwrap_or(0) as i32;
+        fill.fill_type = if pattern < 0 {
+            FillType::Solid
+        } else {
+            FillType::Pattern
+        };
+        offset += 12;
+    }
+    if fill_bits & 0x04 != 0 {
+        // INT16 type, angle, center x, center y, blur, color count;
+        // positions only with more than two colors, then the colors
+        let count = read_u16_le(payload, offset + 10).unwrap_or(0) as usize;
+        let colors_offset = offset + 12 + if count > 2 { count * 4 } else { 0 };
+        if let Some(first) = read_u32_le(payload, colors_offset) {
+            fill.background_color = Color(first);
+            fill.fill_color = Color(
+                read_u32_le(payload, colors_offset + (count.max(1) - 1) * 4).unwrap_or(first),
+            );
+            fill.fill_type = FillType::Gradient;
+        }
+    } else if fill_bits & 0x02 != 0 && fill.fill_type == FillType::None {
+        fill.fill_type = FillType::Image;
+    }
+    fill
+}
+
+/// Map HWP border line type (테두리선 종류) to line style
+pub(crate) fn line_style_from_hwp(val: u8) -> crate::common::LineStyle {
+    use crate::common::LineStyle;
+
+    match val {
+        0 => LineStyle::None,
+        1 => LineStyle::Solid,
+        2 | 6 => LineStyle::Dashed,
+        3 | 7 => LineStyle::Dotted,
+        4 => LineStyle::DashDot,
+        5 => LineStyle::DashDotDot,
+        8..=11 => LineStyle::Double,
+        12 | 13 => LineStyle::Wave,
+        _ => LineStyle::Solid,
+    }
+}
+
+/// Map HWP border width index (0.1mm ... 5.0mm) to 1/20 mm
+pub(crate) fn border_width_from_hwp(val: u8) -> u8 {
+    const WIDTHS: [u8; 16] = [2, 2, 3, 4, 5, 6, 8, 10, 12, 14, 20, 30, 40, 60, 80, 100];
+    WIDTHS.get(val as usize).copied().unwrap_or(2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Block, Inline};
+
+    fn record(tag: u16, level: u32, payload: &[u8]) -> Vec<u8> {
+        let header = tag as u32 | (level << 10) | ((payload.len() as u32) << 20);
+        let mut data = header.to_le_bytes().to_vec();
+        data.extend_from_slice(payload);
+        data
+    }
+
+    #[test]
+    fn test_compressed_section() {
+        // Paragraph header with 3 chars, text "가나" and the paragraph end
+        let mut payload = 3u32.to_le_bytes().to_vec();
+        payload.resize(22, 0);
+        let mut records = record(HWPTAG_PARA_HEADER, 0, &payload);
+        let text: Vec<u8> = "가나\r"
+            .encode_utf16()
+            .flat_map(|u| u.to_le_bytes())
+            .collect();
+        records.extend(record(HWPTAG_PARA_TEXT, 1, &text));
+
+        // A single stored deflate block
+        let len = records.len() as u16;
+        let mut stream = vec![0x01];
+        stream.extend(len.to_le_bytes());
+        stream.extend((!len).to_le_bytes());
+        stream.extend(&records);
+
+        let data = record_data(stream, true).unwrap();
+        assert_eq!(data, records);
+        let section = bodytext::parse_section(&data, &mut Document::new()).unwrap();
+        let Some(Block::Paragraph(para)) = section.blocks.first() else {
+            panic!("expected a paragraph");
+        };
+        assert!(matches!(&para.inlines[..], [Inline::Text(run)] if run.text == "가나"));
+    }
+
+    #[test]
+    fn test_char_line_style() {
+        use crate::common::LineStyle;
+
+        assert_eq!(char_line_style(0), LineStyle::Solid);
+        assert_eq!(char_line_style(5), LineStyle::Dashed);
+        assert_eq!(char_line_style(6), LineStyle::Dotted);
+        assert_eq!(char_line_style(11), LineStyle::Wave);
+    }
 }
@@ -0,0 +1,150 @@
+use super::chart::parse_ooxml_chart;
+use crate::common::{check_signature, read_u32_le};
+use crate::model::{BinData, OlePreview};
+use crate::parser::Ole2;
+
+const OLE2_SIGNATURE: [u8; 8] = [0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1];
+
+// Standard clipboard formats of a presentation stream
+const CF_METAFILEPICT: u32 = 3;
+const CF_DIB: u32 = 8;
+const CF_ENHMETAFILE: u32 = 14;
+
+/// Open the compound file of an embedded OLE item, keeping the file itself,
+/// the picture the storage caches for display and a DrawingML chart it
+/// holds (see `parse_ooxml_chart` for charts without one). The stream
+/// starts with the compound file's size before the file itself.
+pub(crate) fn load_ole_storage(bin_data: &mut BinData) {
+    let data = &bin_data.data;
+    let storage = if check_signature(data, 0, &OLE2_SIGNATURE) {
+        data.clone()
+    } else if check_signature(data, 4, &OLE2_SIGNATURE) {
+        data[4..].to_vec()
+    } else {
+        return;
+    };
+    let Ok(ole2) = Ole2::parse(storage) else {
+        return;
+    };
+
+    bin_data.preview = (0..10)
+        .filter_map(|i| ole2.get_stream(&format!("\u{2}OlePres{:03}", i)).ok())
+        .find_map(|stream| parse_presentation(&stream));
+    bin_data.chart = ole2
+        .get_stream("OOXMLChartContents")
+        .ok()
+        .and_then(|stream| parse_ooxml_chart(&stream));
+    bin_data.data = ole2.data;
+}
+
+/// Parse a presentation stream (\x02OlePresNNN): clipboard format, target
+/// device, aspect, lindex, advise flags, reserved, width and height in
+/// 0.01mm, data size, then the data
+fn parse_presentation(stream: &[u8]) -> Option<OlePreview> {
+    // Standard formats are marked with -1 or -2; anything else is the
+    // length of a registered format name
+    let marker = read_u32_le(stream, 0)?;
+    if marker != 0xFFFFFFFF && marker != 0xFFFFFFFE {
+        return None;
+    }
+    let format = read_u32_le(stream, 4)?;
+    let offset = 8 + read_u32_le(stream, 8)? as usize;
+    let width = read_u32_le(stream, offset + 16)?;
+    let height = read_u32_le(stream, offset + 20)?;
+    let size = read_u32_le(stream, offset + 24)? as usize;
+    let data = stream.get(offset + 28..offset + 28 + size)?;
+
+    let (extension, data) = match format {
+        CF_METAFILEPICT => ("wmf", placeable_wmf(data, width, height)),
+        CF_ENHMETAFILE => ("emf", data.to_vec()),
+        CF_DIB => ("bmp", bitmap_file(data)?),
+        _ => return None,
+    };
+    Some(OlePreview {
+        extension: extension.to_string(),
+        data,
+    })
+}
+
+/// Prefix a metafile with a placeable header so viewers know its size
+fn placeable_wmf(data: &[u8], width: u32, height: u32) -> Vec<u8> {
+    // Bounds in 0.01mm, 2540 units per inch
+    let mut words: Vec<u16> = vec![
+        0xCDD7,
+        0x9AC6,
+        0,
+        0,
+        0,
+        width.min(0x7FFF) as u16,
+        height.min(0x7FFF) as u16,
+        2540,
+        0,
+        0,
+    ];
+    let checksum = words.iter().fold(0, |sum, word| sum ^ word);
+    words.push(checksum);
+
+    let mut wmf: Vec<u8> = words.iter().flat_map(|word| word.to_le_bytes()).collect();
+    wmf.extend_from_slice(data);
+    wmf
+}
+
+/// Prefix a device-independent bitmap with a BITMAPFILEHEADER
+fn bitmap_file(dib: &[u8]) -> Option<Vec<u8>> {
+    let header_size = read_u32_le(dib, 0)?;
+    let bit_count = dib.get(14).copied()? as u32;
+    let compression = read_u32_le(dib, 16).unwrap_or(0);
+    let used_colors = read_u32_le(dib, 32).unwrap_or(0);
+
+    let colors = if used_colors > 0 {
+        used_colors
+    } else if bit_count <= 8 {
+        1 << bit_count
+    } else {
+        0
+    };
+    // BI_BITFIELDS masks follow a plain info header
+    let masks = if compression == 3 && header_size == 40 {
+        12
+    } else {
+        0
+    };
+    let bits_offset = 14 + header_size + colors * 4 + masks;
+
+    let mut bmp = b"BM".to_vec();
+    bmp.extend(((14 + dib.len()) as u32).to_le_bytes());
+    bmp.extend([0; 4]);
+    bmp.extend(bits_offset.to_le_bytes());
+    bmp.extend_from_slice(dib);
+    Some(bmp)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_presentation_dib() {
+        // 1x1 24-bit bitmap: info header, then one padded row
+        let mut dib = vec![0u8; 40];
+        dib[0] = 40;
+        dib[4] = 1;
+        dib[8] = 1;
+        dib[12] = 1;
+        dib[14] = 24;
+        dib.extend([0xFF, 0, 0, 0]);
+
+        let mut stream = Vec::new();
+        for value in [0xFFFFFFFF, CF_DIB, 4, 1, 0xFFFFFFFF, 2, 0, 100, 100] {
+            stream.extend(u32::to_le_bytes(value));
+        }
+        stream.extend((dib.len() as u32).to_le_bytes());
+        stream.extend(&dib);
+
+        let preview = parse_presentation(&stream).unwrap();
+        assert_eq!(preview.extension, "bmp");
+        assert_eq!(&preview.data[..2], b"BM");
+        assert_eq!(read_u32_le(&preview.data, 10), Some(54));
+        assert_eq!(preview.data.len(), 14 + dib.len());
+    }
+}
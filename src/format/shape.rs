@@ -0,0 +1,500 @@
+use super::bodytext::{BodyTextContext, parse_paragraph_list};
+use super::control::{parse_caption, parse_object_placement, read_hwpunit, read_hwpunit16};
+use super::{line_style_from_hwp, parse_fill};
+use crate::common::{
+    Color, HwpResult, HwpUnit, Margin, Position, Rect, Size, VAlignment, read_f64_le, read_u8,
+    read_u16_le, read_u32_le,
+};
+use crate::model::{
+    ArcKind, ArrowStyle, Border, Control, CurveSegment, DrawAspect, EllipseArc, GroupChild,
+    GroupObject, ImageEffect, OLE, ObjectPlacement, OleObjectType, Picture, Shape, ShapeGroup,
+    ShapeLine, ShapeMatrix, ShapeType, TextBox, TextDirection,
+};
+use crate::parser::RecordNode;
+use crate::parser::tags::*;
+
+/// Parse drawing object control (gso): common object attributes, then a
+/// SHAPE_COMPONENT whose child record says what kind of object it is
+pub(crate) fn parse_shape_object(
+    node: &RecordNode,
+    ctx: &mut BodyTextContext,
+) -> HwpResult<Option<Control>> {
+    let mut placement = parse_object_placement(node.payload());
+    placement.caption = parse_caption(node, HWPTAG_SHAPE_COMPONENT, ctx)?;
+    let Some(component) = node.child(HWPTAG_SHAPE_COMPONENT) else {
+        return Ok(None);
+    };
+
+    Ok(
+        parse_component(component, TOP_LEVEL, placement, ctx)?.map(|object| match object {
+            GroupObject::Shape(shape) => Control::Shape(shape),
+            GroupObject::Picture(picture) => Control::Picture(picture),
+            GroupObject::Ole(ole) => Control::OLE(ole),
+            GroupObject::Group(group) => Control::ShapeGroup(group),
+        }),
+    )
+}
+
+/// Parse a SHAPE_COMPONENT into a picture, a group or a drawing shape,
+/// depending on its child records
+fn parse_component(
+    component: &RecordNode,
+    base: usize,
+    placement: ObjectPlacement,
+    ctx: &mut BodyTextContext,
+) -> HwpResult<Option<GroupObject>> {
+    let id = ctx.next_object_id();
+    let payload = component.payload();
+
+    if let Some(picture) = component.child(HWPTAG_SHAPE_COMPONENT_PICTURE) {
+        let original = original_size(payload, base);
+        return Ok(Some(GroupObject::Picture(parse_picture(
+            picture.payload(),
+            placement,
+            original,
+            id,
+        ))));
+    }
+
+    if let Some(ole) = component.child(HWPTAG_SHAPE_COMPONENT_OLE) {
+        return Ok(Some(GroupObject::Ole(parse_ole(
+            ole.payload(),
+            placement,
+            id,
+        ))));
+    }
+
+    // A container record lists the children's control IDs; the children
+    // follow as SHAPE_COMPONENT siblings
+    if component.child(HWPTAG_SHAPE_COMPONENT_CONTAINER).is_some() {
+        let (width, height) = original_size(payload, base);
+        let mut children = Vec::new();
+        for child in &component.children {
+            if child.tagid() != HWPTAG_SHAPE_COMPONENT {
+                continue;
+            }
+            let payload = child.payload();
+            let mut placement = ObjectPlacement::new();
+            placement.width = read_hwpunit(payload, GROUPED + 20);
+            placement.height = read_hwpunit(payload, GROUPED + 24);
+            let matrix = rendering_matrix(payload, GROUPED);
+            if let Some(object) = parse_component(child, GROUPED, placement, ctx)? {
+                children.push(GroupChild { object, matrix });
+            }
+        }
+        return Ok(Some(GroupObject::Group(ShapeGroup {
+            id,
+            rect: Rect::new(HwpUnit(0), HwpUnit(0), width, height),
+            children,
+            placement,
+        })));
+    }
+
+    let Some(mut shape) = parse_drawing_shape(component, base, placement, id) else {
+        return Ok(None);
+    };
+    shape.text = parse_shape_text(component, shape.rect, id, ctx)?;
+    Ok(Some(GroupObject::Shape(shape)))
+}
+
+/// Parse the text of a drawing object: a LIST_HEADER among the component's
+/// children, followed by its paragraphs
+fn parse_shape_text(
+    component: &RecordNode,
+    rect: Rect,
+    id: u32,
+    ctx: &mut BodyTextContext,
+) -> HwpResult<Option<TextBox>> {
+    let children = &component.children;
+    let Some(idx) = children
+        .iter()
+        .position(|c| c.tagid() == HWPTAG_LIST_HEADER)
+    else {
+        return Ok(None);
+    };
+    let end = children[idx + 1..]
+        .iter()
+        .position(|c| c.tagid() != HWPTAG_PARA_HEADER)
+        .map_or(children.len(), |n| idx + 1 + n);
+
+    // Common list header fields (UINT32 attributes at 4, bits 0-2 text
+    // direction, bits 5-6 vertical alignment), then HWPUNIT16 left, right,
+    // top and bottom margins and a HWPUNIT maximum text width
+    let payload = children[idx].payload();
+    let attr = read_u32_le(payload, 4).unwrap_or(0);
+    Ok(Some(TextBox {
+        id,
+        rect,
+        margin: Margin::new(
+            read_hwpunit16(payload, 8),
+            read_hwpunit16(payload, 12),
+            read_hwpunit16(payload, 10),
+            read_hwpunit16(payload, 14),
+        ),
+        v_align: VAlignment::from_u16(((attr >> 5) & 0x03) as u16).unwrap_or(VAlignment::Top),
+        text_direction: TextDirection::from_u32(attr & 0x07).unwrap_or(TextDirection::Horizontal),
+        content: parse_paragraph_list(&children[idx + 1..end], ctx)?,
+    }))
+}
+
+/// Offset of the fields following the control ID in a SHAPE_COMPONENT;
+/// objects directly under a gso control repeat their control ID
+const TOP_LEVEL: usize = 8;
+/// Offset of the same fields in a SHAPE_COMPONENT inside a group
+const GROUPED: usize = 4;
+
+/// Original size: follows the offsets in the group, group level and version
+fn original_size(payload: &[u8], base: usize) -> (HwpUnit, HwpUnit) {
+    (
+        read_hwpunit(payload, base + 12),
+        read_hwpunit(payload, base + 16),
+    )
+}
+
+/// Offset of the line info, after the rendering info: WORD matrix pair
+/// count, the translation matrix, then a scale and rotation matrix per pair
+/// (each matrix is six doubles)
+fn line_info_offset(payload: &[u8], base: usize) -> usize {
+    let count = read_u16_le(payload, base + 42).unwrap_or(0) as usize;
+    base + 44 + 48 + count * 96
+}
+
+/// Rendering matrix mapping the object's original coordinates into its
+/// group: the translation matrix, then a rotation and scale matrix per pair
+fn rendering_matrix(payload: &[u8], base: usize) -> ShapeMatrix {
+    let read_matrix = |offset: usize| {
+        let value = |i: usize| read_f64_le(payload, offset + i * 8);
+        Some(ShapeMatrix {
+            a: value(0)?,
+            b: value(1)?,
+            c: value(2)?,
+            d: value(3)?,
+            e: value(4)?,
+            f: value(5)?,
+        })
+    };
+
+    let count = read_u16_le(payload, base + 42).unwrap_or(0) as usize;
+    let mut matrix = read_matrix(base + 44).unwrap_or_else(ShapeMatrix::identity);
+    for pair in 0..count {
+        let offset = base + 92 + pair * 96;
+        let (Some(scale), Some(rotation)) = (read_matrix(offset), read_matrix(offset + 48)) else {
+            break;
+        };
+        matrix = matrix.multiply(&rotation).multiply(&scale);
+    }
+    matrix
+}
+
+/// Parse a line, rectangle, ellipse, arc, polygon or curve component with
+/// the line and fill info of its SHAPE_COMPONENT
+fn parse_drawing_shape(
+    component: &RecordNode,
+    base: usize,
+    placement: ObjectPlacement,
+    id: u32,
+) -> Option<Shape> {
+    let shape_type = component
+        .children
+        .iter()
+        .find_map(|child| parse_shape_type(child.tagid(), child.payload()))?;
+
+    let payload = component.payload();
+    let (width, height) = original_size(payload, base);
+    let offset = line_info_offset(payload, base);
+    Some(Shape {
+        id,
+        shape_type,
+        rect: Rect::new(HwpUnit(0), HwpUnit(0), width, height),
+        line: parse_shape_line(payload, offset),
+        // Fill info follows the 13-byte line info
+        fill: parse_fill(payload, offset + 13),
+        text: None,
+        placement,
+    })
+}
+
+/// Parse line info: COLORREF color, INT32 thickness, UINT32 attributes,
+/// UINT8 outline style
+fn parse_shape_line(payload: &[u8], offset: usize) -> ShapeLine {
+    let attr = read_u32_le(payload, offset + 8).unwrap_or(0);
+    let arrow =
+        |shift: u32| ArrowStyle::from_u32((attr >> shift) & 0x3F).unwrap_or(ArrowStyle::None);
+
+    ShapeLine {
+        style: line_style_from_hwp((attr & 0x3F) as u8),
+        width: read_hwpunit(payload, offset + 4),
+        color: Color(read_u32_le(payload, offset).unwrap_or(0)),
+        start_arrow: arrow(10),
+        end_arrow: arrow(16),
+        start_arrow_size: ((attr >> 22) & 0x0F) as u8,
+        end_arrow_size: ((attr >> 26) & 0x0F) as u8,
+    }
+}
+
+fn read_position(payload: &[u8], offset: usize) -> Position {
+    Position::new(
+        read_hwpunit(payload, offset),
+        read_hwpunit(payload, offset + 4),
+    )
+}
+
+/// Read a point list: point count, then INT32 x/y pairs. Older files store
+/// the count as INT16, newer ones as INT32; curves follow the points with a
+/// segment type per segment. Returns the offset after the list.
+fn read_points(payload: &[u8], segment_types: bool) -> (Vec<Position>, usize) {
+    let count16 = read_u16_le(payload, 0).unwrap_or(0) as usize;
+    let trailer = if segment_types {
+        count16.saturating_sub(1)
+    } else {
+        0
+    };
+    let (count, start) = if payload.len() == 2 + count16 * 8 + trailer {
+        (count16, 2)
+    } else {
+        (read_u32_le(payload, 0).unwrap_or(0) as usize, 4)
+    };
+    let points: Vec<Position> = (0..count)
+        .map(|i| start + i * 8)
+        .take_while(|&offset| offset + 8 <= payload.len())
+        .map(|offset| read_position(payload, offset))
+        .collect();
+    let end = start + points.len() * 8;
+    (points, end)
+}
+
+/// Parse a shape-specific record into its geometry
+fn parse_shape_type(tagid: u16, payload: &[u8]) -> Option<ShapeType> {
+    match tagid {
+        // Start and end points, then UINT16 attributes
+        HWPTAG_SHAPE_COMPONENT_LINE => Some(ShapeType::Line {
+            start: read_position(payload, 0),
+            end: read_position(payload, 8),
+        }),
+        // UINT8 curvature, then the corner coordinates
+        HWPTAG_SHAPE_COMPONENT_RECTANGLE => Some(ShapeType::Rectangle {
+            curvature: read_u8(payload, 0).unwrap_or(0),
+        }),
+        // UINT32 attributes (bit 1 arc, bits 2-9 arc kind), center, axes,
+        // arc start and end points
+        HWPTAG_SHAPE_COMPONENT_ELLIPSE => {
+            let attr = read_u32_le(payload, 0).unwrap_or(0);
+            let arc = (attr & 0x02 != 0).then(|| EllipseArc {
+                kind: ArcKind::from_u32((attr >> 2) & 0xFF).unwrap_or(ArcKind::Arc),
+                start: read_position(payload, 28),
+                end: read_position(payload, 36),
+            });
+            Some(ShapeType::Ellipse {
+                center: read_position(payload, 4),
+                axis1: read_position(payload, 12),
+                axis2: read_position(payload, 20),
+                arc,
+            })
+        }
+        // Arc kind (UINT8, since 5.0.1.x), center and axes
+        HWPTAG_SHAPE_COMPONENT_ARC => {
+            let (kind, base) = if payload.len() % 4 == 1 {
+                let kind = ArcKind::from_u32(read_u8(payload, 0).unwrap_or(0) as u32);
+                (kind.unwrap_or(ArcKind::Arc), 1)
+            } else {
+                (ArcKind::Arc, 0)
+            };
+            Some(ShapeType::Arc {
+                kind,
+                center: read_position(payload, base),
+                axis1: read_position(payload, base + 8),
+                axis2: read_position(payload, base + 16),
+            })
+        }
+        HWPTAG_SHAPE_COMPONENT_POLYGON => Some(ShapeType::Polygon {
+            points: read_points(payload, false).0,
+        }),
+        // Points, then a UINT8 segment type per segment
+        HWPTAG_SHAPE_COMPONENT_CURVE => {
+            let (points, end) = read_points(payload, true);
+            let segments = (0..points.len().saturating_sub(1))
+                .map(|i| match read_u8(payload, end + i) {
+                    Some(1) => CurveSegment::Curve,
+                    _ => CurveSegment::Line,
+                })
+                .collect();
+            Some(ShapeType::Curve { points, segments })
+        }
+        _ => None,
+    }
+}
+
+/// Parse HWPTAG_SHAPE_COMPONENT_PICTURE payload
+fn parse_picture(
+    payload: &[u8],
+    placement: ObjectPlacement,
+    (original_width, original_height): (HwpUnit, HwpUnit),
+    id: u32,
+) -> Picture {
+    let border = read_border(payload, 0);
+
+    // Image rectangle as four corners from the top left, clockwise
+    let rect = Rect::new(
+        read_hwpunit(payload, 12),
+        read_hwpunit(payload, 16),
+        read_hwpunit(payload, 28),
+        read_hwpunit(payload, 32),
+    );
+
+    // Crop is the visible part of the image at its original size
+    let (left, top) = (read_hwpunit(payload, 44), read_hwpunit(payload, 48));
+    let (right, bottom) = (read_hwpunit(payload, 52), read_hwpunit(payload, 56));
+    let cut = |edge: HwpUnit, from: HwpUnit| {
+        if edge.0 > 0 && from.0 > edge.0 {
+            HwpUnit(from.0 - edge.0)
+        } else {
+            HwpUnit(0)
+        }
+    };
+    let crop = Margin::new(
+        HwpUnit(left.0.max(0)),
+        HwpUnit(top.0.max(0)),
+        cut(right, original_width),
+        cut(bottom, original_height),
+    );
+
+    Picture {
+        id,
+        bindata_id: read_u16_le(payload, 71).unwrap_or(0) as u32,
+        filename: String::new(),
+        rect,
+        crop,
+        margin: Margin::new(
+            read_hwpunit16(payload, 60),
+            read_hwpunit16(payload, 64),
+            read_hwpunit16(payload, 62),
+            read_hwpunit16(payload, 66),
+        ),
+        border,
+        brightness: read_u8(payload, 68).unwrap_or(0) as i8,
+        contrast: read_u8(payload, 69).unwrap_or(0) as i8,
+        effect: ImageEffect::from_u32(read_u8(payload, 70).unwrap_or(0) as u32)
+            .unwrap_or(ImageEffect::RealPicture),
+        placement,
+    }
+}
+
+/// Parse an OLE object (SHAPE_COMPONENT_OLE)
+fn parse_ole(payload: &[u8], mut placement: ObjectPlacement, id: u32) -> OLE {
+    // Attributes: bits 0-7 draw aspect, bit 8 moniker, bits 9-15 baseline,
+    // bits 16-21 object type
+    let attr = read_u32_le(payload, 0).unwrap_or(0);
+    let extent = Size::new(read_hwpunit(payload, 4), read_hwpunit(payload, 8));
+    if placement.width.0 <= 0 || placement.height.0 <= 0 {
+        placement.width = extent.width;
+        placement.height = extent.height;
+    }
+
+    OLE {
+        id,
+        bindata_id: read_u16_le(payload, 12).unwrap_or(0) as u32,
+        extent,
+        draw_aspect: DrawAspect::from_u32(attr & 0xFF).unwrap_or(DrawAspect::Content),
+        object_type: OleObjectType::from_u32((attr >> 16) & 0x3F).unwrap_or(OleObjectType::Unknown),
+        border: read_border(payload, 14),
+        placement,
+    }
+}
+
+/// Border of a picture or OLE object: COLORREF color, INT32 thickness,
+/// UINT32 attributes (bits 0-5 line type)
+fn read_border(payload: &[u8], offset: usize) -> Border {
+    let thickness = HwpUnit(read_u32_le(payload, offset + 4).unwrap_or(0) as i32);
+    Border {
+        style: line_style_from_hwp((read_u32_le(payload, offset + 8).unwrap_or(0) & 0x3F) as u8),
+        width: (thickness.to_mm() * 20.0).round().clamp(0.0, 255.0) as u8,
+        color: Color(read_u32_le(payload, offset).unwrap_or(0)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Block, Inline};
+    use crate::parser::Record;
+
+    fn node(tagid: u16, level: u16, payload: Vec<u8>, children: Vec<RecordNode>) -> RecordNode {
+        RecordNode {
+            record: Record {
+                tagid,
+                tagname: String::new(),
+                level,
+                size: payload.len() as u32,
+                payload,
+            },
+            children,
+        }
+    }
+
+    #[test]
+    fn test_parse_shape_text() {
+        // Vertical text centered, margins 100/200/300/400, width 5000
+        let mut list_header = vec![1, 0, 0, 0];
+        list_header.extend((1u32 | 1 << 5).to_le_bytes());
+        for margin in [100u16, 200, 300, 400] {
+            list_header.extend(margin.to_le_bytes());
+        }
+        list_header.extend(5000u32.to_le_bytes());
+        let text: Vec<u8> = "Hi\r"
+            .encode_utf16()
+            .flat_map(|u| u.to_le_bytes())
+            .collect();
+        let paragraph = node(
+            HWPTAG_PARA_HEADER,
+            2,
+            vec![0; 22],
+            vec![node(HWPTAG_PARA_TEXT, 3, text, Vec::new())],
+        );
+        let component = node(
+            HWPTAG_SHAPE_COMPONENT,
+            1,
+            Vec::new(),
+            vec![
+                node(HWPTAG_LIST_HEADER, 2, list_header, Vec::new()),
+                paragraph,
+                node(HWPTAG_SHAPE_COMPONENT_RECTANGLE, 2, vec![0; 33], Vec::new()),
+            ],
+        );
+
+        let rect = Rect::new(HwpUnit(0), HwpUnit(0), HwpUnit(5000), HwpUnit(2000));
+        let mut ctx = BodyTextContext::new();
+        let text = parse_shape_text(&component, rect, 7, &mut ctx)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            text.margin,
+            Margin::new(HwpUnit(100), HwpUnit(300), HwpUnit(200), HwpUnit(400))
+        );
+        assert_eq!(text.v_align, VAlignment::Center);
+        assert_eq!(text.text_direction, TextDirection::Vertical);
+        // The shape record after the paragraphs is not part of the text
+        let [Block::Paragraph(para)] = &text.content[..] else {
+            panic!("expected one paragraph");
+        };
+        assert!(matches!(&para.inlines[..], [Inline::Text(run)] if run.text == "Hi"));
+    }
+
+    #[test]
+    fn test_parse_curve_points_and_segments() {
+        // INT16 count, three points, two segment types
+        let mut payload = 3u16.to_le_bytes().to_vec();
+        for value in [0i32, 0, 100, 50, 200, 0] {
+            payload.extend(value.to_le_bytes());
+        }
+        payload.extend([1u8, 0]);
+
+        let Some(ShapeType::Curve { points, segments }) =
+            parse_shape_type(HWPTAG_SHAPE_COMPONENT_CURVE, &payload)
+        else {
+            panic!("expected a curve");
+        };
+        assert_eq!(points.len(), 3);
+        assert_eq!(points[1], Position::new(HwpUnit(100), HwpUnit(50)));
+        assert_eq!(segments, vec![CurveSegment::Curve, CurveSegment::Line]);
+    }
+}
@@ -0,0 +1,160 @@
+use super::bodytext::{BodyTextContext, parse_paragraph_list};
+use super::control::{parse_caption, parse_object_placement, read_hwpunit, read_hwpunit16};
+use crate::common::{HwpResult, Margin, VAlignment, read_u16_le, read_u32_le};
+use crate::model::{Table, TableCell, TablePageBreak, TableZone, TextDirection};
+use crate::parser::RecordNode;
+use crate::parser::tags::*;
+
+/// Parse table control (tbl): common object attributes, TABLE record and
+/// one LIST_HEADER per cell followed by the cell's paragraphs
+pub(crate) fn parse_table(node: &RecordNode, ctx: &mut BodyTextContext) -> HwpResult<Table> {
+    let mut table = Table::new(ctx.next_object_id());
+
+    table.placement = parse_object_placement(node.payload());
+    table.placement.caption = parse_caption(node, HWPTAG_TABLE, ctx)?;
+
+    // Cell lists follow the TABLE record; a list before it is the caption
+    let children = &node.children;
+    let mut idx = children
+        .iter()
+        .position(|c| c.tagid() == HWPTAG_TABLE)
+        .unwrap_or(children.len());
+    while idx < children.len() {
+        let child = &children[idx];
+        idx += 1;
+        match child.tagid() {
+            HWPTAG_TABLE => parse_table_record(child.payload(), &mut table),
+            HWPTAG_LIST_HEADER => {
+                // Cell paragraphs are siblings following the LIST_HEADER
+                let start = idx;
+                while idx < children.len() && children[idx].tagid() == HWPTAG_PARA_HEADER {
+                    idx += 1;
+                }
+                let mut cell = parse_cell_header(child.payload());
+                cell.content = parse_paragraph_list(&children[start..idx], ctx)?;
+                table.cells.push(cell);
+            }
+            _ => {}
+        }
+    }
+
+    Ok(table)
+}
+
+/// Parse HWPTAG_TABLE payload
+fn parse_table_record(payload: &[u8], table: &mut Table) {
+    let attr = read_u32_le(payload, 0).unwrap_or(0);
+    table.page_break = TablePageBreak::from_u32(attr & 0x03).unwrap_or(TablePageBreak::None);
+    table.repeat_header = attr & 0x04 != 0;
+
+    table.rows = read_u16_le(payload, 4).unwrap_or(0) as usize;
+    table.cols = read_u16_le(payload, 6).unwrap_or(0) as usize;
+    table.cell_spacing = read_hwpunit16(payload, 8);
+    table.inner_margin = Margin::new(
+        read_hwpunit16(payload, 10),
+        read_hwpunit16(payload, 14),
+        read_hwpunit16(payload, 12),
+        read_hwpunit16(payload, 16),
+    );
+
+    // Skip the number of cells in each row
+    let mut offset = 18 + table.rows * 2;
+
+    table.border_fill_id = read_u16_le(payload, offset).unwrap_or(0) as u32;
+    offset += 2;
+
+    // Zone info (5.0.1.0+): start col, start row, end col, end row, border fill ID
+    let zone_count = read_u16_le(payload, offset).unwrap_or(0) as usize;
+    offset += 2;
+    for i in 0..zone_count {
+        let base = offset + i * 10;
+        let (Some(start_col), Some(start_row), Some(end_col), Some(end_row), Some(border_fill_id)) = (
+            read_u16_le(payload, base),
+            read_u16_le(payload, base + 2),
+            read_u16_le(payload, base + 4),
+            read_u16_le(payload, base + 6),
+            read_u16_le(payload, base + 8),
+        ) else {
+            break;
+        };
+        table.zones.push(TableZone {
+            start_row: start_row as usize,
+            start_col: start_col as usize,
+            end_row: end_row as usize,
+            end_col: end_col as usize,
+            border_fill_id: border_fill_id as u32,
+        });
+    }
+}
+
+/// Parse a cell's LIST_HEADER payload
+/// UINT16 para count, UINT16, UINT32 list attributes, then the cell properties
+fn parse_cell_header(payload: &[u8]) -> TableCell {
+    let list_attr = read_u32_le(payload, 4).unwrap_or(0);
+
+    TableCell {
+        col: read_u16_le(payload, 8).unwrap_or(0) as usize,
+        row: read_u16_le(payload, 10).unwrap_or(0) as usize,
+        col_span: read_u16_le(payload, 12).unwrap_or(1).max(1) as usize,
+        row_span: read_u16_le(payload, 14).unwrap_or(1).max(1) as usize,
+        width: read_hwpunit(payload, 16),
+        height: read_hwpunit(payload, 20),
+        margin: Margin::new(
+            read_hwpunit16(payload, 24),
+            read_hwpunit16(payload, 28),
+            read_hwpunit16(payload, 26),
+            read_hwpunit16(payload, 30),
+        ),
+        border_fill_id: read_u16_le(payload, 32).unwrap_or(0) as u32,
+        v_align: VAlignment::from_u16(((list_attr >> 5) & 0x03) as u16).unwrap_or(VAlignment::Top),
+        text_direction: TextDirection::from_u32(list_attr & 0x07)
+            .unwrap_or(TextDirection::Horizontal),
+        content: Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn u16s(values: &[u16]) -> Vec<u8> {
+        values.iter().flat_map(|v| v.to_le_bytes()).collect()
+    }
+
+    #[test]
+    fn test_parse_table_record() {
+        // Repeat header, 2x3, spacing 0, margins 510/510/141/141, row sizes, border fill 3
+        let mut payload = 0x04u32.to_le_bytes().to_vec();
+        payload.extend(u16s(&[2, 3, 0, 510, 510, 141, 141, 3, 2, 3]));
+        // One zone: cols 0-1 of row 1 with border fill 5
+        payload.extend(u16s(&[1, 0, 1, 1, 1, 5]));
+
+        let mut table = Table::new(1);
+        parse_table_record(&payload, &mut table);
+        assert_eq!((table.rows, table.cols), (2, 3));
+        assert!(table.repeat_header);
+        assert_eq!(table.border_fill_id, 3);
+        assert_eq!(table.inner_margin.top.0, 141);
+        assert_eq!(table.zones.len(), 1);
+        assert_eq!(table.zones[0].end_col, 1);
+        assert_eq!(table.zones[0].border_fill_id, 5);
+    }
+
+    #[test]
+    fn test_parse_cell_header() {
+        // 1 paragraph, vertical align center, col 2 row 1, span 2x1
+        let mut payload = u16s(&[1, 0]);
+        payload.extend((1u32 << 5).to_le_bytes());
+        payload.extend(u16s(&[2, 1, 2, 1]));
+        payload.extend(8000u32.to_le_bytes());
+        payload.extend(1000u32.to_le_bytes());
+        payload.extend(u16s(&[141, 141, 100, 100, 4]));
+
+        let cell = parse_cell_header(&payload);
+        assert_eq!((cell.row, cell.col), (1, 2));
+        assert_eq!((cell.row_span, cell.col_span), (1, 2));
+        assert_eq!(cell.width.0, 8000);
+        assert_eq!(cell.border_fill_id, 4);
+        assert_eq!(cell.v_align, VAlignment::Center);
+    }
+}
@@ -1,4 +1,6 @@
-use crate::common::{Alignment, Color, HwpUnit, LineStyle, Margin, Rect};
+use crate::common::{
+    Alignment, Color, HwpUnit, LineStyle, Margin, Position, Rect, Size, VAlignment,
+};
 use serde::{Deserialize, Serialize};
 
 /// Top-level HWP document model
@@ -6,10 +8,14 @@ use serde::{Deserialize, Serialize};
 pub struct Document {
     pub sections: Vec<Section>,
     pub fonts: Vec<String>,
+    pub font_counts: Vec<u32>, // Fonts of each language group, in `fonts` order
     pub styles: Vec<Style>,
     pub char_shapes: Vec<CharShape>,
     pub para_shapes: Vec<ParaShape>,
     pub border_fills: Vec<BorderFill>,
+    pub bin_data: Vec<BinData>,
+    pub track_changes: Vec<TrackChange>,
+    pub track_change_authors: Vec<String>,
 }
 
 impl Document {
@@ -17,12 +23,36 @@ impl Document {
         Document {
             sections: Vec::new(),
             fonts: Vec::new(),
+            font_counts: Vec::new(),
             styles: Vec::new(),
             char_shapes: Vec::new(),
             para_shapes: Vec::new(),
             border_fills: Vec::new(),
+            bin_data: Vec::new(),
+            track_changes: Vec::new(),
+            track_change_authors: Vec::new(),
         }
     }
+
+    /// Index into `fonts` of a char shape's font for a language group
+    pub fn font_index(&self, lang: usize, font_id: u32) -> u32 {
+        self.font_counts.iter().take(lang).sum::<u32>() + font_id
+    }
+
+    /// Look up binary data by the ID pictures and OLE objects refer to
+    pub fn bin_data(&self, id: u32) -> Option<&BinData> {
+        self.bin_data.iter().find(|b| b.id == id)
+    }
+
+    /// Look up a tracked change by the ID text runs refer to
+    pub fn track_change(&self, id: u32) -> Option<&TrackChange> {
+        self.track_changes.iter().find(|c| c.id == id)
+    }
+
+    /// Look up a border fill by its (1-based) ID
+    pub fn border_fill(&self, id: u32) -> Option<&BorderFill> {
+        self.border_fills.iter().find(|bf| bf.id == id)
+    }
 }
 
 impl Default for Document {
@@ -41,18 +71,93 @@ pub struct Section {
     pub margin_bottom: HwpUnit,
     pub margin_left: HwpUnit,
     pub margin_right: HwpUnit,
+    pub margin_header: HwpUnit,
+    pub margin_footer: HwpUnit,
+    pub margin_gutter: HwpUnit,
+    pub landscape: bool, // Paper size is stored unrotated
+    pub binding: BindingType,
+    pub text_direction: TextDirection,
+    pub column_gap: HwpUnit,
+    pub grid_vertical: HwpUnit,   // 세로 줄맞춤 간격 (0 = off)
+    pub grid_horizontal: HwpUnit, // 가로 줄맞춤 간격 (0 = off)
+    pub default_tab_spacing: HwpUnit,
+    pub start_numbers: StartNumbers,
+    pub footnote_shape: NoteShape,
+    pub endnote_shape: NoteShape,
+    pub page_border_fills: Vec<PageBorderFill>, // Both, even, odd pages
+    pub columns: ColumnDef,                     // Column layout at section start
+    pub headers: Vec<HeaderFooter>,
+    pub footers: Vec<HeaderFooter>,
+    pub hide_first_header: bool, // Hidden on the section's first page
+    pub hide_first_footer: bool,
+    pub hide_first_page_number: bool,
+    pub first_page_parity: PageApply, // Start on an odd or even page
+    pub page_number_position: Option<PageNumberPosition>,
+    pub memos: Vec<Memo>, // Contents of the section's memo fields
 }
 
 impl Section {
     pub fn new() -> Self {
+        // Hancom defaults: A4 paper, 30mm left/right, 20mm top, 15mm bottom/header/footer
         Section {
             blocks: Vec::new(),
-            page_width: HwpUnit(5644),  // 210mm default
-            page_height: HwpUnit(7990), // 297mm default
-            margin_top: HwpUnit(1440),
-            margin_bottom: HwpUnit(1440),
-            margin_left: HwpUnit(1440),
-            margin_right: HwpUnit(1440),
+            page_width: HwpUnit(59528),  // 210mm
+            page_height: HwpUnit(84188), // 297mm
+            margin_top: HwpUnit(5668),
+            margin_bottom: HwpUnit(4252),
+            margin_left: HwpUnit(8504),
+            margin_right: HwpUnit(8504),
+            margin_header: HwpUnit(4252),
+            margin_footer: HwpUnit(4252),
+            margin_gutter: HwpUnit(0),
+            landscape: false,
+            binding: BindingType::SingleSided,
+            text_direction: TextDirection::Horizontal,
+            column_gap: HwpUnit(0),
+            grid_vertical: HwpUnit(0),
+            grid_horizontal: HwpUnit(0),
+            default_tab_spacing: HwpUnit(8000),
+            start_numbers: StartNumbers::default(),
+            footnote_shape: NoteShape::new(),
+            endnote_shape: NoteShape::new(),
+            page_border_fills: Vec::new(),
+            columns: ColumnDef::new(),
+            headers: Vec::new(),
+            footers: Vec::new(),
+            hide_first_header: false,
+            hide_first_footer: false,
+            hide_first_page_number: false,
+            first_page_parity: PageApply::Both,
+            page_number_position: None,
+            memos: Vec::new(),
+        }
+    }
+
+    /// Apply a page hiding or odd/even adjust found on the section's first page
+    pub fn apply_first_page(&mut self, page: PageOverride) {
+        self.hide_first_header |= page.hide_header;
+        self.hide_first_footer |= page.hide_footer;
+        self.hide_first_page_number |= page.hide_page_number;
+        if page.parity != PageApply::Both {
+            self.first_page_parity = page.parity;
+        }
+    }
+
+    /// Page width as laid out, taking orientation into account
+    pub fn layout_width(&self) -> HwpUnit {
+        if self.landscape {
+            self.page_height
+        } else {
+            self.page_width
+        }
+    }
+
+    /// Page height as laid out, taking orientation into account
+    pub fn layout_height(&self) -> HwpUnit {
+        if self.landscape {
+            self.page_width
+        } else {
+            self.page_height
         }
     }
 }
@@ -63,12 +168,481 @@ impl Default for Section {
     }
 }
 
+/// Binding (제책 방법)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum BindingType {
+    SingleSided = 0, // 한쪽 편집
+    DoubleSided = 1, // 맞쪽 편집
+    TopFlip = 2,     // 위로 넘기기
+}
+
+impl BindingType {
+    pub fn from_u32(val: u32) -> Option<Self> {
+        match val {
+            0 => Some(BindingType::SingleSided),
+            1 => Some(BindingType::DoubleSided),
+            2 => Some(BindingType::TopFlip),
+            _ => None,
+        }
+    }
+}
+
+/// Text direction (글자 방향)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TextDirection {
+    Horizontal = 0,
+    Vertical = 1,        // Latin letters lie on their side
+    VerticalUpright = 2, // Latin letters stand upright (영문 세움)
+}
+
+impl TextDirection {
+    pub fn from_u32(val: u32) -> Option<Self> {
+        match val {
+            0 => Some(TextDirection::Horizontal),
+            1 => Some(TextDirection::Vertical),
+            2 => Some(TextDirection::VerticalUpright),
+            _ => None,
+        }
+    }
+
+    pub fn is_vertical(self) -> bool {
+        self != TextDirection::Horizontal
+    }
+}
+
+/// Starting numbers of a section (0 = continue from previous section)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct StartNumbers {
+    pub page: u16,
+    pub figure: u16,
+    pub table: u16,
+    pub equation: u16,
+}
+
+/// Footnote/endnote shape (FOOTNOTE_SHAPE)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NoteShape {
+    pub number_format: NumberFormat,
+    pub user_symbol: Option<char>,
+    pub prefix: Option<char>,
+    pub suffix: Option<char>,
+    pub start_number: u16,
+    pub placement: u8, // Footnote: 0 each column, 1 merged, 2 right column; endnote: 0 document, 1 section
+    pub numbering: NoteNumbering,
+    pub superscript: bool,
+    pub separator_length: HwpUnit,
+    pub separator_margin_top: HwpUnit,
+    pub separator_margin_bottom: HwpUnit,
+    pub note_spacing: HwpUnit,
+    pub separator_line: Border,
+}
+
+impl NoteShape {
+    pub fn new() -> Self {
+        NoteShape {
+            number_format: NumberFormat::Digit,
+            user_symbol: None,
+            prefix: None,
+            suffix: Some(')'),
+            start_number: 1,
+            placement: 0,
+            numbering: NoteNumbering::Continue,
+            superscript: false,
+            separator_length: HwpUnit(-1),
+            separator_margin_top: HwpUnit(850),
+            separator_margin_bottom: HwpUnit(567),
+            note_spacing: HwpUnit(283),
+            separator_line: Border::none(),
+        }
+    }
+}
+
+impl Default for NoteShape {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Note numbering scheme
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum NoteNumbering {
+    Continue = 0,
+    RestartSection = 1,
+    RestartPage = 2,
+}
+
+impl NoteNumbering {
+    pub fn from_u32(val: u32) -> Option<Self> {
+        match val {
+            0 => Some(NoteNumbering::Continue),
+            1 => Some(NoteNumbering::RestartSection),
+            2 => Some(NoteNumbering::RestartPage),
+            _ => None,
+        }
+    }
+}
+
+/// Number format (번호 모양)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum NumberFormat {
+    Digit = 0,                 // 1, 2, 3
+    CircledDigit = 1,          // ①, ②, ③
+    RomanUpper = 2,            // I, II, III
+    RomanLower = 3,            // i, ii, iii
+    LatinUpper = 4,            // A, B, C
+    LatinLower = 5,            // a, b, c
+    CircledLatinUpper = 6,     // Ⓐ, Ⓑ, Ⓒ
+    CircledLatinLower = 7,     // ⓐ, ⓑ, ⓒ
+    HangulSyllable = 8,        // 가, 나, 다
+    CircledHangulSyllable = 9, // ㉮, ㉯, ㉰
+    HangulJamo = 10,           // ㄱ, ㄴ, ㄷ
+    CircledHangulJamo = 11,    // ㉠, ㉡, ㉢
+    HangulNumber = 12,         // 일, 이, 삼
+    Ideograph = 13,            // 一, 二, 三
+    CircledIdeograph = 14,     // ㊀, ㊁, ㊂
+    DecagonHangul = 15,        // 갑, 을, 병
+    DecagonIdeograph = 16,     // 甲, 乙, 丙
+    Symbol = 0x80,             // Four symbols repeated
+    UserChar = 0x81,           // User-defined character
+}
+
+impl NumberFormat {
+    pub fn from_u8(val: u8) -> Option<Self> {
+        match val {
+            0 => Some(NumberFormat::Digit),
+            1 => Some(NumberFormat::CircledDigit),
+            2 => Some(NumberFormat::RomanUpper),
+            3 => Some(NumberFormat::RomanLower),
+            4 => Some(NumberFormat::LatinUpper),
+            5 => Some(NumberFormat::LatinLower),
+            6 => Some(NumberFormat::CircledLatinUpper),
+            7 => Some(NumberFormat::CircledLatinLower),
+            8 => Some(NumberFormat::HangulSyllable),
+            9 => Some(NumberFormat::CircledHangulSyllable),
+            10 => Some(NumberFormat::HangulJamo),
+            11 => Some(NumberFormat::CircledHangulJamo),
+            12 => Some(NumberFormat::HangulNumber),
+            13 => Some(NumberFormat::Ideograph),
+            14 => Some(NumberFormat::CircledIdeograph),
+            15 => Some(NumberFormat::DecagonHangul),
+            16 => Some(NumberFormat::DecagonIdeograph),
+            0x80 => Some(NumberFormat::Symbol),
+            0x81 => Some(NumberFormat::UserChar),
+            _ => None,
+        }
+    }
+
+    /// ODF `style:num-format` value
+    pub fn to_odt_str(&self) -> &'static str {
+        match self {
+            NumberFormat::Digit => "1",
+            NumberFormat::CircledDigit => "①, ②, ③, ...",
+            NumberFormat::RomanUpper => "I",
+            NumberFormat::RomanLower => "i",
+            NumberFormat::LatinUpper => "A",
+            NumberFormat::LatinLower => "a",
+            NumberFormat::CircledLatinUpper => "Ⓐ, Ⓑ, Ⓒ, ...",
+            NumberFormat::CircledLatinLower => "ⓐ, ⓑ, ⓒ, ...",
+            NumberFormat::HangulSyllable => "가, 나, 다, ...",
+            NumberFormat::CircledHangulSyllable => "㉮, ㉯, ㉰, ...",
+            NumberFormat::HangulJamo => "ㄱ, ㄴ, ㄷ, ...",
+            NumberFormat::CircledHangulJamo => "㉠, ㉡, ㉢, ...",
+            NumberFormat::HangulNumber => "일, 이, 삼, ...",
+            NumberFormat::Ideograph => "一, 二, 三, ...",
+            NumberFormat::CircledIdeograph => "㊀, ㊁, ㊂, ...",
+            NumberFormat::DecagonHangul => "갑, 을, 병, ...",
+            NumberFormat::DecagonIdeograph => "甲, 乙, 丙, ...",
+            NumberFormat::Symbol | NumberFormat::UserChar => "1",
+        }
+    }
+
+    /// Render a (1-based) number in this format.
+    /// Formats without a simple rule fall back to digits.
+    pub fn format(&self, n: u32) -> String {
+        const HANGUL_SYLLABLES: [char; 14] = [
+            '가', '나', '다', '라', '마', '바', '사', '아', '자', '차', '카', '타', '파', '하',
+        ];
+        const HANGUL_JAMO: [char; 14] = [
+            'ㄱ', 'ㄴ', 'ㄷ', 'ㄹ', 'ㅁ', 'ㅂ', 'ㅅ', 'ㅇ', 'ㅈ', 'ㅊ', 'ㅋ', 'ㅌ', 'ㅍ', 'ㅎ',
+        ];
+        const SYMBOLS: [char; 4] = ['*', '†', '‡', '§'];
+
+        let offset = |base: u32, count: u32| {
+            (1..=count)
+                .contains(&n)
+                .then(|| char::from_u32(base + n - 1))
+                .flatten()
+                .map(String::from)
+        };
+        let latin = |base: u8| {
+            // A..Z, then AA..ZZ and so on
+            let letter = (base + ((n - 1) % 26) as u8) as char;
+            letter.to_string().repeat(((n - 1) / 26 + 1) as usize)
+        };
+
+        let formatted = match self {
+            _ if n == 0 => None,
+            NumberFormat::CircledDigit => offset(0x2460, 20),
+            NumberFormat::RomanUpper => Some(to_roman(n)),
+            NumberFormat::RomanLower => Some(to_roman(n).to_lowercase()),
+            NumberFormat::LatinUpper => Some(latin(b'A')),
+            NumberFormat::LatinLower => Some(latin(b'a')),
+            NumberFormat::CircledLatinUpper => offset(0x24B6, 26),
+            NumberFormat::CircledLatinLower => offset(0x24D0, 26),
+            NumberFormat::HangulSyllable => {
+                HANGUL_SYLLABLES.get(n as usize - 1).map(|c| c.to_string())
+            }
+            NumberFormat::CircledHangulSyllable => offset(0x326E, 14),
+            NumberFormat::HangulJamo => HANGUL_JAMO.get(n as usize - 1).map(|c| c.to_string()),
+            NumberFormat::CircledHangulJamo => offset(0x3260, 14),
+            NumberFormat::CircledIdeograph => offset(0x3280, 10),
+            NumberFormat::Symbol => {
+                // *, †, ‡, §, then doubled: **, ††, ...
+                let symbol = SYMBOLS[(n as usize - 1) % 4];
+                Some(symbol.to_string().repeat((n as usize - 1) / 4 + 1))
+            }
+            _ => None,
+        };
+        formatted.unwrap_or_else(|| n.to_string())
+    }
+}
+
+/// Roman numerals for 1..=3999 (larger numbers repeat M)
+fn to_roman(mut n: u32) -> String {
+    const NUMERALS: [(u32, &str); 13] = [
+        (1000, "M"),
+        (900, "CM"),
+        (500, "D"),
+        (400, "CD"),
+        (100, "C"),
+        (90, "XC"),
+        (50, "L"),
+        (40, "XL"),
+        (10, "X"),
+        (9, "IX"),
+        (5, "V"),
+        (4, "IV"),
+        (1, "I"),
+    ];
+    let mut result = String::new();
+    for (value, numeral) in NUMERALS {
+        while n >= value {
+            result.push_str(numeral);
+            n -= value;
+        }
+    }
+    result
+}
+
+/// Column definition (단 정의, cold control)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ColumnDef {
+    pub column_type: ColumnType,
+    pub count: u8,
+    pub direction: ColumnDirection,
+    pub same_width: bool,
+    pub gap: HwpUnit,
+    pub widths: Vec<HwpUnit>, // Only when column widths differ
+    pub separator: Border,
+}
+
+impl ColumnDef {
+    pub fn new() -> Self {
+        ColumnDef {
+            column_type: ColumnType::Normal,
+            count: 1,
+            direction: ColumnDirection::LeftToRight,
+            same_width: true,
+            gap: HwpUnit(0),
+            widths: Vec::new(),
+            separator: Border::none(),
+        }
+    }
+}
+
+impl Default for ColumnDef {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Column type (단 종류)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ColumnType {
+    Normal = 0,      // 일반 다단
+    Distributed = 1, // 배분 다단
+    Parallel = 2,    // 평행 다단
+}
+
+impl ColumnType {
+    pub fn from_u16(val: u16) -> Option<Self> {
+        match val {
+            0 => Some(ColumnType::Normal),
+            1 => Some(ColumnType::Distributed),
+            2 => Some(ColumnType::Parallel),
+            _ => None,
+        }
+    }
+}
+
+/// Column direction (단 방향)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ColumnDirection {
+    LeftToRight = 0,
+    RightToLeft = 1,
+    Facing = 2, // 맞쪽
+}
+
+impl ColumnDirection {
+    pub fn from_u16(val: u16) -> Option<Self> {
+        match val {
+            0 => Some(ColumnDirection::LeftToRight),
+            1 => Some(ColumnDirection::RightToLeft),
+            2 => Some(ColumnDirection::Facing),
+            _ => None,
+        }
+    }
+}
+
+/// Page border and fill (PAGE_BORDER_FILL)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct PageBorderFill {
+    pub relative_to_paper: bool, // Spacing measured from paper edge instead of body
+    pub include_header: bool,
+    pub include_footer: bool,
+    pub fill_area: u8, // 0 paper, 1 page, 2 border
+    pub spacing: Margin,
+    pub border_fill_id: u32,
+}
+
+/// Page number position (쪽 번호 위치, pgnp control)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PageNumberPosition {
+    pub format: NumberFormat,
+    pub place: PageNumberPlace,
+    pub user_symbol: Option<char>,
+    pub prefix: Option<char>,
+    pub suffix: Option<char>,
+    pub dash: Option<char>, // Decoration on both sides ("- 1 -")
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PageNumberPlace {
+    None = 0,
+    TopLeft = 1,
+    TopCenter = 2,
+    TopRight = 3,
+    BottomLeft = 4,
+    BottomCenter = 5,
+    BottomRight = 6,
+    OutsideTop = 7,
+    OutsideBottom = 8,
+    InsideTop = 9,
+    InsideBottom = 10,
+}
+
+impl PageNumberPlace {
+    pub fn from_u32(val: u32) -> Option<Self> {
+        match val {
+            0 => Some(PageNumberPlace::None),
+            1 => Some(PageNumberPlace::TopLeft),
+            2 => Some(PageNumberPlace::TopCenter),
+            3 => Some(PageNumberPlace::TopRight),
+            4 => Some(PageNumberPlace::BottomLeft),
+            5 => Some(PageNumberPlace::BottomCenter),
+            6 => Some(PageNumberPlace::BottomRight),
+            7 => Some(PageNumberPlace::OutsideTop),
+            8 => Some(PageNumberPlace::OutsideBottom),
+            9 => Some(PageNumberPlace::InsideTop),
+            10 => Some(PageNumberPlace::InsideBottom),
+            _ => None,
+        }
+    }
+
+    pub fn is_bottom(&self) -> bool {
+        matches!(
+            self,
+            PageNumberPlace::BottomLeft
+                | PageNumberPlace::BottomCenter
+                | PageNumberPlace::BottomRight
+                | PageNumberPlace::OutsideBottom
+                | PageNumberPlace::InsideBottom
+        )
+    }
+}
+
+/// Header or footer (머리말/꼬리말)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HeaderFooter {
+    pub apply_to: PageApply,
+    pub text_width: HwpUnit,
+    pub text_height: HwpUnit,
+    pub blocks: Vec<Block>,
+}
+
+/// Pages a header or footer applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PageApply {
+    Both = 0,
+    Even = 1,
+    Odd = 2,
+}
+
+impl PageApply {
+    pub fn from_u32(val: u32) -> Option<Self> {
+        match val {
+            0 => Some(PageApply::Both),
+            1 => Some(PageApply::Even),
+            2 => Some(PageApply::Odd),
+            _ => None,
+        }
+    }
+}
+
+/// Page hiding (감추기, pghd) or odd/even adjust (홀/짝수 조정, pgct) for the
+/// page the control is on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PageOverride {
+    pub hide_header: bool,
+    pub hide_footer: bool,
+    pub hide_page_number: bool,
+    pub parity: PageApply, // Page starts on an odd or even page
+}
+
+impl PageOverride {
+    pub fn new() -> Self {
+        PageOverride {
+            hide_header: false,
+            hide_footer: false,
+            hide_page_number: false,
+            parity: PageApply::Both,
+        }
+    }
+
+    /// Combine with another control on the same page
+    pub fn merge(&mut self, other: &PageOverride) {
+        self.hide_header |= other.hide_header;
+        self.hide_footer |= other.hide_footer;
+        self.hide_page_number |= other.hide_page_number;
+        if other.parity != PageApply::Both {
+            self.parity = other.parity;
+        }
+    }
+}
+
+impl Default for PageOverride {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Block-level content (Paragraph, Table, etc.)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Block {
     Paragraph(Paragraph),
     Table(Table),
     Shape(Shape),
+    Columns(ColumnDef), // Column layout changes from here on
 }
 
 /// Paragraph
@@ -79,6 +653,10 @@ pub struct Paragraph {
     pub para_shape_id: u32,
     pub char_shape_id: u32,
     pub level: u8,
+    pub char_count: u32,   // WCHAR count including control characters
+    pub control_mask: u32, // Bit n set if control char n appears in text
+    pub page_break: bool,
+    pub column_break: bool,
     pub inlines: Vec<Inline>,
 }
 
@@ -90,6 +668,10 @@ impl Paragraph {
             para_shape_id: 0,
             char_shape_id: 0,
             level: 0,
+            char_count: 0,
+            control_mask: 0,
+            page_break: false,
+            column_break: false,
             inlines: Vec::new(),
         }
     }
@@ -101,6 +683,18 @@ pub enum Inline {
     Text(TextRun),
     Control(Control),
     Field(Field),
+    FieldRange(FieldRange),
+    Bookmark(String), // Point bookmark; range bookmarks are field ranges
+    AutoNumber(AutoNumber),
+    NewNumber(NewNumber),
+    Dutmal(Dutmal),
+    CharOverlap(CharOverlap),
+    PageOverride(PageOverride), // After the section's first page
+    Tab,
+    LineBreak,
+    SoftHyphen,       // Hyphen (하이픈), shown only at line ends
+    NonBreakingSpace, // 묶음 빈칸
+    FixedWidthSpace,  // 고정폭 빈칸
 }
 
 /// Text run with consistent character shape
@@ -108,6 +702,112 @@ pub enum Inline {
 pub struct TextRun {
     pub text: String,
     pub char_shape_id: u32,
+    pub change_id: Option<u32>, // Tracked change the text belongs to
+}
+
+/// Dutmal (덧말, tdut control): ruby text set above or below base text
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Dutmal {
+    pub base: String,
+    pub text: String,
+    pub position: DutmalPosition,
+    pub size_ratio: u32, // Size of the dutmal in percent of the base text
+    pub align: DutmalAlign,
+    pub char_shape_id: u32, // Shape of the text around the control
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DutmalPosition {
+    Above = 0,
+    Below = 1,
+    Center = 2,
+}
+
+impl DutmalPosition {
+    pub fn from_u32(val: u32) -> Option<Self> {
+        match val {
+            0 => Some(DutmalPosition::Above),
+            1 => Some(DutmalPosition::Below),
+            2 => Some(DutmalPosition::Center),
+            _ => None,
+        }
+    }
+}
+
+/// Alignment of the dutmal over its base text
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DutmalAlign {
+    Justify = 0,
+    Left = 1,
+    Right = 2,
+    Center = 3,
+    Distribute = 4,
+    Divide = 5,
+}
+
+impl DutmalAlign {
+    pub fn from_u32(val: u32) -> Option<Self> {
+        match val {
+            0 => Some(DutmalAlign::Justify),
+            1 => Some(DutmalAlign::Left),
+            2 => Some(DutmalAlign::Right),
+            3 => Some(DutmalAlign::Center),
+            4 => Some(DutmalAlign::Distribute),
+            5 => Some(DutmalAlign::Divide),
+            _ => None,
+        }
+    }
+}
+
+/// Overlapping characters (글자 겹침, tcps control), e.g. a circled number
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CharOverlap {
+    pub chars: String,
+    pub border: OverlapBorder,
+    pub inner_size: i8,     // Size adjustment of the characters inside a border
+    pub char_shape_id: u32, // Shape of the text around the control
+}
+
+/// Border drawn around overlapping characters; "inverted" borders are
+/// filled with the characters cut out
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OverlapBorder {
+    None = 0,
+    Circle = 1,
+    InvertedCircle = 2,
+    Rectangle = 3,
+    InvertedRectangle = 4,
+    Triangle = 5,
+    InvertedTriangle = 6,
+    Light = 7,
+    Rhombus = 8,
+    InvertedRhombus = 9,
+    RoundedRectangle = 10,
+    CirculatingTriangle = 11,
+    ThinCirculatingTriangle = 12,
+    ThickCirculatingTriangle = 13,
+}
+
+impl OverlapBorder {
+    pub fn from_u8(val: u8) -> Option<Self> {
+        match val {
+            0 => Some(OverlapBorder::None),
+            1 => Some(OverlapBorder::Circle),
+            2 => Some(OverlapBorder::InvertedCircle),
+            3 => Some(OverlapBorder::Rectangle),
+            4 => Some(OverlapBorder::InvertedRectangle),
+            5 => Some(OverlapBorder::Triangle),
+            6 => Some(OverlapBorder::InvertedTriangle),
+            7 => Some(OverlapBorder::Light),
+            8 => Some(OverlapBorder::Rhombus),
+            9 => Some(OverlapBorder::InvertedRhombus),
+            10 => Some(OverlapBorder::RoundedRectangle),
+            11 => Some(OverlapBorder::CirculatingTriangle),
+            12 => Some(OverlapBorder::ThinCirculatingTriangle),
+            13 => Some(OverlapBorder::ThickCirculatingTriangle),
+            _ => None,
+        }
+    }
 }
 
 /// Control (embedded object)
@@ -115,9 +815,45 @@ pub struct TextRun {
 pub enum Control {
     Table(Table),
     Picture(Picture),
+    Shape(Shape),
+    ShapeGroup(ShapeGroup),
     OLE(OLE),
     TextBox(TextBox),
     Equation(Equation),
+    Note(Note),
+    Annotation(Annotation),
+}
+
+/// Comment shown beside the text: a hidden comment (숨은 설명)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Annotation {
+    pub id: u32,
+    pub author: String,
+    pub date: String, // As stored, e.g. "2024-03-05 14:20:00"
+    pub blocks: Vec<Block>,
+}
+
+/// Contents of a memo (메모), matched to its field by number. The memo's
+/// shape (MEMO_SHAPE) is not kept.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Memo {
+    pub number: u32,
+    pub blocks: Vec<Block>,
+}
+
+/// Footnote or endnote (각주/미주) with its own paragraph list
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Note {
+    pub id: u32,
+    pub kind: NoteKind,
+    pub number: u32, // Number stored in the control (0 = automatic)
+    pub blocks: Vec<Block>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum NoteKind {
+    Footnote,
+    Endnote,
 }
 
 /// Table control
@@ -128,6 +864,59 @@ pub struct Table {
     pub cols: usize,
     pub cells: Vec<TableCell>,
     pub border_fill_id: u32,
+    pub placement: ObjectPlacement,
+    pub page_break: TablePageBreak,
+    pub repeat_header: bool,
+    pub cell_spacing: HwpUnit,
+    pub inner_margin: Margin,  // Default cell margins
+    pub zones: Vec<TableZone>, // Cell ranges with their own border/fill
+}
+
+impl Table {
+    pub fn new(id: u32) -> Self {
+        Table {
+            id,
+            rows: 0,
+            cols: 0,
+            cells: Vec::new(),
+            border_fill_id: 0,
+            placement: ObjectPlacement::new(),
+            page_break: TablePageBreak::None,
+            repeat_header: false,
+            cell_spacing: HwpUnit(0),
+            inner_margin: Margin::zero(),
+            zones: Vec::new(),
+        }
+    }
+}
+
+/// How a table breaks across pages (쪽 경계에서 나눔)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TablePageBreak {
+    None = 0,  // 나누지 않음
+    Cell = 1,  // 셀 단위로 나눔
+    Split = 2, // 나눔
+}
+
+impl TablePageBreak {
+    pub fn from_u32(val: u32) -> Option<Self> {
+        match val {
+            0 => Some(TablePageBreak::None),
+            1 => Some(TablePageBreak::Cell),
+            2 => Some(TablePageBreak::Split),
+            _ => None,
+        }
+    }
+}
+
+/// Cell range with its own border/fill (영역 속성)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct TableZone {
+    pub start_row: usize,
+    pub start_col: usize,
+    pub end_row: usize,
+    pub end_col: usize,
+    pub border_fill_id: u32,
 }
 
 /// Table cell
@@ -137,6 +926,12 @@ pub struct TableCell {
     pub col: usize,
     pub row_span: usize,
     pub col_span: usize,
+    pub width: HwpUnit,
+    pub height: HwpUnit,
+    pub margin: Margin,
+    pub border_fill_id: u32,
+    pub v_align: VAlignment,
+    pub text_direction: TextDirection,
     pub content: Vec<Block>, // Cell content (paragraphs, etc.)
 }
 
@@ -146,8 +941,325 @@ pub struct Picture {
     pub id: u32,
     pub bindata_id: u32,
     pub filename: String,
-    pub rect: Rect,
-    pub margin: Margin,
+    pub rect: Rect,     // Image extent in the picture's own coordinates
+    pub crop: Margin,   // Amount cut off each edge of the image
+    pub margin: Margin, // Inner margins
+    pub border: Border,
+    pub brightness: i8, // -100 ... 100
+    pub contrast: i8,   // -100 ... 100
+    pub effect: ImageEffect,
+    pub placement: ObjectPlacement,
+}
+
+/// Picture effect (그림 효과)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ImageEffect {
+    RealPicture = 0, // 원래 그림
+    Grayscale = 1,   // 회색조
+    BlackWhite = 2,  // 흑백
+    Pattern = 3,     // 8x8 패턴
+}
+
+impl ImageEffect {
+    pub fn from_u32(val: u32) -> Option<Self> {
+        match val {
+            0 => Some(ImageEffect::RealPicture),
+            1 => Some(ImageEffect::Grayscale),
+            2 => Some(ImageEffect::BlackWhite),
+            3 => Some(ImageEffect::Pattern),
+            _ => None,
+        }
+    }
+}
+
+/// Position, size and text wrapping shared by tables, drawing objects and
+/// equations (개체 공통 속성)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObjectPlacement {
+    pub treat_as_char: bool, // 글자처럼 취급
+    pub affect_line_spacing: bool,
+    pub vert_rel: RelativeTo, // Paper, page or paragraph
+    pub vert_align: ObjectAlign,
+    pub horz_rel: RelativeTo,
+    pub horz_align: ObjectAlign,
+    pub offset_x: HwpUnit, // From the alignment position
+    pub offset_y: HwpUnit,
+    pub flow_with_text: bool, // Keep the object within the page body
+    pub allow_overlap: bool,
+    pub width: HwpUnit,
+    pub width_rel: SizeRelativeTo,
+    pub height: HwpUnit,
+    pub height_rel: SizeRelativeTo, // Paper, page or absolute
+    pub z_order: i32,
+    pub outer_margin: Margin,
+    pub wrap: TextWrap,
+    pub text_flow: TextFlow,
+    pub caption: Option<Caption>,
+}
+
+impl ObjectPlacement {
+    pub fn new() -> Self {
+        ObjectPlacement {
+            treat_as_char: false,
+            affect_line_spacing: false,
+            vert_rel: RelativeTo::Paragraph,
+            vert_align: ObjectAlign::Start,
+            horz_rel: RelativeTo::Paragraph,
+            horz_align: ObjectAlign::Start,
+            offset_x: HwpUnit(0),
+            offset_y: HwpUnit(0),
+            flow_with_text: true,
+            allow_overlap: false,
+            width: HwpUnit(0),
+            width_rel: SizeRelativeTo::Absolute,
+            height: HwpUnit(0),
+            height_rel: SizeRelativeTo::Absolute,
+            z_order: 0,
+            outer_margin: Margin::zero(),
+            wrap: TextWrap::Square,
+            text_flow: TextFlow::BothSides,
+            caption: None,
+        }
+    }
+}
+
+impl Default for ObjectPlacement {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// What an object's position is measured from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RelativeTo {
+    Paper,
+    Page,
+    Column,
+    Paragraph,
+}
+
+/// Object alignment along one axis: top/left, center, bottom/right,
+/// or inside/outside on facing pages
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ObjectAlign {
+    Start = 0,
+    Center = 1,
+    End = 2,
+    Inside = 3,
+    Outside = 4,
+}
+
+impl ObjectAlign {
+    pub fn from_u32(val: u32) -> Option<Self> {
+        match val {
+            0 => Some(ObjectAlign::Start),
+            1 => Some(ObjectAlign::Center),
+            2 => Some(ObjectAlign::End),
+            3 => Some(ObjectAlign::Inside),
+            4 => Some(ObjectAlign::Outside),
+            _ => None,
+        }
+    }
+}
+
+/// What an object's width or height is measured against
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SizeRelativeTo {
+    Paper,
+    Page,
+    Column,
+    Paragraph,
+    Absolute,
+}
+
+/// Which sides of a wrapped object text may flow on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TextFlow {
+    BothSides = 0,
+    LeftOnly = 1,
+    RightOnly = 2,
+    LargestOnly = 3,
+}
+
+impl TextFlow {
+    pub fn from_u32(val: u32) -> Option<Self> {
+        match val {
+            0 => Some(TextFlow::BothSides),
+            1 => Some(TextFlow::LeftOnly),
+            2 => Some(TextFlow::RightOnly),
+            3 => Some(TextFlow::LargestOnly),
+            _ => None,
+        }
+    }
+}
+
+/// Object caption (캡션) with its own paragraph list
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Caption {
+    pub side: CaptionSide,
+    pub full_width: bool, // Width includes the object's margins
+    pub width: HwpUnit,   // Caption width for left/right captions
+    pub gap: HwpUnit,     // Space between caption and object
+    pub blocks: Vec<Block>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CaptionSide {
+    Left = 0,
+    Right = 1,
+    Top = 2,
+    Bottom = 3,
+}
+
+impl CaptionSide {
+    pub fn from_u32(val: u32) -> Option<Self> {
+        match val {
+            0 => Some(CaptionSide::Left),
+            1 => Some(CaptionSide::Right),
+            2 => Some(CaptionSide::Top),
+            3 => Some(CaptionSide::Bottom),
+            _ => None,
+        }
+    }
+}
+
+/// How text flows around an object (본문과의 배치)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TextWrap {
+    Square = 0,        // 어울림
+    Tight = 1,         // Follows the object's outline
+    Through = 2,       // Also fills holes in the outline
+    TopAndBottom = 3,  // 자리 차지
+    BehindText = 4,    // 글 뒤로
+    InFrontOfText = 5, // 글 앞으로
+}
+
+impl TextWrap {
+    pub fn from_u32(val: u32) -> Option<Self> {
+        match val {
+            0 => Some(TextWrap::Square),
+            1 => Some(TextWrap::Tight),
+            2 => Some(TextWrap::Through),
+            3 => Some(TextWrap::TopAndBottom),
+            4 => Some(TextWrap::BehindText),
+            5 => Some(TextWrap::InFrontOfText),
+            _ => None,
+        }
+    }
+}
+
+/// Tracked change (변경 추적): an insertion, deletion or format change
+/// (HWPTAG_TRACK_CHANGE is not decoded yet, so HWP files leave these empty)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrackChange {
+    pub id: u32,
+    pub kind: ChangeKind,
+    pub author_id: u32, // 1-based index into track_change_authors
+    pub date: String,   // "2024-03-05T14:20:00", empty if unknown
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ChangeKind {
+    Insertion,
+    Deletion,
+    Format, // Char or paragraph shape change
+}
+
+/// Binary data item (BIN_DATA) with its stream contents
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BinData {
+    pub id: u32,
+    pub kind: BinDataKind,
+    pub extension: String, // File extension without the dot (jpg, png, ...)
+    pub link_path: String, // Absolute path of a linked file
+    pub compressed: Option<bool>, // Stream compression (None = document default)
+    pub data: Vec<u8>,     // Decompressed stream contents (empty for links)
+    pub preview: Option<OlePreview>, // Picture cached in an OLE storage
+    pub chart: Option<Chart>, // DrawingML chart kept in an OLE storage
+}
+
+impl BinData {
+    /// Whether the item is an OLE object: an OLE storage, or an embedded
+    /// stream holding one
+    pub fn is_ole(&self) -> bool {
+        match self.kind {
+            BinDataKind::Storage => true,
+            BinDataKind::Embedding => self.extension == "ole",
+            BinDataKind::Link => false,
+        }
+    }
+}
+
+/// Presentation picture an OLE storage keeps for display without the server
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OlePreview {
+    pub extension: String, // wmf, emf or bmp
+    pub data: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BinDataKind {
+    Link = 0,      // External file
+    Embedding = 1, // BinData/BINxxxx.ext stream
+    Storage = 2,   // OLE storage
+}
+
+impl BinDataKind {
+    pub fn from_u32(val: u32) -> Option<Self> {
+        match val {
+            0 => Some(BinDataKind::Link),
+            1 => Some(BinDataKind::Embedding),
+            2 => Some(BinDataKind::Storage),
+            _ => None,
+        }
+    }
+}
+
+/// Chart (차트) with its data table
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Chart {
+    pub chart_type: ChartType,
+    pub grouping: ChartGrouping,
+    pub three_d: bool,
+    pub title: String,
+    pub legend: Option<LegendPosition>,
+    pub categories: Vec<String>,
+    pub series: Vec<ChartSeries>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ChartType {
+    Column,
+    Bar, // Horizontal bars
+    Line,
+    Area,
+    Pie,
+    Doughnut,
+    Scatter, // Categories hold the x values
+    Radar,
+}
+
+/// How series of bar, line and area charts combine
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ChartGrouping {
+    Standard,
+    Stacked,
+    PercentStacked,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LegendPosition {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+/// Data series: one value per category (None = missing)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChartSeries {
+    pub name: String,
+    pub values: Vec<Option<f64>>,
 }
 
 /// OLE Object
@@ -155,22 +1267,151 @@ pub struct Picture {
 pub struct OLE {
     pub id: u32,
     pub bindata_id: u32,
+    pub extent: Size, // Size of the object itself
+    pub draw_aspect: DrawAspect,
+    pub object_type: OleObjectType,
+    pub border: Border,
+    pub placement: ObjectPlacement,
+}
+
+/// How the object is drawn (DVASPECT)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DrawAspect {
+    Content = 1,
+    Thumbnail = 2,
+    Icon = 4,
+    DocPrint = 8,
+}
+
+impl DrawAspect {
+    pub fn from_u32(val: u32) -> Option<Self> {
+        match val {
+            1 => Some(DrawAspect::Content),
+            2 => Some(DrawAspect::Thumbnail),
+            4 => Some(DrawAspect::Icon),
+            8 => Some(DrawAspect::DocPrint),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OleObjectType {
+    Unknown = 0,
+    Embedded = 1,
+    Link = 2,
+    Static = 3,
+    Equation = 4,
 }
 
-/// Text box
+impl OleObjectType {
+    pub fn from_u32(val: u32) -> Option<Self> {
+        match val {
+            0 => Some(OleObjectType::Unknown),
+            1 => Some(OleObjectType::Embedded),
+            2 => Some(OleObjectType::Link),
+            3 => Some(OleObjectType::Static),
+            4 => Some(OleObjectType::Equation),
+            _ => None,
+        }
+    }
+}
+
+/// Text box (글상자): text inside a drawing object
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TextBox {
     pub id: u32,
     pub rect: Rect,
-    pub margin: Margin,
+    pub margin: Margin, // Inner margins between the outline and the text
+    pub v_align: VAlignment,
+    pub text_direction: TextDirection,
     pub content: Vec<Block>,
 }
 
-/// Equation (formula)
+/// Equation (수식)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Equation {
     pub id: u32,
-    pub bindata_id: u32,
+    pub script: String, // HWP equation script, e.g. `{a} over {b}`
+    pub root: EquationNode,
+    pub font_size: HwpUnit,
+    pub color: Color,
+    pub baseline: i16, // Baseline position in percent of the height
+    pub version: String,
+    pub font_name: String,
+    pub placement: ObjectPlacement,
+}
+
+/// Node of a parsed equation script
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum EquationNode {
+    Row(Vec<EquationNode>),
+    Identifier(String),
+    Number(String),
+    Operator(String),
+    Text(String),     // Quoted or non-Latin text, set upright
+    Function(String), // sin, log, ... set upright
+    Space(EquationSpace),
+    Fraction {
+        numerator: Box<EquationNode>,
+        denominator: Box<EquationNode>,
+        line: bool, // false for `atop`
+    },
+    Root {
+        index: Option<Box<EquationNode>>,
+        radicand: Box<EquationNode>,
+    },
+    Script {
+        base: Box<EquationNode>,
+        sub: Option<Box<EquationNode>>,
+        sup: Option<Box<EquationNode>>,
+    },
+    /// Sum, integral, limit, ... with `from` and `to` limits
+    LargeOperator {
+        operator: String,
+        from: Option<Box<EquationNode>>,
+        to: Option<Box<EquationNode>>,
+    },
+    /// Matrix, cases or pile: rows of cells
+    Table {
+        align: EquationAlign,
+        rows: Vec<Vec<EquationNode>>,
+    },
+    /// `left ( ... right )`; an empty delimiter is left out
+    Fenced {
+        open: String,
+        close: String,
+        body: Box<EquationNode>,
+    },
+    Accent {
+        accent: String,
+        under: bool,
+        body: Box<EquationNode>,
+    },
+    Styled {
+        style: EquationStyle,
+        body: Box<EquationNode>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EquationSpace {
+    Normal, // ~
+    Thin,   // `
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EquationAlign {
+    Left,
+    Center,
+    Right,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EquationStyle {
+    Roman,  // rm
+    Italic, // it
+    Bold,   // bold
 }
 
 /// Field (date, page number, etc.)
@@ -184,53 +1425,403 @@ pub enum Field {
     Hyperlink(String),
 }
 
-/// Generic shape
+/// Automatic number (자동 번호, atno control)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AutoNumber {
+    pub kind: NumberKind,
+    pub format: NumberFormat,
+    pub superscript: bool,
+    pub number: u16, // Number stored in the control
+    pub user_symbol: Option<char>,
+    pub prefix: Option<char>,
+    pub suffix: Option<char>,
+}
+
+/// New number (새 번호 지정, nwno control): numbering continues from `number`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NewNumber {
+    pub kind: NumberKind,
+    pub number: u16,
+}
+
+/// What an automatic number counts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum NumberKind {
+    Page = 0,
+    Footnote = 1,
+    Endnote = 2,
+    Figure = 3,
+    Table = 4,
+    Equation = 5,
+}
+
+impl NumberKind {
+    pub fn from_u32(val: u32) -> Option<Self> {
+        match val {
+            0 => Some(NumberKind::Page),
+            1 => Some(NumberKind::Footnote),
+            2 => Some(NumberKind::Endnote),
+            3 => Some(NumberKind::Figure),
+            4 => Some(NumberKind::Table),
+            5 => Some(NumberKind::Equation),
+            _ => None,
+        }
+    }
+}
+
+/// Field range (필드) between field start and end controls
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldRange {
+    pub id: u32,
+    pub kind: FieldKind,
+    pub command: String, // Raw command string of the control
+    pub inlines: Vec<Inline>,
+}
+
+/// Field type from the control ID, with the parts of the command we use
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum FieldKind {
+    Hyperlink {
+        target: String,
+    }, // %hlk
+    ClickHere {
+        direction: String,
+    }, // %clk 누름틀
+    CrossReference {
+        target: String,
+    }, // %xrf
+    MailMerge {
+        name: String,
+    }, // %mmg
+    Bookmark {
+        name: String,
+    }, // %bmk
+    Date,         // %dte
+    DocumentDate, // %ddt
+    FilePath,     // %pat
+    Formula,      // %fmu
+    Summary,      // %smr
+    UserInfo,     // %usr
+    Memo {
+        number: u32,
+        author: String,
+        date: String,
+    }, // %%me, commented text range; contents are in the section's memos
+    Other(String), // Unknown or unsupported control ID
+}
+
+/// Drawing object (그리기 개체)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Shape {
     pub id: u32,
     pub shape_type: ShapeType,
-    pub rect: Rect,
+    pub rect: Rect, // Original extent; shape points use these coordinates
+    pub line: ShapeLine,
+    pub fill: Fill,
+    pub text: Option<TextBox>,
+    pub placement: ObjectPlacement,
 }
 
-/// Shape types
+/// Shape types with their geometry
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ShapeType {
-    Rectangle,
-    Ellipse,
-    Arc,
-    Polygon,
-    Curve,
-    Container,
+    Line {
+        start: Position,
+        end: Position,
+    },
+    Rectangle {
+        curvature: u8, // Corner rounding in percent of the shorter side
+    },
+    Ellipse {
+        center: Position,
+        axis1: Position,
+        axis2: Position,
+        arc: Option<EllipseArc>,
+    },
+    Arc {
+        kind: ArcKind,
+        center: Position,
+        axis1: Position, // Arc runs from the end of one axis to the other
+        axis2: Position,
+    },
+    Polygon {
+        points: Vec<Position>,
+    },
+    Curve {
+        points: Vec<Position>,
+        segments: Vec<CurveSegment>, // One per pair of consecutive points
+    },
+}
+
+/// Grouped drawing objects (묶음 개체)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShapeGroup {
+    pub id: u32,
+    pub rect: Rect, // Original extent; children are placed in these coordinates
+    pub children: Vec<GroupChild>,
+    pub placement: ObjectPlacement,
+}
+
+/// Object in a group with the matrix placing it in the group's coordinates
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GroupChild {
+    pub object: GroupObject,
+    pub matrix: ShapeMatrix,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum GroupObject {
+    Shape(Shape),
+    Picture(Picture),
+    Ole(OLE),
+    Group(ShapeGroup),
+}
+
+/// Affine transform in HWP's row order:
+/// x' = a·x + b·y + c, y' = d·x + e·y + f
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ShapeMatrix {
+    pub a: f64,
+    pub b: f64,
+    pub c: f64,
+    pub d: f64,
+    pub e: f64,
+    pub f: f64,
+}
+
+impl ShapeMatrix {
+    pub fn identity() -> Self {
+        ShapeMatrix {
+            a: 1.0,
+            b: 0.0,
+            c: 0.0,
+            d: 0.0,
+            e: 1.0,
+            f: 0.0,
+        }
+    }
+
+    /// Matrix applying `other` first, then `self`
+    pub fn multiply(&self, other: &ShapeMatrix) -> ShapeMatrix {
+        ShapeMatrix {
+            a: self.a * other.a + self.b * other.d,
+            b: self.a * other.b + self.b * other.e,
+            c: self.a * other.c + self.b * other.f + self.c,
+            d: self.d * other.a + self.e * other.d,
+            e: self.d * other.b + self.e * other.e,
+            f: self.d * other.c + self.e * other.f + self.f,
+        }
+    }
+
+    pub fn apply(&self, x: f64, y: f64) -> (f64, f64) {
+        (
+            self.a * x + self.b * y + self.c,
+            self.d * x + self.e * y + self.f,
+        )
+    }
+}
+
+/// Part of an ellipse drawn as an arc
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct EllipseArc {
+    pub kind: ArcKind,
+    pub start: Position,
+    pub end: Position,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ArcKind {
+    Arc = 0,   // 호
+    Pie = 1,   // 부채꼴
+    Chord = 2, // 활 모양
+}
+
+impl ArcKind {
+    pub fn from_u32(val: u32) -> Option<Self> {
+        match val {
+            0 => Some(ArcKind::Arc),
+            1 => Some(ArcKind::Pie),
+            2 => Some(ArcKind::Chord),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CurveSegment {
+    Line = 0,
+    Curve = 1, // Cubic Bezier; the next two points are control points
+}
+
+/// Outline of a drawing object (선 정보)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct ShapeLine {
+    pub style: LineStyle,
+    pub width: HwpUnit,
+    pub color: Color,
+    pub start_arrow: ArrowStyle,
+    pub end_arrow: ArrowStyle,
+    pub start_arrow_size: u8, // 0-8: width (small/medium/large) × 3 + length
+    pub end_arrow_size: u8,
+}
+
+/// Arrowhead at a line end (화살표 모양)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ArrowStyle {
+    None,
+    Arrow,
+    Spear,
+    ConcaveArrow,
+    Diamond,
+    Circle,
+    Box,
+}
+
+impl ArrowStyle {
+    /// Hollow diamond, circle and box (7-9) share the filled shapes
+    pub fn from_u32(val: u32) -> Option<Self> {
+        match val {
+            0 => Some(ArrowStyle::None),
+            1 => Some(ArrowStyle::Arrow),
+            2 => Some(ArrowStyle::Spear),
+            3 => Some(ArrowStyle::ConcaveArrow),
+            4 | 7 => Some(ArrowStyle::Diamond),
+            5 | 8 => Some(ArrowStyle::Circle),
+            6 | 9 => Some(ArrowStyle::Box),
+            _ => None,
+        }
+    }
+}
+
+/// Area fill (채우기 정보)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Fill {
+    pub fill_type: FillType,
+    pub fill_color: Color,       // Pattern color, or the last gradient color
+    pub background_color: Color, // Face color, or the first gradient color
+}
+
+impl Fill {
+    pub fn none() -> Self {
+        Fill {
+            fill_type: FillType::None,
+            fill_color: Color(0),
+            background_color: Color(0xFFFFFF),
+        }
+    }
 }
 
 /// Character shape (글자 모양)
+/// Per-language arrays are indexed by language group: Hangul, Latin,
+/// Hanja, Japanese, other, symbol and user fonts (see `lang`).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CharShape {
     pub id: u32,
-    pub font_id: u32,
-    pub font_size: HwpUnit,
+    pub font_ids: [u32; 7],      // Within each language group's fonts
+    pub ratios: [u8; 7],         // 장평: width in percent
+    pub spacings: [i8; 7],       // 자간: in percent of the font size
+    pub relative_sizes: [u8; 7], // 상대 크기: in percent of `font_size`
+    pub offsets: [i8; 7],        // 글자 위치: in percent, negative raises
+    pub font_size: HwpUnit,      // 기준 크기
     pub bold: bool,
     pub italic: bool,
     pub underline: LineStyle,
+    pub underline_above: bool, // Drawn above the text instead of below
+    pub underline_color: Color,
     pub strikethrough: bool,
+    pub strike_style: LineStyle,
+    pub strike_color: Color,
+    pub outline: bool,
+    pub shadow: Option<CharShadow>,
+    pub emboss: bool,
+    pub engrave: bool,
+    pub emphasis: EmphasisMark, // 강조점
+    pub superscript: bool,
+    pub subscript: bool,
     pub color: Color,
-    pub background_color: Color,
+    pub background_color: Color, // 음영 색
+}
+
+/// Shadow behind characters
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CharShadow {
+    pub offset_x: i8, // In percent of the font size
+    pub offset_y: i8,
+    pub color: Color,
+}
+
+/// Emphasis mark (강조점) set over each character
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EmphasisMark {
+    None = 0,
+    FilledCircle = 1,
+    OpenCircle = 2,
+    Caron = 3,
+    Tilde = 4,
+    Dot = 5,
+    Colon = 6,
+}
+
+impl EmphasisMark {
+    pub fn from_u32(val: u32) -> Option<Self> {
+        match val {
+            0 => Some(EmphasisMark::None),
+            1 => Some(EmphasisMark::FilledCircle),
+            2 => Some(EmphasisMark::OpenCircle),
+            3 => Some(EmphasisMark::Caron),
+            4 => Some(EmphasisMark::Tilde),
+            5 => Some(EmphasisMark::Dot),
+            6 => Some(EmphasisMark::Colon),
+            _ => None,
+        }
+    }
+}
+
+/// Language groups of char shape arrays and face names
+pub mod lang {
+    pub const HANGUL: usize = 0;
+    pub const LATIN: usize = 1;
+    pub const HANJA: usize = 2;
+    pub const JAPANESE: usize = 3;
+    pub const OTHER: usize = 4;
+    pub const SYMBOL: usize = 5;
+    pub const USER: usize = 6;
 }
 
 impl CharShape {
     pub fn new(id: u32) -> Self {
         CharShape {
             id,
-            font_id: 0,
-            font_size: HwpUnit(200), // 10pt default
+            font_ids: [0; 7],
+            ratios: [100; 7],
+            spacings: [0; 7],
+            relative_sizes: [100; 7],
+            offsets: [0; 7],
+            font_size: HwpUnit(1000), // 10pt default
             bold: false,
             italic: false,
             underline: LineStyle::None,
+            underline_above: false,
+            underline_color: Color(0),
             strikethrough: false,
+            strike_style: LineStyle::Solid,
+            strike_color: Color(0),
+            outline: false,
+            shadow: None,
+            emboss: false,
+            engrave: false,
+            emphasis: EmphasisMark::None,
+            superscript: false,
+            subscript: false,
             color: Color(0),                   // Black
             background_color: Color(0xFFFFFF), // White
         }
     }
+
+    /// Font size of a language group after its relative size
+    pub fn lang_size(&self, lang: usize) -> HwpUnit {
+        HwpUnit(self.font_size.0 * self.relative_sizes[lang] as i32 / 100)
+    }
 }
 
 /// Paragraph shape (문단 모양)
@@ -337,7 +1928,7 @@ pub struct BorderFill {
 }
 
 /// Border definition
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct Border {
     pub style: LineStyle,
     pub width: u8, // in 1/20 mm
@@ -355,7 +1946,7 @@ impl Border {
 }
 
 /// Fill type
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum FillType {
     None = 0,
     Solid = 1,
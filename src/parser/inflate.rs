@@ -0,0 +1,251 @@
+use crate::common::{HwpError, HwpResult};
+
+// Raw DEFLATE (RFC 1951) decoder for compressed HWP streams
+
+const LENGTH_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+const LENGTH_EXTRA: [u8; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+const DIST_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+const DIST_EXTRA: [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+    13,
+];
+/// Order in which code length code lengths are stored
+const CODE_LENGTH_ORDER: [usize; 19] = [
+    16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15,
+];
+
+fn truncated() -> HwpError {
+    HwpError::InvalidData("Truncated deflate stream".to_string())
+}
+
+/// LSB-first bit reader
+struct BitReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+    bits: u32,
+    count: u32,
+}
+
+impl<'a> BitReader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        BitReader {
+            data,
+            pos: 0,
+            bits: 0,
+            count: 0,
+        }
+    }
+
+    fn read(&mut self, n: u32) -> HwpResult<u32> {
+        while self.count < n {
+            let byte = *self.data.get(self.pos).ok_or_else(truncated)?;
+            self.pos += 1;
+            self.bits |= (byte as u32) << self.count;
+            self.count += 8;
+        }
+        let value = self.bits & ((1u32 << n) - 1);
+        self.bits >>= n;
+        self.count -= n;
+        Ok(value)
+    }
+
+    /// Drop the rest of the current byte
+    fn align(&mut self) {
+        self.bits = 0;
+        self.count = 0;
+    }
+}
+
+/// Canonical Huffman code: code count per length and symbols in code order
+struct Huffman {
+    counts: [u16; 16],
+    symbols: Vec<u16>,
+}
+
+impl Huffman {
+    fn new(lengths: &[u8]) -> Self {
+        let mut counts = [0u16; 16];
+        for &len in lengths {
+            counts[len as usize] += 1;
+        }
+        counts[0] = 0;
+
+        let mut offsets = [0u16; 16];
+        for len in 1..15 {
+            offsets[len + 1] = offsets[len] + counts[len];
+        }
+        let mut symbols = vec![0u16; lengths.len()];
+        for (symbol, &len) in lengths.iter().enumerate() {
+            if len != 0 {
+                symbols[offsets[len as usize] as usize] = symbol as u16;
+                offsets[len as usize] += 1;
+            }
+        }
+        Huffman { counts, symbols }
+    }
+
+    fn decode(&self, reader: &mut BitReader) -> HwpResult<u16> {
+        let (mut code, mut first, mut index) = (0i32, 0i32, 0i32);
+        for len in 1..16 {
+            code |= reader.read(1)? as i32;
+            let count = self.counts[len] as i32;
+            if code - count < first {
+                return self
+                    .symbols
+                    .get((index + code - first) as usize)
+                    .copied()
+                    .ok_or_else(|| HwpError::InvalidData("Invalid Huffman code".to_string()));
+            }
+            index += count;
+            first = (first + count) << 1;
+            code <<= 1;
+        }
+        Err(HwpError::InvalidData("Invalid Huffman code".to_string()))
+    }
+}
+
+/// Decompress a raw DEFLATE stream (no zlib header), as used by HWP
+pub fn inflate(data: &[u8]) -> HwpResult<Vec<u8>> {
+    let mut reader = BitReader::new(data);
+    let mut out = Vec::with_capacity(data.len() * 4);
+
+    loop {
+        let last = reader.read(1)? == 1;
+        match reader.read(2)? {
+            0 => {
+                reader.align();
+                let pos = reader.pos;
+                let header = data.get(pos..pos + 4).ok_or_else(truncated)?;
+                let len = u16::from_le_bytes([header[0], header[1]]);
+                if u16::from_le_bytes([header[2], header[3]]) != !len {
+                    return Err(HwpError::InvalidData(
+                        "Stored deflate block length mismatch".to_string(),
+                    ));
+                }
+                let len = len as usize;
+                let block = data.get(pos + 4..pos + 4 + len).ok_or_else(truncated)?;
+                out.extend_from_slice(block);
+                reader.pos = pos + 4 + len;
+            }
+            1 => {
+                let mut lengths = [0u8; 288];
+                lengths[..144].fill(8);
+                lengths[144..256].fill(9);
+                lengths[256..280].fill(7);
+                lengths[280..].fill(8);
+                let lit = Huffman::new(&lengths);
+                let dist = Huffman::new(&[5u8; 30]);
+                inflate_block(&mut reader, &mut out, &lit, &dist)?;
+            }
+            2 => {
+                let (lit, dist) = read_dynamic_tables(&mut reader)?;
+                inflate_block(&mut reader, &mut out, &lit, &dist)?;
+            }
+            _ => return Err(HwpError::InvalidData("Invalid deflate block".to_string())),
+        }
+        if last {
+            return Ok(out);
+        }
+    }
+}
+
+fn read_dynamic_tables(reader: &mut BitReader) -> HwpResult<(Huffman, Huffman)> {
+    let lit_count = reader.read(5)? as usize + 257;
+    let dist_count = reader.read(5)? as usize + 1;
+    let code_count = reader.read(4)? as usize + 4;
+
+    let mut code_lengths = [0u8; 19];
+    for &idx in CODE_LENGTH_ORDER.iter().take(code_count) {
+        code_lengths[idx] = reader.read(3)? as u8;
+    }
+    let code = Huffman::new(&code_lengths);
+
+    let mut lengths = Vec::with_capacity(lit_count + dist_count);
+    while lengths.len() < lit_count + dist_count {
+        let (value, repeat) = match code.decode(reader)? {
+            symbol @ 0..=15 => (symbol as u8, 1),
+            16 => {
+                let prev = *lengths
+                    .last()
+                    .ok_or_else(|| HwpError::InvalidData("Repeat without length".to_string()))?;
+                (prev, 3 + reader.read(2)? as usize)
+            }
+            17 => (0, 3 + reader.read(3)? as usize),
+            _ => (0, 11 + reader.read(7)? as usize),
+        };
+        lengths.extend(std::iter::repeat_n(value, repeat));
+    }
+    if lengths.len() > lit_count + dist_count {
+        return Err(HwpError::InvalidData("Too many code lengths".to_string()));
+    }
+
+    Ok((
+        Huffman::new(&lengths[..lit_count]),
+        Huffman::new(&lengths[lit_count..]),
+    ))
+}
+
+fn inflate_block(
+    reader: &mut BitReader,
+    out: &mut Vec<u8>,
+    lit: &Huffman,
+    dist: &Huffman,
+) -> HwpResult<()> {
+    loop {
+        let symbol = lit.decode(reader)? as usize;
+        match symbol {
+            0..=255 => out.push(symbol as u8),
+            256 => return Ok(()),
+            _ => {
+                let idx = symbol - 257;
+                let (Some(&base), Some(&extra)) = (LENGTH_BASE.get(idx), LENGTH_EXTRA.get(idx))
+                else {
+                    return Err(HwpError::InvalidData("Invalid length code".to_string()));
+                };
+                let len = base as usize + reader.read(extra as u32)? as usize;
+
+                let idx = dist.decode(reader)? as usize;
+                let (Some(&base), Some(&extra)) = (DIST_BASE.get(idx), DIST_EXTRA.get(idx)) else {
+                    return Err(HwpError::InvalidData("Invalid distance code".to_string()));
+                };
+                let distance = base as usize + reader.read(extra as u32)? as usize;
+                if distance > out.len() {
+                    return Err(HwpError::InvalidData("Distance too far back".to_string()));
+                }
+
+                let start = out.len() - distance;
+                for i in 0..len {
+                    out.push(out[start + i]);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inflate_fixed_and_stored_blocks() {
+        // "hello hello hello" compressed with fixed Huffman codes
+        let fixed = [0xcb, 0x48, 0xcd, 0xc9, 0xc9, 0x57, 0xc8, 0x40, 0x90, 0x00];
+        assert_eq!(inflate(&fixed).unwrap(), b"hello hello hello");
+
+        // Stored block
+        let stored = [0x01, 0x03, 0x00, 0xfc, 0xff, b'a', b'b', b'c'];
+        assert_eq!(inflate(&stored).unwrap(), b"abc");
+
+        // Stored block whose NLEN is not the complement of LEN
+        let corrupt = [0x01, 0x03, 0x00, 0xfc, 0xfe, b'a', b'b', b'c'];
+        assert!(inflate(&corrupt).is_err());
+    }
+}
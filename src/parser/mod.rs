@@ -1,5 +1,9 @@
+pub mod inflate;
 pub mod ole2;
 pub mod record;
+pub mod xml;
 
+pub use inflate::inflate;
 pub use ole2::{DirEntry, Ole2, Ole2Header};
-pub use record::{Record, RecordHeader, RecordStream};
+pub use record::{Record, RecordHeader, RecordNode, RecordStream, build_record_tree, tags};
+pub use xml::XmlElement;
@@ -1,4 +1,5 @@
 use crate::common::{HwpError, HwpResult, check_signature, read_u8, read_u16_le, read_u32_le};
+use std::collections::HashSet;
 
 /// OLE2 (Object Linking and Embedding) header structure
 /// Standard Compound Document Format v3
@@ -13,9 +14,12 @@ pub struct Ole2Header {
     pub num_sectors: u32,
     pub num_fat_sectors: u32,
     pub first_dir_sector: u32,
+    pub mini_stream_cutoff: u32, // Smaller streams live in the mini stream
     pub first_minifat_sector: u32,
     pub num_minifat_sectors: u32,
-    pub fat: Vec<u32>, // FAT array (first 109 entries)
+    pub first_difat_sector: u32,
+    pub num_difat_sectors: u32,
+    pub difat: Vec<u32>, // Locations of the first 109 FAT sectors
 }
 
 impl Ole2Header {
@@ -48,6 +52,13 @@ impl Ole2Header {
         let mini_sector_size_power = read_u16_le(data, 0x20).ok_or(HwpError::ParseError(
             "Cannot read mini sector size power".to_string(),
         ))?;
+        // Version 3 files use 512-byte sectors, version 4 files 4096-byte
+        // ones; mini sectors are always 64 bytes
+        if !matches!(sector_size_power, 9 | 12) || mini_sector_size_power != 6 {
+            return Err(HwpError::InvalidFormat(
+                "Unsupported OLE2 sector size".to_string(),
+            ));
+        }
         let num_sectors = read_u32_le(data, 0x30)
             .ok_or(HwpError::ParseError("Cannot read num sectors".to_string()))?;
         let num_fat_sectors = read_u32_le(data, 0x2C).ok_or(HwpError::ParseError(
@@ -56,6 +67,9 @@ impl Ole2Header {
         let first_dir_sector = read_u32_le(data, 0x34).ok_or(HwpError::ParseError(
             "Cannot read first dir sector".to_string(),
         ))?;
+        let mini_stream_cutoff = read_u32_le(data, 0x38).ok_or(HwpError::ParseError(
+            "Cannot read mini stream cutoff".to_string(),
+        ))?;
         let first_minifat_sector = read_u32_le(data, 0x3C).ok_or(HwpError::ParseError(
             "Cannot read first minifat sector".to_string(),
         ))?;
@@ -63,11 +77,18 @@ impl Ole2Header {
             "Cannot read num minifat sectors".to_string(),
         ))?;
 
-        // Read FAT array (first 109 entries at offset 0x4C)
-        let mut fat = Vec::with_capacity(109);
+        let first_difat_sector = read_u32_le(data, 0x44).ok_or(HwpError::ParseError(
+            "Cannot read first DIFAT sector".to_string(),
+        ))?;
+        let num_difat_sectors = read_u32_le(data, 0x48).ok_or(HwpError::ParseError(
+            "Cannot read num DIFAT sectors".to_string(),
+        ))?;
+
+        // FAT sector locations (first 109 entries at offset 0x4C)
+        let mut difat = Vec::with_capacity(109);
         for i in 0..109 {
             if let Some(val) = read_u32_le(data, 0x4C + i * 4) {
-                fat.push(val);
+                difat.push(val);
             } else {
                 break;
             }
@@ -83,9 +104,12 @@ impl Ole2Header {
             num_sectors,
             num_fat_sectors,
             first_dir_sector,
+            mini_stream_cutoff,
             first_minifat_sector,
             num_minifat_sectors,
-            fat,
+            first_difat_sector,
+            num_difat_sectors,
+            difat,
         })
     }
 
@@ -124,8 +148,9 @@ impl DirEntry {
         let name_bytes = &data[0..64];
         let name_len = read_u16_le(data, 64).unwrap_or(0);
 
-        let name = if name_len > 0 && name_len <= 32 {
-            let utf16_str: Result<Vec<u16>, ()> = (0..name_len as usize - 1)
+        // The length is in bytes and includes the terminating null
+        let name = if name_len > 2 && name_len <= 64 {
+            let utf16_str: Result<Vec<u16>, ()> = (0..name_len as usize - 2)
                 .step_by(2)
                 .map(|i| {
                     let b0 = name_bytes.get(i).copied().ok_or(())?;
@@ -168,67 +193,153 @@ impl DirEntry {
     }
 }
 
+/// End of a sector chain
+const END_OF_CHAIN: u32 = 0xFFFFFFFE;
+/// No sibling or child directory entry
+const NO_STREAM: u32 = 0xFFFFFFFF;
+
 /// OLE2 container for reading streams
 pub struct Ole2 {
     pub header: Ole2Header,
     pub data: Vec<u8>,
+    /// Sector allocation table: next sector of each sector's chain
+    pub fat: Vec<u32>,
+    /// Mini sector allocation table and the mini stream it indexes
+    pub mini_fat: Vec<u32>,
+    pub mini_stream: Vec<u8>,
+    /// Directory sectors, 128 bytes per entry
+    directory: Vec<u8>,
 }
 
 impl Ole2 {
     pub fn parse(data: Vec<u8>) -> HwpResult<Self> {
         let header = Ole2Header::parse(&data)?;
-        Ok(Ole2 { header, data })
+        let mut ole2 = Ole2 {
+            header,
+            data,
+            fat: Vec::new(),
+            mini_fat: Vec::new(),
+            mini_stream: Vec::new(),
+            directory: Vec::new(),
+        };
+
+        // FAT sectors are listed in the header, then in a chain of DIFAT
+        // sectors whose last entry points to the next one. The walk stops
+        // once all FAT sectors are known or the chain loops.
+        let mut fat_sectors: Vec<u32> = ole2.header.difat.clone();
+        let num_fat_sectors = ole2.header.num_fat_sectors as usize;
+        let per_sector = ole2.header.sector_size() / 4;
+        let mut difat_sector = ole2.header.first_difat_sector;
+        let mut difat_sectors = HashSet::new();
+        while fat_sectors.len() < num_fat_sectors && difat_sectors.insert(difat_sector) {
+            let Some(sector) = ole2.sector(difat_sector) else {
+                break;
+            };
+            let entries: Vec<u32> = (0..per_sector)
+                .filter_map(|i| read_u32_le(sector, i * 4))
+                .collect();
+            fat_sectors.extend_from_slice(&entries[..per_sector - 1]);
+            difat_sector = entries[per_sector - 1];
+        }
+        let mut fat = Vec::new();
+        for &sector_id in fat_sectors.iter().take(num_fat_sectors) {
+            if let Some(sector) = ole2.sector(sector_id) {
+                fat.extend((0..per_sector).filter_map(|i| read_u32_le(sector, i * 4)));
+            }
+        }
+        ole2.fat = fat;
+
+        ole2.directory = ole2.read_fat_chain(ole2.header.first_dir_sector, usize::MAX)?;
+        let minifat = ole2.read_fat_chain(ole2.header.first_minifat_sector, usize::MAX)?;
+        ole2.mini_fat = (0..minifat.len() / 4)
+            .filter_map(|i| read_u32_le(&minifat, i * 4))
+            .collect();
+        // The root entry's stream is the mini stream
+        let root = ole2.read_dir_entry(0)?;
+        ole2.mini_stream = ole2.read_fat_chain(root.start_sector, usize::MAX)?;
+        Ok(ole2)
+    }
+
+    /// Contents of a sector; sector 0 follows the header sector
+    fn sector(&self, sector_id: u32) -> Option<&[u8]> {
+        let sector_size = self.header.sector_size();
+        let offset = (sector_id as usize)
+            .checked_add(1)?
+            .checked_mul(sector_size)?;
+        self.data.get(offset..offset.checked_add(sector_size)?)
     }
 
     /// Read FAT chain starting from sector_id
     pub fn read_fat_chain(&self, mut sector_id: u32, max_sectors: usize) -> HwpResult<Vec<u8>> {
-        let sector_size = self.header.sector_size();
         let mut result = Vec::new();
         let mut count = 0;
 
-        while sector_id != 0xFFFFFFFE && count < max_sectors {
-            let offset = 512 + (sector_id as usize) * sector_size;
-            if offset + sector_size > self.data.len() {
+        // The count also guards against cycles in a corrupt FAT
+        while sector_id != END_OF_CHAIN && count < max_sectors.min(self.fat.len().max(1)) {
+            let Some(sector) = self.sector(sector_id) else {
                 break;
+            };
+            result.extend_from_slice(sector);
+            match self.fat.get(sector_id as usize) {
+                Some(&next) => sector_id = next,
+                None => break,
             }
-            result.extend_from_slice(&self.data[offset..offset + sector_size]);
+            count += 1;
+        }
 
-            // Read next sector from FAT
-            if sector_id < self.header.fat.len() as u32 {
-                sector_id = self.header.fat[sector_id as usize];
-            } else {
+        Ok(result)
+    }
+
+    /// Read a chain of mini sectors from the mini stream
+    fn read_mini_chain(&self, mut sector_id: u32) -> Vec<u8> {
+        let size = self.header.mini_sector_size();
+        let mut result = Vec::new();
+        let mut count = 0;
+        while sector_id != END_OF_CHAIN && count < self.mini_fat.len() {
+            let Some(sector) = (sector_id as usize)
+                .checked_mul(size)
+                .and_then(|offset| self.mini_stream.get(offset..offset.checked_add(size)?))
+            else {
                 break;
+            };
+            result.extend_from_slice(sector);
+            match self.mini_fat.get(sector_id as usize) {
+                Some(&next) => sector_id = next,
+                None => break,
             }
             count += 1;
         }
-
-        Ok(result)
+        result
     }
 
     /// Read a directory entry
     pub fn read_dir_entry(&self, entry_id: u32) -> HwpResult<DirEntry> {
-        let sector_size = self.header.sector_size();
-        let entries_per_sector = sector_size / 128;
-        let sector_id =
-            self.header.first_dir_sector + (entry_id as usize / entries_per_sector) as u32;
-        let entry_offset = (entry_id as usize % entries_per_sector) * 128;
-
-        let sector_data = self.read_fat_chain(sector_id, 1)?;
-        if entry_offset + 128 > sector_data.len() {
+        let offset = entry_id as usize * 128;
+        let Some(entry) = self.directory.get(offset..offset + 128) else {
             return Err(HwpError::ParseError(
                 "Invalid directory entry offset".to_string(),
             ));
-        }
-
-        DirEntry::parse(&sector_data[entry_offset..entry_offset + 128])
+        };
+        DirEntry::parse(entry)
     }
 
-    /// List all streams (recursively from root)
+    /// List all streams (recursively from root). Paths are relative to the
+    /// root storage, with `/` between storage and stream names.
     pub fn list_streams(&self) -> HwpResult<Vec<(String, DirEntry)>> {
         let mut result = Vec::new();
-        let mut queue = vec![(String::new(), 0u32)]; // (path, entry_id)
+        let root = self.read_dir_entry(0)?;
+        let mut queue = Vec::new();
+        if root.child != NO_STREAM {
+            queue.push((String::new(), root.child)); // (path, entry_id)
+        }
 
+        let mut visited = 0;
         while let Some((path, entry_id)) = queue.pop() {
+            // Guard against sibling cycles in a corrupt directory
+            visited += 1;
+            if visited > self.directory.len() / 128 {
+                break;
+            }
             let entry = self.read_dir_entry(entry_id)?;
 
             let current_path = if path.is_empty() {
@@ -240,17 +351,17 @@ impl Ole2 {
             if entry.entry_type == 2 {
                 // Stream
                 result.push((current_path, entry.clone()));
-            } else if entry.entry_type == 1 || entry.entry_type == 5 {
+            } else if entry.entry_type == 1 {
                 // Storage (directory)
-                if entry.child != 0xFFFFFFFF {
+                if entry.child != NO_STREAM {
                     queue.push((current_path, entry.child));
                 }
             }
 
-            if entry.right_sibling != 0xFFFFFFFF {
+            if entry.right_sibling != NO_STREAM {
                 queue.push((path.clone(), entry.right_sibling));
             }
-            if entry.left_sibling != 0xFFFFFFFF {
+            if entry.left_sibling != NO_STREAM {
                 queue.push((path, entry.left_sibling));
             }
         }
@@ -262,10 +373,117 @@ impl Ole2 {
     pub fn get_stream(&self, name: &str) -> HwpResult<Vec<u8>> {
         let streams = self.list_streams()?;
         for (stream_name, entry) in streams {
-            if stream_name == name {
-                return self.read_fat_chain(entry.start_sector, usize::MAX);
+            // Compound file names compare case-insensitively
+            if stream_name.eq_ignore_ascii_case(name) {
+                let size = entry.stream_size as usize;
+                let mut data = if entry.stream_size < self.header.mini_stream_cutoff {
+                    self.read_mini_chain(entry.start_sector)
+                } else {
+                    self.read_fat_chain(entry.start_sector, usize::MAX)?
+                };
+                data.truncate(size);
+                return Ok(data);
             }
         }
         Err(HwpError::NotFound(format!("Stream '{}' not found", name)))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn put_u32(data: &mut [u8], offset: usize, value: u32) {
+        data[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+    }
+
+    fn dir_entry(
+        name: &str,
+        entry_type: u8,
+        child: u32,
+        right: u32,
+        start: u32,
+        size: u32,
+    ) -> Vec<u8> {
+        let mut entry = vec![0u8; 128];
+        let units: Vec<u16> = name.encode_utf16().collect();
+        for (i, unit) in units.iter().enumerate() {
+            entry[i * 2..i * 2 + 2].copy_from_slice(&unit.to_le_bytes());
+        }
+        entry[64..66].copy_from_slice(&((units.len() as u16 + 1) * 2).to_le_bytes());
+        entry[66] = entry_type;
+        put_u32(&mut entry, 68, NO_STREAM);
+        put_u32(&mut entry, 72, right);
+        put_u32(&mut entry, 76, child);
+        put_u32(&mut entry, 116, start);
+        put_u32(&mut entry, 120, size);
+        entry
+    }
+
+    /// Header, FAT, directory, mini FAT, mini stream, then a regular
+    /// stream in sectors 4 and 5
+    fn compound_file() -> Vec<u8> {
+        let mut data = vec![0u8; 512 * 7];
+        data[..8].copy_from_slice(&[0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1]);
+        data[0x1E] = 9; // 512-byte sectors
+        data[0x20] = 6; // 64-byte mini sectors
+        put_u32(&mut data, 0x2C, 1);
+        put_u32(&mut data, 0x34, 1);
+        put_u32(&mut data, 0x38, 512); // Small cutoff keeps the file small
+        put_u32(&mut data, 0x3C, 2);
+        put_u32(&mut data, 0x40, 1);
+        put_u32(&mut data, 0x44, END_OF_CHAIN);
+        for i in 0..109 {
+            put_u32(&mut data, 0x4C + i * 4, if i == 0 { 0 } else { NO_STREAM });
+        }
+
+        let fat = [
+            0xFFFFFFFD,
+            END_OF_CHAIN,
+            END_OF_CHAIN,
+            END_OF_CHAIN,
+            5,
+            END_OF_CHAIN,
+        ];
+        for (i, next) in fat.iter().enumerate() {
+            put_u32(&mut data, 512 + i * 4, *next);
+        }
+        let directory = [
+            dir_entry("Root Entry", 5, 1, NO_STREAM, 3, 64),
+            dir_entry("Small", 2, NO_STREAM, 2, 0, 5),
+            dir_entry("Big", 2, NO_STREAM, NO_STREAM, 4, 1000),
+        ]
+        .concat();
+        data[1024..1024 + directory.len()].copy_from_slice(&directory);
+        put_u32(&mut data, 1536, END_OF_CHAIN);
+        data[2048..2053].copy_from_slice(b"hello");
+        data[2560..3584].fill(0xAB);
+        data
+    }
+
+    #[test]
+    fn test_read_mini_and_regular_streams() {
+        let ole2 = Ole2::parse(compound_file()).unwrap();
+        assert_eq!(ole2.get_stream("Small").unwrap(), b"hello");
+        assert_eq!(ole2.get_stream("Big").unwrap(), vec![0xAB; 1000]);
+    }
+
+    #[test]
+    fn test_corrupt_header_and_difat() {
+        let mut data = compound_file();
+        data[0x1E] = 1;
+        assert!(Ole2::parse(data).is_err());
+
+        // A DIFAT sector pointing to itself, with huge sector counts
+        let mut data = compound_file();
+        put_u32(&mut data, 0x2C, u32::MAX);
+        put_u32(&mut data, 0x44, 4);
+        put_u32(&mut data, 0x48, u32::MAX);
+        put_u32(&mut data, 2560 + 508, 4);
+        let ole2 = Ole2::parse(data).unwrap();
+        assert_eq!(ole2.get_stream("Small").unwrap(), b"hello");
+
+        // Sector IDs near the top of the range are out of the file
+        assert!(ole2.sector(0xFFFFFFFA).is_none());
+    }
+}
@@ -120,57 +120,170 @@ impl Iterator for RecordStream {
     }
 }
 
+/// Build a record tree from a flat record list using each record's level.
+/// A record becomes a child of the closest preceding record with a lower level.
+pub fn build_record_tree(records: Vec<Record>) -> Vec<RecordNode> {
+    fn attach(nodes: &mut Vec<RecordNode>, record: Record) {
+        if let Some(last) = nodes.last_mut()
+            && last.record.level < record.level
+        {
+            attach(&mut last.children, record);
+            return;
+        }
+        nodes.push(RecordNode {
+            record,
+            children: Vec::new(),
+        });
+    }
+
+    let mut roots = Vec::new();
+    for record in records {
+        attach(&mut roots, record);
+    }
+    roots
+}
+
+/// HWP Record with its child records
+#[derive(Debug, Clone)]
+pub struct RecordNode {
+    pub record: Record,
+    pub children: Vec<RecordNode>,
+}
+
+impl RecordNode {
+    pub fn tagid(&self) -> u16 {
+        self.record.tagid
+    }
+
+    pub fn payload(&self) -> &[u8] {
+        &self.record.payload
+    }
+
+    /// Find the first direct child with the given tag
+    pub fn child(&self, tagid: u16) -> Option<&RecordNode> {
+        self.children.iter().find(|c| c.record.tagid == tagid)
+    }
+}
+
+/// HWP tag IDs (HWPTAG_BEGIN = 0x10)
+pub mod tags {
+    // DocInfo tags
+    pub const HWPTAG_DOCUMENT_PROPERTIES: u16 = 16;
+    pub const HWPTAG_ID_MAPPINGS: u16 = 17;
+    pub const HWPTAG_BIN_DATA: u16 = 18;
+    pub const HWPTAG_FACE_NAME: u16 = 19;
+    pub const HWPTAG_BORDER_FILL: u16 = 20;
+    pub const HWPTAG_CHAR_SHAPE: u16 = 21;
+    pub const HWPTAG_TAB_DEF: u16 = 22;
+    pub const HWPTAG_NUMBERING: u16 = 23;
+    pub const HWPTAG_BULLET: u16 = 24;
+    pub const HWPTAG_PARA_SHAPE: u16 = 25;
+    pub const HWPTAG_STYLE: u16 = 26;
+    pub const HWPTAG_DOC_DATA: u16 = 27;
+    pub const HWPTAG_DISTRIBUTE_DOC_DATA: u16 = 28;
+    pub const HWPTAG_RESERVED: u16 = 29;
+    pub const HWPTAG_COMPATIBLE_DOCUMENT: u16 = 30;
+    pub const HWPTAG_LAYOUT_COMPATIBILITY: u16 = 31;
+    pub const HWPTAG_TRACKCHANGE: u16 = 32;
+
+    // BodyText tags
+    pub const HWPTAG_PARA_HEADER: u16 = 66;
+    pub const HWPTAG_PARA_TEXT: u16 = 67;
+    pub const HWPTAG_PARA_CHAR_SHAPE: u16 = 68;
+    pub const HWPTAG_PARA_LINE_SEG: u16 = 69;
+    pub const HWPTAG_PARA_RANGE_TAG: u16 = 70;
+    pub const HWPTAG_CTRL_HEADER: u16 = 71;
+    pub const HWPTAG_LIST_HEADER: u16 = 72;
+    pub const HWPTAG_PAGE_DEF: u16 = 73;
+    pub const HWPTAG_FOOTNOTE_SHAPE: u16 = 74;
+    pub const HWPTAG_PAGE_BORDER_FILL: u16 = 75;
+    pub const HWPTAG_SHAPE_COMPONENT: u16 = 76;
+    pub const HWPTAG_TABLE: u16 = 77;
+    pub const HWPTAG_SHAPE_COMPONENT_LINE: u16 = 78;
+    pub const HWPTAG_SHAPE_COMPONENT_RECTANGLE: u16 = 79;
+    pub const HWPTAG_SHAPE_COMPONENT_ELLIPSE: u16 = 80;
+    pub const HWPTAG_SHAPE_COMPONENT_ARC: u16 = 81;
+    pub const HWPTAG_SHAPE_COMPONENT_POLYGON: u16 = 82;
+    pub const HWPTAG_SHAPE_COMPONENT_CURVE: u16 = 83;
+    pub const HWPTAG_SHAPE_COMPONENT_OLE: u16 = 84;
+    pub const HWPTAG_SHAPE_COMPONENT_PICTURE: u16 = 85;
+    pub const HWPTAG_SHAPE_COMPONENT_CONTAINER: u16 = 86;
+    pub const HWPTAG_CTRL_DATA: u16 = 87;
+    pub const HWPTAG_EQEDIT: u16 = 88;
+    pub const HWPTAG_SHAPE_COMPONENT_TEXTART: u16 = 90;
+    pub const HWPTAG_FORM_OBJECT: u16 = 91;
+    pub const HWPTAG_MEMO_SHAPE: u16 = 92;
+    pub const HWPTAG_MEMO_LIST: u16 = 93;
+    pub const HWPTAG_FORBIDDEN_CHAR: u16 = 94;
+    pub const HWPTAG_CHART_DATA: u16 = 95;
+    pub const HWPTAG_TRACK_CHANGE: u16 = 96;
+    pub const HWPTAG_TRACK_CHANGE_AUTHOR: u16 = 97;
+    pub const HWPTAG_VIDEO_DATA: u16 = 98;
+    pub const HWPTAG_SHAPE_COMPONENT_UNKNOWN: u16 = 115;
+}
+
 /// Format HWP tag ID to tag name
 fn format_tagname(tagid: u16) -> String {
-    match tagid {
+    use tags::*;
+
+    let name = match tagid {
         // DocInfo tags
-        0 => "HWP_ID".to_string(),
-        3 => "HWPTAG_PARA_TEXT".to_string(),
-        4 => "HWPTAG_PARA_CHAR_SHAPE".to_string(),
-        5 => "HWPTAG_PARA_LINE_SEG".to_string(),
-        6 => "HWPTAG_PARA_RANGE_TAG".to_string(),
-        7 => "HWPTAG_CTRL_HEADER".to_string(),
-        9 => "HWPTAG_LIST_HEADER".to_string(),
-        10 => "HWPTAG_TABLE_CELL".to_string(),
-        11 => "HWPTAG_SECTION_DEF".to_string(),
-        16 => "HWPTAG_DOC_INFO".to_string(),
-        17 => "HWPTAG_ID_MAPPINGS".to_string(),
-        18 => "HWPTAG_BIN_DATA".to_string(),
-        19 => "HWPTAG_FACE_NAME".to_string(),
-        20 => "HWPTAG_BORDER_FILL".to_string(),
-        21 => "HWPTAG_CHAR_SHAPE".to_string(),
-        22 => "HWPTAG_TAB_DEF".to_string(),
-        23 => "HWPTAG_NUMBERING".to_string(),
-        24 => "HWPTAG_BULLET".to_string(),
-        25 => "HWPTAG_PARA_SHAPE".to_string(),
-        26 => "HWPTAG_STYLE".to_string(),
-        27 => "HWPTAG_DOC_DATA".to_string(),
-        28 => "HWPTAG_DISTRIBUTE_DOC_DATA".to_string(),
-        29 => "HWPTAG_RESERVED".to_string(),
-        30 => "HWPTAG_COMPATIBLE_DOC_DATA".to_string(),
-        31 => "HWPTAG_LAYOUT_COMPATIBILITY".to_string(),
+        HWPTAG_DOCUMENT_PROPERTIES => "HWPTAG_DOCUMENT_PROPERTIES",
+        HWPTAG_ID_MAPPINGS => "HWPTAG_ID_MAPPINGS",
+        HWPTAG_BIN_DATA => "HWPTAG_BIN_DATA",
+        HWPTAG_FACE_NAME => "HWPTAG_FACE_NAME",
+        HWPTAG_BORDER_FILL => "HWPTAG_BORDER_FILL",
+        HWPTAG_CHAR_SHAPE => "HWPTAG_CHAR_SHAPE",
+        HWPTAG_TAB_DEF => "HWPTAG_TAB_DEF",
+        HWPTAG_NUMBERING => "HWPTAG_NUMBERING",
+        HWPTAG_BULLET => "HWPTAG_BULLET",
+        HWPTAG_PARA_SHAPE => "HWPTAG_PARA_SHAPE",
+        HWPTAG_STYLE => "HWPTAG_STYLE",
+        HWPTAG_DOC_DATA => "HWPTAG_DOC_DATA",
+        HWPTAG_DISTRIBUTE_DOC_DATA => "HWPTAG_DISTRIBUTE_DOC_DATA",
+        HWPTAG_RESERVED => "HWPTAG_RESERVED",
+        HWPTAG_COMPATIBLE_DOCUMENT => "HWPTAG_COMPATIBLE_DOCUMENT",
+        HWPTAG_LAYOUT_COMPATIBILITY => "HWPTAG_LAYOUT_COMPATIBILITY",
+        HWPTAG_TRACKCHANGE => "HWPTAG_TRACKCHANGE",
 
         // BodyText tags
-        50 => "HWPTAG_PARAGRAPH".to_string(),
-        51 => "HWPTAG_PARA_TEXT".to_string(),
-        52 => "HWPTAG_PARA_CHAR_SHAPE".to_string(),
-        53 => "HWPTAG_PARA_LINE_SEG".to_string(),
-        54 => "HWPTAG_PARA_RANGE_TAG".to_string(),
+        HWPTAG_PARA_HEADER => "HWPTAG_PARA_HEADER",
+        HWPTAG_PARA_TEXT => "HWPTAG_PARA_TEXT",
+        HWPTAG_PARA_CHAR_SHAPE => "HWPTAG_PARA_CHAR_SHAPE",
+        HWPTAG_PARA_LINE_SEG => "HWPTAG_PARA_LINE_SEG",
+        HWPTAG_PARA_RANGE_TAG => "HWPTAG_PARA_RANGE_TAG",
+        HWPTAG_CTRL_HEADER => "HWPTAG_CTRL_HEADER",
+        HWPTAG_LIST_HEADER => "HWPTAG_LIST_HEADER",
+        HWPTAG_PAGE_DEF => "HWPTAG_PAGE_DEF",
+        HWPTAG_FOOTNOTE_SHAPE => "HWPTAG_FOOTNOTE_SHAPE",
+        HWPTAG_PAGE_BORDER_FILL => "HWPTAG_PAGE_BORDER_FILL",
+        HWPTAG_TABLE => "HWPTAG_TABLE",
+        HWPTAG_CTRL_DATA => "HWPTAG_CTRL_DATA",
+        HWPTAG_EQEDIT => "HWPTAG_EQEDIT",
+        HWPTAG_FORM_OBJECT => "HWPTAG_FORM_OBJECT",
+        HWPTAG_MEMO_SHAPE => "HWPTAG_MEMO_SHAPE",
+        HWPTAG_MEMO_LIST => "HWPTAG_MEMO_LIST",
+        HWPTAG_FORBIDDEN_CHAR => "HWPTAG_FORBIDDEN_CHAR",
+        HWPTAG_CHART_DATA => "HWPTAG_CHART_DATA",
+        HWPTAG_TRACK_CHANGE => "HWPTAG_TRACK_CHANGE",
+        HWPTAG_TRACK_CHANGE_AUTHOR => "HWPTAG_TRACK_CHANGE_AUTHOR",
+        HWPTAG_VIDEO_DATA => "HWPTAG_VIDEO_DATA",
 
         // Shape tags
-        61 => "HWPTAG_TABLE".to_string(),
-        62 => "HWPTAG_SHAPE_COMPONENT_RECT".to_string(),
-        63 => "HWPTAG_SHAPE_COMPONENT_ELLIPSE".to_string(),
-        64 => "HWPTAG_SHAPE_COMPONENT_ARC".to_string(),
-        65 => "HWPTAG_SHAPE_COMPONENT_POLYGON".to_string(),
-        66 => "HWPTAG_SHAPE_COMPONENT_CURVE".to_string(),
-        67 => "HWPTAG_SHAPE_COMPONENT_OLE".to_string(),
-        68 => "HWPTAG_SHAPE_COMPONENT_PICTURE".to_string(),
-        69 => "HWPTAG_SHAPE_COMPONENT_CONTAINER".to_string(),
-        70 => "HWPTAG_CTRL_DATA".to_string(),
-        71 => "HWPTAG_EQEDIT".to_string(),
-        72 => "HWPTAG_SHAPE_COMPONENT_TEXTBOX".to_string(),
-
-        _ => format!("HWPTAG_{}", tagid),
-    }
+        HWPTAG_SHAPE_COMPONENT => "HWPTAG_SHAPE_COMPONENT",
+        HWPTAG_SHAPE_COMPONENT_LINE => "HWPTAG_SHAPE_COMPONENT_LINE",
+        HWPTAG_SHAPE_COMPONENT_RECTANGLE => "HWPTAG_SHAPE_COMPONENT_RECTANGLE",
+        HWPTAG_SHAPE_COMPONENT_ELLIPSE => "HWPTAG_SHAPE_COMPONENT_ELLIPSE",
+        HWPTAG_SHAPE_COMPONENT_ARC => "HWPTAG_SHAPE_COMPONENT_ARC",
+        HWPTAG_SHAPE_COMPONENT_POLYGON => "HWPTAG_SHAPE_COMPONENT_POLYGON",
+        HWPTAG_SHAPE_COMPONENT_CURVE => "HWPTAG_SHAPE_COMPONENT_CURVE",
+        HWPTAG_SHAPE_COMPONENT_OLE => "HWPTAG_SHAPE_COMPONENT_OLE",
+        HWPTAG_SHAPE_COMPONENT_PICTURE => "HWPTAG_SHAPE_COMPONENT_PICTURE",
+        HWPTAG_SHAPE_COMPONENT_CONTAINER => "HWPTAG_SHAPE_COMPONENT_CONTAINER",
+        HWPTAG_SHAPE_COMPONENT_TEXTART => "HWPTAG_SHAPE_COMPONENT_TEXTART",
+        HWPTAG_SHAPE_COMPONENT_UNKNOWN => "HWPTAG_SHAPE_COMPONENT_UNKNOWN",
+
+        _ => return format!("HWPTAG_{}", tagid),
+    };
+    name.to_string()
 }
@@ -0,0 +1,252 @@
+use crate::common::{HwpError, HwpResult};
+
+// Minimal XML reader for XML streams embedded in HWP files (chart parts).
+// Namespace prefixes are dropped from element and attribute names.
+
+/// XML element with its attributes, child elements and text content
+#[derive(Debug, Clone, Default)]
+pub struct XmlElement {
+    pub name: String,
+    pub attributes: Vec<(String, String)>,
+    pub children: Vec<XmlElement>,
+    pub text: String,
+}
+
+impl XmlElement {
+    /// Parse a document and return its root element. UTF-16 documents are
+    /// recognised by their byte order mark.
+    pub fn parse(data: &[u8]) -> HwpResult<Self> {
+        let text = if data.starts_with(&[0xFF, 0xFE]) {
+            let units: Vec<u16> = data[2..]
+                .chunks_exact(2)
+                .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+                .collect();
+            String::from_utf16_lossy(&units)
+        } else {
+            let data = data.strip_prefix(&[0xEF, 0xBB, 0xBF]).unwrap_or(data);
+            String::from_utf8_lossy(data).into_owned()
+        };
+
+        let mut stack: Vec<XmlElement> = vec![XmlElement::default()];
+        let mut rest = text.as_str();
+        while !rest.is_empty() {
+            let Some(start) = rest.find('<') else {
+                push_text(&mut stack, rest);
+                break;
+            };
+            push_text(&mut stack, &rest[..start]);
+            rest = &rest[start..];
+
+            if let Some(body) = rest.strip_prefix("<![CDATA[") {
+                let end = body.find("]]>").ok_or_else(|| error("Unclosed CDATA"))?;
+                if let Some(top) = stack.last_mut() {
+                    top.text.push_str(&body[..end]);
+                }
+                rest = &body[end + 3..];
+            } else if let Some(body) = rest.strip_prefix("<!--") {
+                let end = body.find("-->").ok_or_else(|| error("Unclosed comment"))?;
+                rest = &body[end + 3..];
+            } else if rest.starts_with("<?") || rest.starts_with("<!") {
+                let end = rest
+                    .find('>')
+                    .ok_or_else(|| error("Unclosed declaration"))?;
+                rest = &rest[end + 1..];
+            } else if let Some(body) = rest.strip_prefix("</") {
+                let end = body.find('>').ok_or_else(|| error("Unclosed end tag"))?;
+                rest = &body[end + 1..];
+                if stack.len() > 1 {
+                    let element = stack.pop().unwrap_or_default();
+                    if let Some(parent) = stack.last_mut() {
+                        parent.children.push(element);
+                    }
+                }
+            } else {
+                let end = tag_end(rest).ok_or_else(|| error("Unclosed start tag"))?;
+                let tag = &rest[1..end];
+                rest = &rest[end + 1..];
+                let (tag, empty) = match tag.strip_suffix('/') {
+                    Some(tag) => (tag, true),
+                    None => (tag, false),
+                };
+                let element = start_tag(tag);
+                if empty {
+                    if let Some(parent) = stack.last_mut() {
+                        parent.children.push(element);
+                    }
+                } else {
+                    stack.push(element);
+                }
+            }
+        }
+
+        // Close elements left open by a truncated document
+        while stack.len() > 1 {
+            let element = stack.pop().unwrap_or_default();
+            if let Some(parent) = stack.last_mut() {
+                parent.children.push(element);
+            }
+        }
+        stack
+            .pop()
+            .and_then(|document| document.children.into_iter().next())
+            .ok_or_else(|| error("No root element"))
+    }
+
+    /// Value of an attribute
+    pub fn attr(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// First child element with the given name
+    pub fn child(&self, name: &str) -> Option<&XmlElement> {
+        self.children.iter().find(|child| child.name == name)
+    }
+
+    /// Child elements with the given name
+    pub fn children_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a XmlElement> {
+        self.children.iter().filter(move |child| child.name == name)
+    }
+
+    /// First element with the given name below this one, depth first
+    pub fn descendant(&self, name: &str) -> Option<&XmlElement> {
+        self.children.iter().find_map(|child| {
+            if child.name == name {
+                Some(child)
+            } else {
+                child.descendant(name)
+            }
+        })
+    }
+
+    /// Text of this element and all elements below it
+    pub fn all_text(&self) -> String {
+        let mut text = self.text.clone();
+        for child in &self.children {
+            text.push_str(&child.all_text());
+        }
+        text
+    }
+}
+
+fn error(message: &str) -> HwpError {
+    HwpError::ParseError(format!("XML: {}", message))
+}
+
+fn push_text(stack: &mut [XmlElement], text: &str) {
+    if let Some(top) = stack.last_mut()
+        && !text.is_empty()
+    {
+        top.text.push_str(&unescape(text));
+    }
+}
+
+/// Position of the `>` closing a start tag, skipping quoted values
+fn tag_end(tag: &str) -> Option<usize> {
+    let mut quote = None;
+    for (i, c) in tag.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), _) if c == q => quote = None,
+            (None, '>') => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Element name and attributes of a start tag without its brackets
+fn start_tag(tag: &str) -> XmlElement {
+    let tag = tag.trim();
+    let name_end = tag.find(char::is_whitespace).unwrap_or(tag.len());
+    let mut element = XmlElement {
+        name: local_name(&tag[..name_end]),
+        ..XmlElement::default()
+    };
+
+    let mut rest = &tag[name_end..];
+    while let Some(eq) = rest.find('=') {
+        let key = rest[..eq].trim();
+        let value = rest[eq + 1..].trim_start();
+        let Some(quote) = value.chars().next().filter(|c| *c == '"' || *c == '\'') else {
+            break;
+        };
+        let Some(len) = value[1..].find(quote) else {
+            break;
+        };
+        element
+            .attributes
+            .push((local_name(key), unescape(&value[1..1 + len])));
+        rest = &value[len + 2..];
+    }
+    element
+}
+
+fn local_name(name: &str) -> String {
+    name.rsplit(':').next().unwrap_or(name).to_string()
+}
+
+/// Replace entity and character references
+fn unescape(text: &str) -> String {
+    if !text.contains('&') {
+        return text.to_string();
+    }
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(amp) = rest.find('&') {
+        result.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let Some(semi) = rest.find(';') else {
+            break;
+        };
+        let entity = &rest[1..semi];
+        let decoded = match entity {
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "amp" => Some('&'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => entity
+                .strip_prefix("#x")
+                .or_else(|| entity.strip_prefix("#X"))
+                .map(|hex| u32::from_str_radix(hex, 16))
+                .or_else(|| entity.strip_prefix('#').map(str::parse))
+                .and_then(Result::ok)
+                .and_then(char::from_u32),
+        };
+        match decoded {
+            Some(c) => {
+                result.push(c);
+                rest = &rest[semi + 1..];
+            }
+            None => {
+                result.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_elements_and_entities() {
+        let xml = br#"<?xml version="1.0"?>
+<c:chartSpace xmlns:c="urn:c"><!-- comment -->
+  <c:legendPos val="r"/>
+  <c:v>A &amp; B &#xAC00;</c:v><c:f><![CDATA[<x>]]></c:f>
+</c:chartSpace>"#;
+        let root = XmlElement::parse(xml).unwrap();
+        assert_eq!(root.name, "chartSpace");
+        assert_eq!(root.attr("c"), Some("urn:c"));
+        assert_eq!(root.child("legendPos").unwrap().attr("val"), Some("r"));
+        assert_eq!(root.descendant("v").unwrap().text, "A & B 가");
+        assert_eq!(root.child("f").unwrap().text, "<x>");
+    }
+}
@@ -0,0 +1,118 @@
+use super::{ContentContext, escape_xml, generate_block_content, generate_inline_content};
+use crate::common::HwpResult;
+use crate::model::{Annotation, Block, FieldKind, FieldRange};
+
+/// Generate a hidden comment as an annotation at its position
+pub(super) fn generate_annotation(
+    xml: &mut String,
+    annotation: &Annotation,
+    ctx: &mut ContentContext,
+) -> HwpResult<()> {
+    xml.push_str("<office:annotation>");
+    annotation_body(
+        xml,
+        &annotation.author,
+        &annotation.date,
+        &annotation.blocks,
+        ctx,
+    )?;
+    xml.push_str("</office:annotation>");
+    Ok(())
+}
+
+/// Generate a memo field as an annotation spanning the field's text
+pub(super) fn generate_memo(
+    xml: &mut String,
+    range: &FieldRange,
+    ctx: &mut ContentContext,
+) -> HwpResult<()> {
+    let FieldKind::Memo {
+        number,
+        author,
+        date,
+    } = &range.kind
+    else {
+        return Ok(());
+    };
+    ctx.annotation_count += 1;
+    let name = format!("memo{}", ctx.annotation_count);
+    let memos = ctx.memos;
+    let blocks = memos
+        .iter()
+        .find(|memo| memo.number == *number)
+        .map_or(&[][..], |memo| memo.blocks.as_slice());
+
+    xml.push_str(&format!(r#"<office:annotation office:name="{}">"#, name));
+    annotation_body(xml, author, date, blocks, ctx)?;
+    xml.push_str("</office:annotation>");
+    for inline in &range.inlines {
+        generate_inline_content(xml, inline, ctx)?;
+    }
+    xml.push_str(&format!(
+        r#"<office:annotation-end office:name="{}"/>"#,
+        name
+    ));
+    Ok(())
+}
+
+/// Author, date and paragraphs of an annotation. An annotation needs at
+/// least one paragraph.
+fn annotation_body(
+    xml: &mut String,
+    author: &str,
+    date: &str,
+    blocks: &[Block],
+    ctx: &mut ContentContext,
+) -> HwpResult<()> {
+    if !author.is_empty() {
+        xml.push_str(&format!("<dc:creator>{}</dc:creator>", escape_xml(author)));
+    }
+    if let Some(date) = iso_date(date) {
+        xml.push_str(&format!("<dc:date>{}</dc:date>", date));
+    }
+    if blocks.is_empty() {
+        xml.push_str("<text:p/>");
+    }
+    for block in blocks {
+        generate_block_content(xml, block, ctx)?;
+    }
+    Ok(())
+}
+
+/// "2024-03-05 14:20:00" as an xsd:dateTime, or None if it is not a date
+fn iso_date(date: &str) -> Option<String> {
+    let date = date.trim();
+    let (day, time) = date.split_once([' ', 'T']).unwrap_or((date, "00:00:00"));
+    let parts: Vec<&str> = day.split(['-', '.', '/']).collect();
+    let [year, month, day] = parts[..] else {
+        return None;
+    };
+    if [year, month, day]
+        .iter()
+        .any(|part| part.is_empty() || !part.chars().all(|c| c.is_ascii_digit()))
+        || !time.chars().all(|c| c.is_ascii_digit() || c == ':')
+    {
+        return None;
+    }
+    let time = match time.matches(':').count() {
+        0 => "00:00:00".to_string(),
+        1 => format!("{}:00", time),
+        _ => time.to_string(),
+    };
+    Some(format!("{}-{:0>2}-{:0>2}T{}", year, month, day, time))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_iso_date() {
+        assert_eq!(
+            iso_date("2024-03-05 14:20:00").as_deref(),
+            Some("2024-03-05T14:20:00")
+        );
+        assert_eq!(iso_date("2024.3.5").as_deref(), Some("2024-03-05T00:00:00"));
+        assert_eq!(iso_date("yesterday"), None);
+    }
+}
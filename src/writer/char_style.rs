@@ -0,0 +1,194 @@
+use crate::common::{Color, LineStyle};
+use crate::model::{CharShape, Document, EmphasisMark, lang};
+
+/// Attributes of `style:text-properties` for a char shape. Latin fonts
+/// are the western fonts, Hangul fonts the asian ones and other scripts
+/// the complex ones. ODF has a single letter spacing, scale and position,
+/// which come from the Hangul settings.
+pub(super) fn text_properties(doc: &Document, shape: &CharShape) -> String {
+    let mut xml = String::new();
+    for (lang, suffix) in [
+        (lang::LATIN, ""),
+        (lang::HANGUL, "-asian"),
+        (lang::OTHER, "-complex"),
+    ] {
+        let font = doc.font_index(lang, shape.font_ids[lang]);
+        if (font as usize) < doc.fonts.len() {
+            xml.push_str(&format!(r#" style:font-name{}="F{}""#, suffix, font));
+        }
+        let size = shape.lang_size(lang).to_pt();
+        if suffix.is_empty() {
+            xml.push_str(&format!(r#" fo:font-size="{}pt""#, size));
+        } else {
+            xml.push_str(&format!(r#" style:font-size{}="{}pt""#, suffix, size));
+        }
+    }
+
+    let spacing = shape.spacings[lang::HANGUL];
+    if spacing != 0 {
+        let size = shape.lang_size(lang::HANGUL).to_pt();
+        xml.push_str(&format!(
+            r#" fo:letter-spacing="{:.2}pt""#,
+            size * spacing as f64 / 100.0
+        ));
+    }
+    let ratio = shape.ratios[lang::HANGUL];
+    if ratio != 100 {
+        xml.push_str(&format!(r#" style:text-scale="{}%""#, ratio));
+    }
+    let offset = shape.offsets[lang::HANGUL];
+    if shape.superscript {
+        xml.push_str(r#" style:text-position="super 58%""#);
+    } else if shape.subscript {
+        xml.push_str(r#" style:text-position="sub 58%""#);
+    } else if offset != 0 {
+        xml.push_str(&format!(
+            r#" style:text-position="{}% 100%""#,
+            -(offset as i32)
+        ));
+    }
+
+    xml.push_str(&format!(r#" fo:color="{}""#, shape.color.to_hex()));
+    if shape.bold {
+        xml.push_str(
+            r#" fo:font-weight="bold" style:font-weight-asian="bold" style:font-weight-complex="bold""#,
+        );
+    }
+    if shape.italic {
+        xml.push_str(
+            r#" fo:font-style="italic" style:font-style-asian="italic" style:font-style-complex="italic""#,
+        );
+    }
+    xml.push_str(&decoration_properties(shape));
+    xml
+}
+
+/// Lines, outline, shadow, relief, emphasis marks and shading
+fn decoration_properties(shape: &CharShape) -> String {
+    let mut xml = String::new();
+    if shape.underline != LineStyle::None {
+        // An underline above the text is an overline
+        let line = if shape.underline_above {
+            "style:text-overline"
+        } else {
+            "style:text-underline"
+        };
+        xml.push_str(&line_properties(
+            line,
+            shape.underline,
+            shape.underline_color,
+        ));
+    }
+    if shape.strikethrough {
+        xml.push_str(&line_properties(
+            "style:text-line-through",
+            shape.strike_style,
+            shape.strike_color,
+        ));
+    }
+    if shape.outline {
+        xml.push_str(r#" style:text-outline="true""#);
+    }
+    if let Some(shadow) = &shape.shadow {
+        let size = shape.lang_size(lang::HANGUL).to_pt();
+        xml.push_str(&format!(
+            r#" fo:text-shadow="{:.2}pt {:.2}pt {}""#,
+            size * shadow.offset_x as f64 / 100.0,
+            size * shadow.offset_y as f64 / 100.0,
+            shadow.color.to_hex()
+        ));
+    }
+    if shape.emboss {
+        xml.push_str(r#" style:font-relief="embossed""#);
+    } else if shape.engrave {
+        xml.push_str(r#" style:font-relief="engraved""#);
+    }
+    let emphasis = match shape.emphasis {
+        EmphasisMark::None => None,
+        EmphasisMark::FilledCircle => Some("disc"),
+        EmphasisMark::OpenCircle => Some("circle"),
+        EmphasisMark::Dot => Some("dot"),
+        EmphasisMark::Caron | EmphasisMark::Tilde | EmphasisMark::Colon => Some("accent"),
+    };
+    if let Some(mark) = emphasis {
+        xml.push_str(&format!(r#" style:text-emphasize="{} above""#, mark));
+    }
+    // White shading is no shading
+    if shape.background_color.0 & 0xFFFFFF != 0xFFFFFF {
+        xml.push_str(&format!(
+            r#" fo:background-color="{}""#,
+            shape.background_color.to_hex()
+        ));
+    }
+    xml
+}
+
+/// Style, type, width and color of an underline, overline or strike line
+fn line_properties(prefix: &str, style: LineStyle, color: Color) -> String {
+    let (line_style, line_type) = match style {
+        LineStyle::Dotted => ("dotted", "single"),
+        LineStyle::Dashed => ("dash", "single"),
+        LineStyle::DashDot => ("dot-dash", "single"),
+        LineStyle::DashDotDot => ("dot-dot-dash", "single"),
+        LineStyle::Double => ("solid", "double"),
+        LineStyle::Wave => ("wave", "single"),
+        LineStyle::None | LineStyle::Solid => ("solid", "single"),
+    };
+    format!(
+        r#" {0}-style="{1}" {0}-type="{2}" {0}-width="auto" {0}-color="{3}""#,
+        prefix,
+        line_style,
+        line_type,
+        color.to_hex()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_language_fonts_and_spacing() {
+        let mut doc = Document::new();
+        doc.fonts = vec![
+            "함초롬바탕".to_string(),
+            "돋움".to_string(),
+            "Arial".to_string(),
+        ];
+        doc.font_counts = vec![2, 1, 0, 0, 0, 0, 0];
+        let mut shape = CharShape::new(0);
+        shape.font_ids[lang::HANGUL] = 1;
+        shape.relative_sizes[lang::LATIN] = 90;
+        shape.spacings[lang::HANGUL] = -5;
+        shape.ratios[lang::HANGUL] = 95;
+        shape.offsets[lang::HANGUL] = -10;
+
+        let xml = text_properties(&doc, &shape);
+        assert!(xml.contains(r#"style:font-name="F2" fo:font-size="9pt""#));
+        assert!(xml.contains(r#"style:font-name-asian="F1" style:font-size-asian="10pt""#));
+        assert!(xml.contains(r#"fo:letter-spacing="-0.50pt""#));
+        assert!(xml.contains(r#"style:text-scale="95%""#));
+        assert!(xml.contains(r#"style:text-position="10% 100%""#));
+    }
+
+    #[test]
+    fn test_decorations() {
+        let mut shape = CharShape::new(0);
+        shape.italic = true;
+        shape.underline = LineStyle::Wave;
+        shape.underline_color = Color(0x0000FF);
+        shape.strikethrough = true;
+        shape.strike_style = LineStyle::Double;
+        shape.engrave = true;
+        shape.emphasis = EmphasisMark::FilledCircle;
+
+        let xml = text_properties(&Document::new(), &shape);
+        assert!(xml.contains(r#"fo:font-style="italic""#));
+        assert!(xml.contains(r#"style:text-underline-style="wave""#));
+        assert!(xml.contains(r##"style:text-underline-color="#FF0000""##));
+        assert!(xml.contains(r#"style:text-line-through-type="double""#));
+        assert!(xml.contains(r#"style:font-relief="engraved""#));
+        assert!(xml.contains(r#"style:text-emphasize="disc above""#));
+        assert!(!xml.contains("fo:background-color"));
+    }
+}
@@ -0,0 +1,189 @@
+use super::escape_xml;
+use crate::common::HwpUnit;
+use crate::model::{Chart, ChartGrouping, ChartType, LegendPosition};
+
+/// content.xml of a chart object: the chart referring to its own data
+/// table, with categories in the first column and a column per series
+pub(super) fn chart_xml(chart: &Chart, width: HwpUnit, height: HwpUnit) -> String {
+    let class = match chart.chart_type {
+        ChartType::Column | ChartType::Bar => "chart:bar",
+        ChartType::Line => "chart:line",
+        ChartType::Area => "chart:area",
+        ChartType::Pie => "chart:circle",
+        ChartType::Doughnut => "chart:ring",
+        ChartType::Scatter => "chart:scatter",
+        ChartType::Radar => "chart:radar",
+    };
+
+    let mut properties = String::new();
+    if chart.chart_type == ChartType::Bar {
+        properties.push_str(r#" chart:vertical="true""#);
+    }
+    match chart.grouping {
+        ChartGrouping::Standard => {}
+        ChartGrouping::Stacked => properties.push_str(r#" chart:stacked="true""#),
+        ChartGrouping::PercentStacked => properties.push_str(r#" chart:percentage="true""#),
+    }
+    if chart.three_d {
+        properties.push_str(r#" chart:three-dimensional="true""#);
+    }
+
+    let rows = chart
+        .series
+        .iter()
+        .map(|series| series.values.len())
+        .chain([chart.categories.len()])
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    let last_column = column_name(chart.series.len());
+    let range = |column: &str, first: usize, last: usize| {
+        format!("local-table.${0}${1}:.${0}${2}", column, first, last)
+    };
+
+    let mut xml = format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<office:document-content xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0" xmlns:style="urn:oasis:names:tc:opendocument:xmlns:style:1.0" xmlns:text="urn:oasis:names:tc:opendocument:xmlns:text:1.0" xmlns:table="urn:oasis:names:tc:opendocument:xmlns:table:1.0" xmlns:chart="urn:oasis:names:tc:opendocument:xmlns:chart:1.0" xmlns:svg="urn:oasis:names:tc:opendocument:xmlns:svg-compatible:1.0" office:version="1.2"><office:automatic-styles><style:style style:name="ch1" style:family="chart"><style:chart-properties{}/></style:style></office:automatic-styles><office:body><office:chart><chart:chart chart:class="{}" svg:width="{:.2}mm" svg:height="{:.2}mm">"#,
+        properties,
+        class,
+        width.to_mm(),
+        height.to_mm()
+    );
+    if !chart.title.is_empty() {
+        xml.push_str(&format!(
+            "<chart:title><text:p>{}</text:p></chart:title>",
+            escape_xml(&chart.title)
+        ));
+    }
+    if let Some(legend) = chart.legend {
+        let position = match legend {
+            LegendPosition::Top => "top",
+            LegendPosition::Bottom => "bottom",
+            LegendPosition::Left => "start",
+            LegendPosition::Right => "end",
+        };
+        xml.push_str(&format!(
+            r#"<chart:legend chart:legend-position="{}"/>"#,
+            position
+        ));
+    }
+
+    xml.push_str(&format!(
+        r#"<chart:plot-area chart:style-name="ch1" table:cell-range-address="local-table.$A$1:.${}${}" chart:data-source-has-labels="both">"#,
+        last_column,
+        rows + 1
+    ));
+    // Scatter charts take their x values from the first column instead
+    if chart.chart_type == ChartType::Scatter {
+        xml.push_str(r#"<chart:axis chart:dimension="x" chart:name="primary-x"/>"#);
+    } else {
+        xml.push_str(&format!(
+            r#"<chart:axis chart:dimension="x" chart:name="primary-x"><chart:categories table:cell-range-address="{}"/></chart:axis>"#,
+            range("A", 2, rows + 1)
+        ));
+    }
+    xml.push_str(r#"<chart:axis chart:dimension="y" chart:name="primary-y"/>"#);
+    for i in 0..chart.series.len() {
+        let column = column_name(i + 1);
+        xml.push_str(&format!(
+            r#"<chart:series chart:values-cell-range-address="{}" chart:label-cell-address="local-table.${}$1">"#,
+            range(&column, 2, rows + 1),
+            column
+        ));
+        if chart.chart_type == ChartType::Scatter {
+            xml.push_str(&format!(
+                r#"<chart:domain table:cell-range-address="{}"/>"#,
+                range("A", 2, rows + 1)
+            ));
+        }
+        xml.push_str("</chart:series>");
+    }
+    xml.push_str("</chart:plot-area></chart:chart>");
+
+    // Data table: a header row of series names, then a row per category
+    xml.push_str(&format!(
+        r#"<table:table table:name="local-table"><table:table-header-columns><table:table-column/></table:table-header-columns><table:table-columns><table:table-column table:number-columns-repeated="{}"/></table:table-columns><table:table-header-rows><table:table-row><table:table-cell/>"#,
+        chart.series.len().max(1)
+    ));
+    for series in &chart.series {
+        xml.push_str(&string_cell(&series.name));
+    }
+    xml.push_str("</table:table-row></table:table-header-rows><table:table-rows>");
+    for row in 0..rows {
+        xml.push_str("<table:table-row>");
+        let category = chart.categories.get(row).map_or("", String::as_str);
+        match category.trim().parse::<f64>() {
+            Ok(x) if chart.chart_type == ChartType::Scatter && x.is_finite() => {
+                xml.push_str(&float_cell(x))
+            }
+            _ => xml.push_str(&string_cell(category)),
+        }
+        for series in &chart.series {
+            match series.values.get(row).copied().flatten() {
+                Some(value) => xml.push_str(&float_cell(value)),
+                None => xml.push_str("<table:table-cell/>"),
+            }
+        }
+        xml.push_str("</table:table-row>");
+    }
+    xml.push_str(
+        "</table:table-rows></table:table></office:chart></office:body></office:document-content>",
+    );
+    xml
+}
+
+fn string_cell(text: &str) -> String {
+    format!(
+        r#"<table:table-cell office:value-type="string"><text:p>{}</text:p></table:table-cell>"#,
+        escape_xml(text)
+    )
+}
+
+fn float_cell(value: f64) -> String {
+    format!(
+        r#"<table:table-cell office:value-type="float" office:value="{0}"><text:p>{0}</text:p></table:table-cell>"#,
+        value
+    )
+}
+
+/// Spreadsheet column name of a zero-based column index (A, B, ..., AA)
+fn column_name(index: usize) -> String {
+    let mut name = Vec::new();
+    let mut n = index + 1;
+    while n > 0 {
+        name.push(b'A' + ((n - 1) % 26) as u8);
+        n = (n - 1) / 26;
+    }
+    name.reverse();
+    String::from_utf8(name).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::ChartSeries;
+
+    #[test]
+    fn test_chart_xml_ranges() {
+        assert_eq!(column_name(0), "A");
+        assert_eq!(column_name(27), "AB");
+
+        let chart = Chart {
+            chart_type: ChartType::Bar,
+            grouping: ChartGrouping::Standard,
+            three_d: false,
+            title: String::new(),
+            legend: None,
+            categories: vec!["Q1".to_string(), "Q2".to_string()],
+            series: vec![ChartSeries {
+                name: "2024".to_string(),
+                values: vec![Some(1.0), Some(2.5)],
+            }],
+        };
+        let xml = chart_xml(&chart, HwpUnit(7200), HwpUnit(7200));
+        assert!(xml.contains(r#"chart:vertical="true""#));
+        assert!(xml.contains(r#"table:cell-range-address="local-table.$A$1:.$B$3""#));
+        assert!(xml.contains(r#"chart:values-cell-range-address="local-table.$B$2:.$B$3""#));
+        assert!(xml.contains(r#"office:value="2.5""#));
+    }
+}
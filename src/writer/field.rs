@@ -0,0 +1,110 @@
+use super::annotation::generate_memo;
+use super::{ContentContext, escape_xml, generate_inline_content};
+use crate::common::HwpResult;
+use crate::model::{FieldKind, FieldRange, Inline};
+
+/// Generate a field range as the matching ODF field or link.
+/// Fields without an ODF counterpart keep only their content.
+pub(super) fn generate_field_range(
+    xml: &mut String,
+    range: &FieldRange,
+    ctx: &mut ContentContext,
+) -> HwpResult<()> {
+    match &range.kind {
+        FieldKind::Hyperlink { target } => {
+            xml.push_str(&format!(
+                r#"<text:a xlink:type="simple" xlink:href="{}">"#,
+                escape_xml(target)
+            ));
+            generate_inlines(xml, &range.inlines, ctx)?;
+            xml.push_str("</text:a>");
+        }
+        FieldKind::ClickHere { direction } => {
+            // An untouched click-here field shows its guide text
+            if plain_text(&range.inlines).is_empty() {
+                xml.push_str(&format!(
+                    r#"<text:placeholder text:placeholder-type="text" text:description="{}">&lt;{}&gt;</text:placeholder>"#,
+                    escape_xml(direction),
+                    escape_xml(direction)
+                ));
+            } else {
+                generate_inlines(xml, &range.inlines, ctx)?;
+            }
+        }
+        FieldKind::CrossReference { target } => {
+            text_field(
+                xml,
+                &format!(
+                    r#"text:bookmark-ref text:reference-format="text" text:ref-name="{}""#,
+                    escape_xml(target)
+                ),
+                &range.inlines,
+            );
+        }
+        FieldKind::MailMerge { name } => {
+            text_field(
+                xml,
+                &format!(
+                    r#"text:database-display text:database-name="" text:table-name="" text:table-type="table" text:column-name="{}""#,
+                    escape_xml(name)
+                ),
+                &range.inlines,
+            );
+        }
+        FieldKind::Bookmark { name } => {
+            let name = escape_xml(name);
+            xml.push_str(&format!(r#"<text:bookmark-start text:name="{}"/>"#, name));
+            generate_inlines(xml, &range.inlines, ctx)?;
+            xml.push_str(&format!(r#"<text:bookmark-end text:name="{}"/>"#, name));
+        }
+        FieldKind::Date => text_field(xml, "text:date", &range.inlines),
+        FieldKind::DocumentDate => {
+            text_field(xml, r#"text:date text:fixed="true""#, &range.inlines)
+        }
+        FieldKind::FilePath => {
+            text_field(xml, r#"text:file-name text:display="full""#, &range.inlines)
+        }
+        FieldKind::Memo { .. } => generate_memo(xml, range, ctx)?,
+        _ => generate_inlines(xml, &range.inlines, ctx)?,
+    }
+    Ok(())
+}
+
+fn generate_inlines(
+    xml: &mut String,
+    inlines: &[Inline],
+    ctx: &mut ContentContext,
+) -> HwpResult<()> {
+    for inline in inlines {
+        generate_inline_content(xml, inline, ctx)?;
+    }
+    Ok(())
+}
+
+/// ODF field element whose content is the field's current text.
+/// `element` is the element name followed by its attributes.
+fn text_field(xml: &mut String, element: &str, inlines: &[Inline]) {
+    let name = element.split(' ').next().unwrap_or(element);
+    xml.push_str(&format!(
+        "<{}>{}</{}>",
+        element,
+        escape_xml(&plain_text(inlines)),
+        name
+    ));
+}
+
+/// Text of a field's content without formatting
+fn plain_text(inlines: &[Inline]) -> String {
+    let mut text = String::new();
+    for inline in inlines {
+        match inline {
+            Inline::Text(run) => text.push_str(&run.text),
+            Inline::FieldRange(range) => text.push_str(&plain_text(&range.inlines)),
+            Inline::Dutmal(dutmal) => text.push_str(&dutmal.base),
+            Inline::CharOverlap(overlap) => text.push_str(&overlap.chars),
+            Inline::NonBreakingSpace | Inline::FixedWidthSpace => text.push(' '),
+            _ => {}
+        }
+    }
+    text
+}
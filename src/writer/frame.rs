@@ -0,0 +1,226 @@
+use super::{ContentContext, generate_block_content};
+use crate::common::{HwpResult, HwpUnit};
+use crate::model::{CaptionSide, ObjectAlign, ObjectPlacement, RelativeTo, TextFlow, TextWrap};
+
+/// Generate a frame around a drawing object's `content`.
+/// `properties` are the object's own graphic properties (border, image
+/// adjustments, ...) and `transform` an optional draw:transform attribute.
+/// A captioned object sits as a character inside an outer frame whose text
+/// box also holds the caption paragraphs.
+pub(super) fn generate_frame(
+    xml: &mut String,
+    placement: &ObjectPlacement,
+    name: &str,
+    transform: &str,
+    properties: &str,
+    content: &str,
+    ctx: &mut ContentContext,
+) -> HwpResult<()> {
+    let Some(caption) = &placement.caption else {
+        let style = ctx.automatic_style(
+            "graphic",
+            "fr",
+            &format!(
+                "<style:graphic-properties{}{}/>",
+                placement_properties(placement),
+                properties
+            ),
+        );
+        xml.push_str(&format!(
+            r#"<draw:frame draw:style-name="{}" draw:name="{}"{}{}>{}</draw:frame>"#,
+            style,
+            name,
+            transform,
+            frame_attributes(placement, placement.width, placement.height),
+            content
+        ));
+        return Ok(());
+    };
+
+    let outer_style = ctx.automatic_style(
+        "graphic",
+        "fr",
+        &format!(
+            "<style:graphic-properties{}/>",
+            placement_properties(placement)
+        ),
+    );
+    let inner_style = ctx.automatic_style(
+        "graphic",
+        "fr",
+        &format!(
+            r#"<style:graphic-properties style:vertical-pos="top" style:vertical-rel="baseline"{}/>"#,
+            properties
+        ),
+    );
+    ctx.frame_count += 1;
+
+    // The text box grows with the caption; left and right captions go below
+    xml.push_str(&format!(
+        r#"<draw:frame draw:style-name="{}" draw:name="Frame{}"{}{}><draw:text-box fo:min-height="{:.2}mm">"#,
+        outer_style,
+        ctx.frame_count,
+        transform,
+        frame_attributes(placement, placement.width, HwpUnit(0)),
+        placement.height.to_mm()
+    ));
+    if caption.side == CaptionSide::Top {
+        for block in &caption.blocks {
+            generate_block_content(xml, block, ctx)?;
+        }
+    }
+    xml.push_str(&format!(
+        r#"<text:p><draw:frame draw:style-name="{}" draw:name="{}" text:anchor-type="as-char" svg:width="{:.2}mm" svg:height="{:.2}mm">{}</draw:frame></text:p>"#,
+        inner_style,
+        name,
+        placement.width.to_mm(),
+        placement.height.to_mm(),
+        content
+    ));
+    if caption.side != CaptionSide::Top {
+        for block in &caption.blocks {
+            generate_block_content(xml, block, ctx)?;
+        }
+    }
+    xml.push_str("</draw:text-box></draw:frame>");
+    Ok(())
+}
+
+/// Anchor, position, size and stacking order of a frame or shape.
+/// A zero height is left out so the frame can grow with its content.
+pub(super) fn frame_attributes(
+    placement: &ObjectPlacement,
+    width: HwpUnit,
+    height: HwpUnit,
+) -> String {
+    let mut attrs = if placement.treat_as_char {
+        r#" text:anchor-type="as-char""#.to_string()
+    } else {
+        let mut attrs = r#" text:anchor-type="paragraph""#.to_string();
+        // Offsets only apply to positions measured from the top or left
+        if horizontal_pos(placement.horz_align).starts_with("from") {
+            attrs.push_str(&format!(r#" svg:x="{:.2}mm""#, placement.offset_x.to_mm()));
+        }
+        if vertical_pos(placement.vert_align).starts_with("from") {
+            attrs.push_str(&format!(r#" svg:y="{:.2}mm""#, placement.offset_y.to_mm()));
+        }
+        attrs
+    };
+    attrs.push_str(&format!(r#" svg:width="{:.2}mm""#, width.to_mm()));
+    if height.0 > 0 {
+        attrs.push_str(&format!(r#" svg:height="{:.2}mm""#, height.to_mm()));
+    }
+    attrs.push_str(&format!(r#" draw:z-index="{}""#, placement.z_order.max(0)));
+    attrs
+}
+
+fn vertical_pos(align: ObjectAlign) -> &'static str {
+    match align {
+        ObjectAlign::Center => "middle",
+        ObjectAlign::End => "bottom",
+        ObjectAlign::Start | ObjectAlign::Inside | ObjectAlign::Outside => "from-top",
+    }
+}
+
+fn horizontal_pos(align: ObjectAlign) -> &'static str {
+    match align {
+        ObjectAlign::Start => "from-left",
+        ObjectAlign::Center => "center",
+        ObjectAlign::End => "right",
+        ObjectAlign::Inside => "inside",
+        ObjectAlign::Outside => "outside",
+    }
+}
+
+/// Position, wrapping and margin properties of a floating object
+pub(super) fn placement_properties(placement: &ObjectPlacement) -> String {
+    let mut props = String::new();
+
+    if placement.treat_as_char {
+        props.push_str(r#" style:vertical-pos="top" style:vertical-rel="baseline""#);
+    } else {
+        // Paper-relative positions are measured from the page edge,
+        // page-relative ones from the page margins. ODF has no column
+        // reference; the paragraph area is the closest.
+        let rel = |rel: RelativeTo| match rel {
+            RelativeTo::Paper => "page",
+            RelativeTo::Page => "page-content",
+            RelativeTo::Column | RelativeTo::Paragraph => "paragraph",
+        };
+        props.push_str(&format!(
+            r#" style:vertical-pos="{}" style:vertical-rel="{}" style:horizontal-pos="{}" style:horizontal-rel="{}""#,
+            vertical_pos(placement.vert_align),
+            rel(placement.vert_rel),
+            horizontal_pos(placement.horz_align),
+            rel(placement.horz_rel)
+        ));
+
+        let side = match placement.text_flow {
+            TextFlow::BothSides => "parallel",
+            TextFlow::LeftOnly => "left",
+            TextFlow::RightOnly => "right",
+            TextFlow::LargestOnly => "biggest",
+        };
+        let (wrap, extra) = match placement.wrap {
+            TextWrap::Square => (side, ""),
+            TextWrap::Tight => (
+                side,
+                r#" style:wrap-contour="true" style:wrap-contour-mode="outside""#,
+            ),
+            TextWrap::Through => (
+                side,
+                r#" style:wrap-contour="true" style:wrap-contour-mode="full""#,
+            ),
+            TextWrap::TopAndBottom => ("none", ""),
+            TextWrap::BehindText => ("run-through", r#" style:run-through="background""#),
+            TextWrap::InFrontOfText => ("run-through", r#" style:run-through="foreground""#),
+        };
+        props.push_str(&format!(r#" style:wrap="{}"{}"#, wrap, extra));
+        props.push_str(&format!(
+            r#" style:flow-with-text="{}""#,
+            placement.flow_with_text
+        ));
+    }
+
+    let margin = &placement.outer_margin;
+    props.push_str(&format!(
+        r#" fo:margin-left="{:.2}mm" fo:margin-right="{:.2}mm" fo:margin-top="{:.2}mm" fo:margin-bottom="{:.2}mm""#,
+        margin.left.to_mm(),
+        margin.right.to_mm(),
+        margin.top.to_mm(),
+        margin.bottom.to_mm()
+    ));
+    props
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_placement_properties() {
+        let mut placement = ObjectPlacement::new();
+        placement.vert_rel = RelativeTo::Paper;
+        placement.horz_align = ObjectAlign::Center;
+        placement.wrap = TextWrap::Square;
+        placement.text_flow = TextFlow::LargestOnly;
+        placement.offset_x = HwpUnit(7200);
+        placement.offset_y = HwpUnit(7200);
+        placement.z_order = 2;
+
+        let props = placement_properties(&placement);
+        assert!(props.contains(r#"style:vertical-pos="from-top" style:vertical-rel="page" style:horizontal-pos="center" style:horizontal-rel="paragraph""#));
+        assert!(props.contains(r#"style:wrap="biggest""#));
+
+        // A centered object ignores its horizontal offset
+        let attrs = frame_attributes(&placement, HwpUnit(7200), HwpUnit(3600));
+        assert_eq!(
+            attrs,
+            r#" text:anchor-type="paragraph" svg:y="25.40mm" svg:width="25.40mm" svg:height="12.70mm" draw:z-index="2""#
+        );
+
+        placement.wrap = TextWrap::BehindText;
+        let props = placement_properties(&placement);
+        assert!(props.contains(r#"style:wrap="run-through" style:run-through="background""#));
+    }
+}
@@ -0,0 +1,303 @@
+use super::{ContentContext, escape_xml, frame};
+use crate::common::HwpResult;
+use crate::model::{Equation, EquationAlign, EquationNode, EquationSpace, EquationStyle};
+
+/// Generate an equation as a frame holding an embedded formula object.
+/// The object's MathML is collected in the context and packaged under
+/// `Object N/`.
+pub(super) fn generate_equation(
+    xml: &mut String,
+    equation: &Equation,
+    ctx: &mut ContentContext,
+) -> HwpResult<()> {
+    let name = format!("Object {}", ctx.objects.len() + 1);
+    ctx.objects.push((
+        name.clone(),
+        "application/vnd.oasis.opendocument.formula",
+        formula_xml(equation),
+    ));
+
+    let object = format!(
+        r#"<draw:object xlink:href="./{}" xlink:type="simple" xlink:show="embed" xlink:actuate="onLoad"/>"#,
+        name
+    );
+    frame::generate_frame(
+        xml,
+        &equation.placement,
+        &name.replace(' ', ""),
+        "",
+        "",
+        &object,
+        ctx,
+    )
+}
+
+/// content.xml of a formula object
+fn formula_xml(equation: &Equation) -> String {
+    let mut math = String::new();
+    write_node(&mut math, &equation.root, None);
+    // HWPUNIT is 1/7200 inch, so 100 units make a point
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<math xmlns="http://www.w3.org/1998/Math/MathML" display="inline"><mstyle mathsize="{}pt" mathcolor="{}">{}</mstyle></math>"#,
+        equation.font_size.0.max(100) as f64 / 100.0,
+        equation.color.to_hex(),
+        math
+    )
+}
+
+/// Write a node as MathML. `variant` is the mathvariant set by `rm`, `it`
+/// or `bold`, applied to the token elements.
+fn write_node(xml: &mut String, node: &EquationNode, variant: Option<&str>) {
+    match node {
+        EquationNode::Row(items) => {
+            xml.push_str("<mrow>");
+            for item in items {
+                write_node(xml, item, variant);
+            }
+            xml.push_str("</mrow>");
+        }
+        // Multi-letter identifiers would otherwise be set upright
+        EquationNode::Identifier(name) if name.chars().count() > 1 => {
+            token(xml, "mi", name, variant.or(Some("italic")))
+        }
+        EquationNode::Identifier(name) | EquationNode::Function(name) => {
+            token(xml, "mi", name, variant)
+        }
+        EquationNode::Number(number) => token(xml, "mn", number, variant),
+        EquationNode::Text(text) => token(xml, "mtext", text, variant),
+        EquationNode::Operator(operator) => {
+            xml.push_str(&format!("<mo>{}</mo>", escape_xml(operator)))
+        }
+        EquationNode::Space(space) => {
+            let width = match space {
+                EquationSpace::Normal => "0.33em",
+                EquationSpace::Thin => "0.17em",
+            };
+            xml.push_str(&format!(r#"<mspace width="{}"/>"#, width));
+        }
+        EquationNode::Fraction {
+            numerator,
+            denominator,
+            line,
+        } => {
+            xml.push_str(if *line {
+                "<mfrac>"
+            } else {
+                r#"<mfrac linethickness="0">"#
+            });
+            write_node(xml, numerator, variant);
+            write_node(xml, denominator, variant);
+            xml.push_str("</mfrac>");
+        }
+        EquationNode::Root {
+            index: None,
+            radicand,
+        } => {
+            xml.push_str("<msqrt>");
+            write_node(xml, radicand, variant);
+            xml.push_str("</msqrt>");
+        }
+        EquationNode::Root {
+            index: Some(index),
+            radicand,
+        } => {
+            xml.push_str("<mroot>");
+            write_node(xml, radicand, variant);
+            write_node(xml, index, variant);
+            xml.push_str("</mroot>");
+        }
+        EquationNode::Script { base, sub, sup } => {
+            let tag = match (sub, sup) {
+                (Some(_), Some(_)) => "msubsup",
+                (Some(_), None) => "msub",
+                _ => "msup",
+            };
+            xml.push_str(&format!("<{}>", tag));
+            write_node(xml, base, variant);
+            for script in [sub, sup].into_iter().flatten() {
+                write_node(xml, script, variant);
+            }
+            xml.push_str(&format!("</{}>", tag));
+        }
+        EquationNode::LargeOperator { operator, from, to } => {
+            // Integral limits go beside the sign, other limits above and below
+            let integral = operator.starts_with(['∫', '∬', '∭', '∮', '∯', '∰']);
+            let tag = match (from, to, integral) {
+                (None, None, _) => "",
+                (Some(_), Some(_), true) => "msubsup",
+                (Some(_), None, true) => "msub",
+                (None, Some(_), true) => "msup",
+                (Some(_), Some(_), false) => "munderover",
+                (Some(_), None, false) => "munder",
+                (None, Some(_), false) => "mover",
+            };
+            if !tag.is_empty() {
+                xml.push_str(&format!("<{}>", tag));
+            }
+            if operator.chars().all(char::is_alphabetic) {
+                xml.push_str(&format!("<mi>{}</mi>", operator));
+            } else {
+                xml.push_str(&format!(r#"<mo largeop="true">{}</mo>"#, operator));
+            }
+            for limit in [from, to].into_iter().flatten() {
+                write_node(xml, limit, variant);
+            }
+            if !tag.is_empty() {
+                xml.push_str(&format!("</{}>", tag));
+            }
+        }
+        EquationNode::Table { align, rows } => {
+            let align = match align {
+                EquationAlign::Left => "left",
+                EquationAlign::Center => "center",
+                EquationAlign::Right => "right",
+            };
+            xml.push_str(&format!(r#"<mtable columnalign="{}">"#, align));
+            for row in rows {
+                xml.push_str("<mtr>");
+                for cell in row {
+                    xml.push_str("<mtd>");
+                    write_node(xml, cell, variant);
+                    xml.push_str("</mtd>");
+                }
+                xml.push_str("</mtr>");
+            }
+            xml.push_str("</mtable>");
+        }
+        EquationNode::Fenced { open, close, body } => {
+            xml.push_str("<mrow>");
+            if !open.is_empty() {
+                xml.push_str(&fence(open));
+            }
+            write_node(xml, body, variant);
+            if !close.is_empty() {
+                xml.push_str(&fence(close));
+            }
+            xml.push_str("</mrow>");
+        }
+        EquationNode::Accent {
+            accent,
+            under,
+            body,
+        } => {
+            let (tag, attribute) = if *under {
+                ("munder", "accentunder")
+            } else {
+                ("mover", "accent")
+            };
+            xml.push_str(&format!(r#"<{} {}="true">"#, tag, attribute));
+            write_node(xml, body, variant);
+            xml.push_str(&format!("<mo>{}</mo></{}>", escape_xml(accent), tag));
+        }
+        EquationNode::Styled { style, body } => {
+            let variant = match style {
+                EquationStyle::Roman => "normal",
+                EquationStyle::Italic => "italic",
+                EquationStyle::Bold => "bold",
+            };
+            write_node(xml, body, Some(variant));
+        }
+    }
+}
+
+/// Token element (mi, mn, mtext) with an optional mathvariant
+fn token(xml: &mut String, tag: &str, text: &str, variant: Option<&str>) {
+    match variant {
+        Some(variant) => xml.push_str(&format!(
+            r#"<{0} mathvariant="{1}">{2}</{0}>"#,
+            tag,
+            variant,
+            escape_xml(text)
+        )),
+        None => xml.push_str(&format!("<{0}>{1}</{0}>", tag, escape_xml(text))),
+    }
+}
+
+fn fence(delimiter: &str) -> String {
+    format!(
+        r#"<mo fence="true" stretchy="true">{}</mo>"#,
+        escape_xml(delimiter)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mathml(node: &EquationNode) -> String {
+        let mut xml = String::new();
+        write_node(&mut xml, node, None);
+        xml
+    }
+
+    #[test]
+    fn test_write_node_fraction_and_limits() {
+        let fraction = EquationNode::Fraction {
+            numerator: Box::new(EquationNode::Identifier("a".to_string())),
+            denominator: Box::new(EquationNode::Number("2".to_string())),
+            line: true,
+        };
+        assert_eq!(mathml(&fraction), "<mfrac><mi>a</mi><mn>2</mn></mfrac>");
+
+        let sum = EquationNode::LargeOperator {
+            operator: "∑".to_string(),
+            from: Some(Box::new(EquationNode::Identifier("i".to_string()))),
+            to: Some(Box::new(EquationNode::Identifier("n".to_string()))),
+        };
+        assert_eq!(
+            mathml(&sum),
+            r#"<munderover><mo largeop="true">∑</mo><mi>i</mi><mi>n</mi></munderover>"#
+        );
+
+        let roman = EquationNode::Styled {
+            style: EquationStyle::Roman,
+            body: Box::new(EquationNode::Identifier("dx".to_string())),
+        };
+        assert_eq!(mathml(&roman), r#"<mi mathvariant="normal">dx</mi>"#);
+    }
+
+    #[test]
+    fn test_header_objects_follow_content_objects() {
+        use crate::common::{Color, HwpUnit};
+        use crate::model::{
+            Block, Control, Document, HeaderFooter, Inline, ObjectPlacement, PageApply, Paragraph,
+            Section,
+        };
+
+        let equation_para = || {
+            let mut para = Paragraph::new(0);
+            para.inlines
+                .push(Inline::Control(Control::Equation(Equation {
+                    id: 0,
+                    script: "x".to_string(),
+                    root: EquationNode::Identifier("x".to_string()),
+                    font_size: HwpUnit(1000),
+                    color: Color(0),
+                    baseline: 0,
+                    version: String::new(),
+                    font_name: String::new(),
+                    placement: ObjectPlacement::new(),
+                })));
+            Block::Paragraph(para)
+        };
+        let mut section = Section::new();
+        section.blocks.push(equation_para());
+        section.headers.push(HeaderFooter {
+            apply_to: PageApply::Both,
+            text_width: HwpUnit(0),
+            text_height: HwpUnit(0),
+            blocks: vec![equation_para()],
+        });
+        let mut doc = Document::new();
+        doc.sections.push(section);
+
+        let page_styles = super::super::PageStyles::collect(&doc);
+        let (_, objects) = super::super::generate_content_xml(&doc, &page_styles).unwrap();
+        let (styles, objects) =
+            super::super::generate_styles_xml(&doc, &page_styles, objects).unwrap();
+        let names: Vec<&str> = objects.iter().map(|(name, _, _)| name.as_str()).collect();
+        assert_eq!(names, vec!["Object 1", "Object 2"]);
+        assert!(styles.contains(r#"xlink:href="./Object 2""#));
+    }
+}
@@ -1,8 +1,29 @@
-use crate::common::HwpResult;
-use crate::model::Document;
-use std::collections::HashMap;
-
+use crate::common::{HwpResult, LineStyle};
+use crate::model::{
+    Border, BorderFill, ColumnDef, Document, FillType, Memo, NoteShape, NumberKind, PageOverride,
+    TextDirection,
+};
+use std::collections::{BTreeMap, HashMap};
+
+mod annotation;
+mod char_style;
+mod chart;
+mod field;
+mod frame;
+mod math;
+mod note;
+mod numbering;
+mod ole;
+mod overlap;
+mod page;
+mod picture;
+mod ruby;
+mod shape;
+mod table;
+mod tracked;
+mod vertical;
 mod zip_utils;
+use page::PageStyles;
 use zip_utils::write_zip_stored;
 
 /// Generate ODT (OpenDocument Text) file from document model
@@ -15,16 +36,20 @@ pub fn generate_odt(doc: &Document) -> HwpResult<Vec<u8>> {
         b"application/vnd.oasis.opendocument.text".to_vec(),
     ));
 
-    // 2. META-INF/manifest.xml
-    let manifest = generate_manifest();
+    // 2. META-INF/manifest.xml, listing the formula and chart objects found
+    // while generating content and the headers and footers of styles
+    let page_styles = PageStyles::collect(doc);
+    let (content, objects) = generate_content_xml(doc, &page_styles)?;
+    let (styles, objects) = generate_styles_xml(doc, &page_styles, objects)?;
+    let mut pictures = picture::package_pictures(doc);
+    pictures.extend(ole::package_ole_objects(doc));
+    let manifest = generate_manifest(&pictures, &objects);
     entries.push(("META-INF/manifest.xml", manifest.into_bytes()));
 
     // 3. content.xml
-    let content = generate_content_xml(doc)?;
     entries.push(("content.xml", content.into_bytes()));
 
     // 4. styles.xml
-    let styles = generate_styles_xml(doc)?;
     entries.push(("styles.xml", styles.into_bytes()));
 
     // 5. settings.xml
@@ -35,33 +60,75 @@ pub fn generate_odt(doc: &Document) -> HwpResult<Vec<u8>> {
     let meta = generate_meta_xml();
     entries.push(("meta.xml", meta.into_bytes()));
 
+    // 7. Embedded images, OLE objects and their replacement images
+    for (path, _, data) in &pictures {
+        entries.push((path, data.to_vec()));
+    }
+
+    // 8. Embedded formula and chart objects
+    let object_paths: Vec<String> = objects
+        .iter()
+        .map(|(name, _, _)| format!("{}/content.xml", name))
+        .collect();
+    for (path, (_, _, object)) in object_paths.iter().zip(&objects) {
+        entries.push((path, object.as_bytes().to_vec()));
+    }
+
     // Package as ZIP
     let odt_data = write_zip_stored(&entries)?;
     Ok(odt_data)
 }
 
-fn generate_manifest() -> String {
-    r#"<?xml version="1.0" encoding="UTF-8"?>
+fn generate_manifest(pictures: &[(String, &str, &[u8])], objects: &[EmbeddedObject]) -> String {
+    let mut xml = String::from(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
 <manifest:manifest xmlns:manifest="urn:oasis:names:tc:opendocument:xmlns:manifest:1.0" manifest:version="1.2">
   <manifest:file-entry manifest:media-type="application/vnd.oasis.opendocument.text" manifest:full-path="/"/>
   <manifest:file-entry manifest:media-type="text/xml" manifest:full-path="content.xml"/>
   <manifest:file-entry manifest:media-type="text/xml" manifest:full-path="styles.xml"/>
   <manifest:file-entry manifest:media-type="text/xml" manifest:full-path="settings.xml"/>
-  <manifest:file-entry manifest:media-type="text/xml" manifest:full-path="meta.xml"/>
-</manifest:manifest>"#
-        .to_string()
+  <manifest:file-entry manifest:media-type="text/xml" manifest:full-path="meta.xml"/>"#,
+    );
+    for (path, media_type, _) in pictures {
+        xml.push_str(&format!(
+            r#"
+  <manifest:file-entry manifest:media-type="{}" manifest:full-path="{}"/>"#,
+            media_type, path
+        ));
+    }
+    for (name, media_type, _) in objects {
+        xml.push_str(&format!(
+            r#"
+  <manifest:file-entry manifest:media-type="{1}" manifest:full-path="{0}/" manifest:version="1.2"/>
+  <manifest:file-entry manifest:media-type="text/xml" manifest:full-path="{0}/content.xml"/>"#,
+            name, media_type
+        ));
+    }
+    xml.push_str("\n</manifest:manifest>");
+    xml
 }
 
-fn generate_content_xml(doc: &Document) -> HwpResult<String> {
+/// Formula or chart object: directory name, media type and content.xml
+type EmbeddedObject = (String, &'static str, String);
+
+/// Generate content.xml; also returns the embedded objects
+fn generate_content_xml(
+    doc: &Document,
+    page_styles: &PageStyles,
+) -> HwpResult<(String, Vec<EmbeddedObject>)> {
     let mut xml = String::new();
     xml.push_str(
         r#"<?xml version="1.0" encoding="UTF-8"?>
 <office:document-content
   xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0"
+  xmlns:style="urn:oasis:names:tc:opendocument:xmlns:style:1.0"
   xmlns:text="urn:oasis:names:tc:opendocument:xmlns:text:1.0"
   xmlns:table="urn:oasis:names:tc:opendocument:xmlns:table:1.0"
   xmlns:draw="urn:oasis:names:tc:opendocument:xmlns:drawing:1.0"
+  xmlns:fo="urn:oasis:names:tc:opendocument:xmlns:xsl-fo-compatible:1.0"
   xmlns:xlink="http://www.w3.org/1999/xlink"
+  xmlns:svg="urn:oasis:names:tc:opendocument:xmlns:svg-compatible:1.0"
+  xmlns:dc="http://purl.org/dc/elements/1.1/"
   office:version="1.2">
   <office:scripts/>
   <office:font-face-decls>"#,
@@ -77,20 +144,49 @@ fn generate_content_xml(doc: &Document) -> HwpResult<String> {
         ));
     }
 
+    // Generate document content first so automatic styles can be collected
+    let mut body = String::new();
+    let mut ctx = ContentContext::new(doc);
+    ctx.track_changes = true;
+    for (idx, section) in doc.sections.iter().enumerate() {
+        // Every section after the first starts a new page with its master page
+        let master_page = page_styles.master_name(idx);
+        if idx > 0 || section.start_numbers.page != 0 || master_page != "Standard" {
+            ctx.section_start = Some(SectionStart {
+                master_page,
+                page_number: section.start_numbers.page,
+            });
+        }
+        ctx.current_master = page_styles.body_master_name(idx);
+        ctx.override_masters = page_styles.override_master_names(idx);
+        ctx.memos = &section.memos;
+        ctx.page_columns = Some(&section.columns);
+        ctx.upright_latin = section.text_direction == TextDirection::VerticalUpright;
+        note::start_section(section, &mut ctx);
+        numbering::start_section(section, &mut ctx);
+        for block in &section.blocks {
+            generate_block_content(&mut body, block, &mut ctx)?;
+        }
+        close_text_section(&mut body, &mut ctx);
+    }
+
     xml.push_str(
         r#"
   </office:font-face-decls>
-  <office:automatic-styles/>
+  <office:automatic-styles>"#,
+    );
+    for style in ctx.auto_styles.values() {
+        xml.push_str(style);
+    }
+    xml.push_str(
+        r#"
+  </office:automatic-styles>
   <office:body>
     <office:text>"#,
     );
-
-    // Generate document content
-    for section in &doc.sections {
-        for block in &section.blocks {
-            generate_block_content(&mut xml, block)?;
-        }
-    }
+    xml.push_str(&tracked::tracked_changes_xml(&ctx));
+    xml.push_str(&numbering::sequence_decls_xml());
+    xml.push_str(&body);
 
     xml.push_str(
         r#"
@@ -98,75 +194,378 @@ fn generate_content_xml(doc: &Document) -> HwpResult<String> {
   </office:body>
 </office:document-content>"#,
     );
-    Ok(xml)
+    Ok((xml, ctx.objects))
+}
+
+/// State shared while generating content.xml
+struct ContentContext<'a> {
+    doc: &'a Document,
+    /// Automatic styles keyed by style name
+    auto_styles: BTreeMap<String, String>,
+    /// Names of deduplicated automatic styles keyed by family and properties
+    style_names: HashMap<(&'static str, String), String>,
+    /// Master page switch applied to the next paragraph
+    section_start: Option<SectionStart>,
+    /// Whether a `text:section` for a column change is open
+    in_text_section: bool,
+    text_section_count: u32,
+    table_count: u32,
+    picture_count: u32,
+    frame_count: u32, // Outer frames of captioned objects
+    shape_count: u32,
+    ole_count: u32,
+    annotation_count: u32,
+    /// Memo contents of the current section
+    memos: &'a [Memo],
+    /// Column layout of the current section's pages
+    page_columns: Option<&'a ColumnDef>,
+    /// Whether tracked changes get changed regions (body text only), and
+    /// the regions collected so far
+    track_changes: bool,
+    changed_regions: Vec<String>,
+    /// Whether the current text area is vertical with upright Latin letters
+    upright_latin: bool,
+    /// Formula and chart objects packaged with the document
+    objects: Vec<EmbeddedObject>,
+    /// Note shapes of the current section, running note counts for the
+    /// citations and a document-wide counter for unique note IDs
+    footnote_shape: NoteShape,
+    endnote_shape: NoteShape,
+    footnote_count: u32,
+    endnote_count: u32,
+    note_id_count: u32,
+    /// Caption sequence values, restarts pending for the next number, and
+    /// a counter for unique sequence reference names
+    numbers: HashMap<NumberKind, u32>,
+    next_numbers: HashMap<NumberKind, u32>,
+    sequence_ref_count: u32,
+    /// Master page of the current section's body pages, and the one-page
+    /// masters of its page hiding and odd/even adjust controls
+    current_master: String,
+    override_masters: Vec<(PageOverride, String)>,
+}
+
+impl<'a> ContentContext<'a> {
+    fn new(doc: &'a Document) -> Self {
+        ContentContext {
+            doc,
+            auto_styles: BTreeMap::new(),
+            style_names: HashMap::new(),
+            section_start: None,
+            in_text_section: false,
+            text_section_count: 0,
+            table_count: 0,
+            picture_count: 0,
+            frame_count: 0,
+            shape_count: 0,
+            ole_count: 0,
+            annotation_count: 0,
+            memos: &[],
+            page_columns: None,
+            track_changes: false,
+            changed_regions: Vec::new(),
+            upright_latin: false,
+            objects: Vec::new(),
+            footnote_shape: NoteShape::new(),
+            endnote_shape: NoteShape::new(),
+            footnote_count: 0,
+            endnote_count: 0,
+            note_id_count: 0,
+            numbers: HashMap::new(),
+            next_numbers: HashMap::new(),
+            sequence_ref_count: 0,
+            current_master: "Standard".to_string(),
+            override_masters: Vec::new(),
+        }
+    }
+
+    fn add_style(&mut self, name: &str, xml: impl FnOnce() -> String) {
+        if !self.auto_styles.contains_key(name) {
+            self.auto_styles.insert(name.to_string(), xml());
+        }
+    }
+
+    /// Add an automatic style of the given family, reusing an existing one
+    /// with the same properties, and return its name
+    fn automatic_style(&mut self, family: &'static str, prefix: &str, properties: &str) -> String {
+        let key = (family, properties.to_string());
+        if let Some(name) = self.style_names.get(&key) {
+            return name.clone();
+        }
+
+        let count = self
+            .style_names
+            .keys()
+            .filter(|(f, _)| *f == family)
+            .count();
+        let name = format!("{}{}", prefix, count + 1);
+        self.auto_styles.insert(
+            name.clone(),
+            format!(
+                r#"
+    <style:style style:name="{}" style:family="{}">
+      {}
+    </style:style>"#,
+                name, family, properties
+            ),
+        );
+        self.style_names.insert(key, name.clone());
+        name
+    }
+}
+
+/// Start of a section in the body text
+struct SectionStart {
+    master_page: String,
+    page_number: u16, // 0 = continue numbering
 }
 
-fn generate_block_content(xml: &mut String, block: &crate::model::Block) -> HwpResult<()> {
+/// Resolve the paragraph style name, adding a break or master page style when needed
+fn paragraph_style_name(para: &crate::model::Paragraph, ctx: &mut ContentContext) -> String {
+    use crate::model::Inline;
+
+    // A page number restart on a page break re-applies the current master page
+    let page_restart = para.inlines.iter().find_map(|i| match i {
+        Inline::NewNumber(n) if n.kind == NumberKind::Page => Some(n.number),
+        _ => None,
+    });
+    // Page hiding or odd/even adjust on a page break switches to that page's master
+    let override_master = page::page_override(para).and_then(|page| {
+        ctx.override_masters
+            .iter()
+            .find(|(p, _)| *p == page)
+            .map(|(_, name)| name.clone())
+    });
+    if let Some(master_page) = override_master
+        && ctx.section_start.is_none()
+    {
+        ctx.section_start = Some(SectionStart {
+            master_page,
+            page_number: page_restart.unwrap_or(0),
+        });
+    }
+    if let Some(number) = page_restart.filter(|_| para.page_break)
+        && ctx.section_start.is_none()
+    {
+        ctx.section_start = Some(SectionStart {
+            master_page: ctx.current_master.clone(),
+            page_number: number,
+        });
+    }
+
+    if let Some(start) = ctx.section_start.take() {
+        let (name, page_number) = if start.page_number != 0 {
+            (
+                format!(
+                    "{}P{}N{}",
+                    start.master_page, para.para_shape_id, start.page_number
+                ),
+                format!(r#" style:page-number="{}""#, start.page_number),
+            )
+        } else {
+            (
+                format!("{}P{}", start.master_page, para.para_shape_id),
+                String::new(),
+            )
+        };
+        ctx.add_style(&name, || {
+            format!(
+                r#"
+    <style:style style:name="{}" style:family="paragraph" style:parent-style-name="P{}" style:master-page-name="{}">
+      <style:paragraph-properties{}/>
+    </style:style>"#,
+                name, para.para_shape_id, start.master_page, page_number
+            )
+        });
+        return name;
+    }
+
+    let (prefix, break_type) = if para.page_break {
+        ("PB", "page")
+    } else if para.column_break {
+        ("CB", "column")
+    } else {
+        return format!("P{}", para.para_shape_id);
+    };
+
+    let name = format!("{}{}", prefix, para.para_shape_id);
+    ctx.add_style(&name, || {
+        format!(
+            r#"
+    <style:style style:name="{}" style:family="paragraph" style:parent-style-name="P{}">
+      <style:paragraph-properties fo:break-before="{}"/>
+    </style:style>"#,
+            name, para.para_shape_id, break_type
+        )
+    });
+    name
+}
+
+/// Close the `text:section` opened by a column layout change, if any
+fn close_text_section(xml: &mut String, ctx: &mut ContentContext) {
+    if ctx.in_text_section {
+        xml.push_str("\n      </text:section>");
+        ctx.in_text_section = false;
+    }
+}
+
+fn generate_block_content(
+    xml: &mut String,
+    block: &crate::model::Block,
+    ctx: &mut ContentContext,
+) -> HwpResult<()> {
     use crate::model::Block;
 
     match block {
         Block::Paragraph(para) => {
+            let style_name = paragraph_style_name(para, ctx);
             xml.push_str(&format!(
                 r#"
-      <text:p text:style-name="P{}">"#,
-                para.style_id
+      <text:p text:style-name="{}">"#,
+                style_name
             ));
 
             for inline in &para.inlines {
-                generate_inline_content(xml, inline)?;
+                generate_inline_content(xml, inline, ctx)?;
             }
 
-            xml.push_str("\n      </text:p>");
+            xml.push_str("</text:p>");
         }
-        Block::Table(table) => {
-            xml.push_str(&format!(
-                r#"
-      <table:table table:name="Table{}" table:style-name="Table1">"#,
-                table.id
-            ));
-
-            // Group cells by row
-            let mut rows: HashMap<usize, Vec<_>> = HashMap::new();
-            for cell in &table.cells {
-                rows.entry(cell.row).or_insert_with(Vec::new).push(cell);
+        Block::Table(table) => table::generate_table(xml, table, ctx)?,
+        Block::Shape(shape) => {
+            // A shape on its own is anchored to an empty paragraph
+            xml.push_str("\n      <text:p>");
+            shape::generate_shape(xml, shape, ctx)?;
+            xml.push_str("</text:p>");
+        }
+        Block::Columns(columns) => {
+            // A layout other than the page's goes into its own section;
+            // returning to the page's layout ends it
+            close_text_section(xml, ctx);
+            if ctx.page_columns != Some(columns) {
+                ctx.text_section_count += 1;
+                let name = format!("Sect{}", ctx.text_section_count);
+                ctx.add_style(&name, || {
+                    format!(
+                        r#"
+    <style:style style:name="{}" style:family="section">{}
+    </style:style>"#,
+                        name,
+                        page::section_properties_xml(columns)
+                    )
+                });
+                xml.push_str(&format!(
+                    r#"
+      <text:section text:style-name="{0}" text:name="{0}">"#,
+                    name
+                ));
+                ctx.in_text_section = true;
             }
+        }
+    }
 
-            for row_idx in 0..table.rows {
-                xml.push_str("\n        <table:table-row>\n");
-                if let Some(cells) = rows.get(&row_idx) {
-                    for cell in cells {
-                        xml.push_str(r#"          <table:table-cell table:value-type="string">"#);
-                        for content in &cell.content {
-                            generate_block_content(xml, content)?;
-                        }
-                        xml.push_str("\n          </table:table-cell>\n");
-                    }
-                }
-                xml.push_str("        </table:table-row>");
-            }
+    Ok(())
+}
 
-            xml.push_str("\n      </table:table>");
-        }
-        Block::Shape(_shape) => {
-            // Shape handling - simplified for now
+/// `fo:border-*` and background properties for a border/fill definition
+pub(super) fn border_fill_properties(border_fill: &BorderFill) -> String {
+    let mut xml = String::new();
+    for (side, border) in [
+        ("left", &border_fill.left),
+        ("right", &border_fill.right),
+        ("top", &border_fill.top),
+        ("bottom", &border_fill.bottom),
+    ] {
+        xml.push_str(&format!(r#" fo:border-{}="{}""#, side, border_xml(border)));
+    }
+
+    // Patterns and gradients fall back to their background color
+    match border_fill.fill_type {
+        FillType::Solid | FillType::Pattern | FillType::Gradient => {
+            xml.push_str(&format!(
+                r#" fo:background-color="{}""#,
+                border_fill.background_color.to_hex()
+            ));
         }
+        FillType::None | FillType::Image => {}
     }
+    xml
+}
 
-    Ok(())
+/// A single border as an XSL-FO border value
+fn border_xml(border: &Border) -> String {
+    let style = match border.style {
+        LineStyle::None => return "none".to_string(),
+        LineStyle::Dotted => "dotted",
+        LineStyle::Dashed | LineStyle::DashDot | LineStyle::DashDotDot => "dashed",
+        LineStyle::Double => "double",
+        LineStyle::Solid | LineStyle::Wave => "solid",
+    };
+    format!(
+        "{:.2}mm {} {}",
+        border.width as f64 / 20.0,
+        style,
+        border.color.to_hex()
+    )
 }
 
-fn generate_inline_content(xml: &mut String, inline: &crate::model::Inline) -> HwpResult<()> {
-    use crate::model::Inline;
+fn generate_inline_content(
+    xml: &mut String,
+    inline: &crate::model::Inline,
+    ctx: &mut ContentContext,
+) -> HwpResult<()> {
+    use crate::model::{Control, Inline, TextRun};
 
     match inline {
+        Inline::Text(
+            text_run @ TextRun {
+                change_id: Some(change_id),
+                ..
+            },
+        ) => tracked::generate_changed_run(xml, text_run, *change_id, ctx),
+        Inline::Text(text_run) if ctx.upright_latin => {
+            vertical::generate_upright_run(xml, text_run, ctx)
+        }
         Inline::Text(text_run) => {
+            // No pretty-printing here: whitespace inside text:p is significant
             xml.push_str(&format!(
-                r#"
-        <text:span text:style-name="T{}">{}</text:span>"#,
+                r#"<text:span text:style-name="T{}">{}</text:span>"#,
                 text_run.char_shape_id,
-                escape_xml_for_content(&text_run.text)
+                escape_text_content(&text_run.text)
+            ));
+        }
+        Inline::Tab => xml.push_str("<text:tab/>"),
+        Inline::LineBreak => xml.push_str("<text:line-break/>"),
+        Inline::SoftHyphen => xml.push('\u{00AD}'),
+        Inline::NonBreakingSpace => xml.push('\u{00A0}'),
+        Inline::FixedWidthSpace => xml.push_str("<text:s/>"),
+        Inline::Control(Control::Note(note)) => note::generate_note(xml, note, ctx)?,
+        Inline::FieldRange(range) => field::generate_field_range(xml, range, ctx)?,
+        Inline::AutoNumber(number) => numbering::generate_auto_number(xml, number, ctx),
+        Inline::NewNumber(number) => numbering::apply_new_number(number, ctx),
+        // Applied through the master page of the paragraph's style
+        Inline::PageOverride(_) => {}
+        Inline::Dutmal(dutmal) => ruby::generate_dutmal(xml, dutmal, ctx),
+        Inline::CharOverlap(overlap) => overlap::generate_char_overlap(xml, overlap, ctx),
+        Inline::Bookmark(name) => {
+            xml.push_str(&format!(
+                r#"<text:bookmark text:name="{}"/>"#,
+                escape_xml(name)
             ));
         }
+        Inline::Control(Control::Picture(pic)) => picture::generate_picture(xml, pic, ctx)?,
+        Inline::Control(Control::Shape(shape)) => shape::generate_shape(xml, shape, ctx)?,
+        Inline::Control(Control::Equation(equation)) => {
+            math::generate_equation(xml, equation, ctx)?
+        }
+        Inline::Control(Control::TextBox(text_box)) => {
+            shape::generate_text_box(xml, text_box, ctx)?
+        }
+        Inline::Control(Control::ShapeGroup(group)) => shape::generate_group(xml, group, ctx)?,
+        Inline::Control(Control::OLE(ole)) => ole::generate_ole(xml, ole, ctx)?,
+        Inline::Control(Control::Annotation(annotation)) => {
+            annotation::generate_annotation(xml, annotation, ctx)?
+        }
         Inline::Control(_ctrl) => {
             // Control handling
         }
@@ -183,6 +582,13 @@ fn generate_inline_content(xml: &mut String, inline: &crate::model::Inline) -> H
             crate::model::Field::Time => {
                 xml.push_str(r#"<text:time/>"#);
             }
+            crate::model::Field::Hyperlink(url) => {
+                let url = escape_xml(url);
+                xml.push_str(&format!(
+                    r#"<text:a xlink:type="simple" xlink:href="{0}">{0}</text:a>"#,
+                    url
+                ));
+            }
             _ => {}
         },
     }
@@ -190,7 +596,13 @@ fn generate_inline_content(xml: &mut String, inline: &crate::model::Inline) -> H
     Ok(())
 }
 
-fn generate_styles_xml(doc: &Document) -> HwpResult<String> {
+/// Generate styles.xml. Objects in headers and footers are numbered after
+/// those of content.xml; returns the objects of both.
+fn generate_styles_xml(
+    doc: &Document,
+    page_styles: &PageStyles,
+    objects: Vec<EmbeddedObject>,
+) -> HwpResult<(String, Vec<EmbeddedObject>)> {
     let mut xml = String::new();
     xml.push_str(
         r#"<?xml version="1.0" encoding="UTF-8"?>
@@ -199,8 +611,11 @@ fn generate_styles_xml(doc: &Document) -> HwpResult<String> {
   xmlns:style="urn:oasis:names:tc:opendocument:xmlns:style:1.0"
   xmlns:text="urn:oasis:names:tc:opendocument:xmlns:text:1.0"
   xmlns:table="urn:oasis:names:tc:opendocument:xmlns:table:1.0"
+  xmlns:draw="urn:oasis:names:tc:opendocument:xmlns:drawing:1.0"
   xmlns:fo="urn:oasis:names:tc:opendocument:xmlns:xsl-fo-compatible:1.0"
+  xmlns:xlink="http://www.w3.org/1999/xlink"
   xmlns:svg="urn:oasis:names:tc:opendocument:xmlns:svg-compatible:1.0"
+  xmlns:dc="http://purl.org/dc/elements/1.1/"
   office:version="1.2">
   <office:font-face-decls>"#,
     );
@@ -230,16 +645,9 @@ fn generate_styles_xml(doc: &Document) -> HwpResult<String> {
         xml.push_str(&format!(
             r#"
     <style:style style:name="T{}" style:family="text">
-      <style:text-properties style:font-name="F{}" fo:font-size="{}pt" fo:color="{}"{}/>"#,
+      <style:text-properties{}/>"#,
             char_shape.id,
-            char_shape.font_id,
-            char_shape.font_size.to_pt(),
-            char_shape.color.to_hex(),
-            if char_shape.bold {
-                " fo:font-weight=\"bold\""
-            } else {
-                ""
-            }
+            char_style::text_properties(doc, char_shape)
         ));
         xml.push_str("\n    </style:style>");
     }
@@ -258,21 +666,36 @@ fn generate_styles_xml(doc: &Document) -> HwpResult<String> {
         xml.push_str("\n    </style:style>");
     }
 
+    xml.push_str(&note::notes_configuration_xml(doc));
+    xml.push_str(&shape::shape_styles_xml());
+
+    // Header and footer content collects its own automatic styles
+    let mut ctx = ContentContext::new(doc);
+    ctx.objects = objects;
+    let master_pages = page_styles.master_pages_xml(doc, &mut ctx)?;
+
     xml.push_str(
         r#"
   </office:styles>
-  <office:automatic-styles>
-    <style:style style:name="Table1" style:family="table">
-      <style:table-properties table:border-model="collapsing"/>
-    </style:style>
+  <office:automatic-styles>"#,
+    );
+    xml.push_str(&page_styles.page_layouts_xml());
+    for style in ctx.auto_styles.values() {
+        xml.push_str(style);
+    }
+    xml.push_str(
+        r#"
   </office:automatic-styles>
-  <office:master-styles>
-    <style:master-page style:name="Standard" style:page-layout-name="pm1"/>
+  <office:master-styles>"#,
+    );
+    xml.push_str(&master_pages);
+    xml.push_str(
+        r#"
   </office:master-styles>
 </office:document-styles>"#,
     );
 
-    Ok(xml)
+    Ok((xml, ctx.objects))
 }
 
 fn generate_settings_xml() -> String {
@@ -299,9 +722,43 @@ fn generate_meta_xml() -> String {
         .to_string()
 }
 
-fn escape_xml_for_content(text: &str) -> String {
+/// Escape XML special characters in attribute values and field text
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Escape run text, encoding spaces that ODF would otherwise collapse
+/// (leading spaces and consecutive spaces) as `text:s`
+fn escape_text_content(text: &str) -> String {
+    fn push_spaces(result: &mut String, count: usize) {
+        match count {
+            0 => {}
+            1 => result.push_str("<text:s/>"),
+            n => result.push_str(&format!(r#"<text:s text:c="{}"/>"#, n)),
+        }
+    }
+
     let mut result = String::with_capacity(text.len());
+    let mut spaces = 0;
+    let mut at_start = true;
     for c in text.chars() {
+        if c == ' ' {
+            spaces += 1;
+            continue;
+        }
+        if spaces > 0 {
+            if at_start {
+                push_spaces(&mut result, spaces);
+            } else {
+                result.push(' ');
+                push_spaces(&mut result, spaces - 1);
+            }
+            spaces = 0;
+        }
+        at_start = false;
         match c {
             '&' => result.push_str("&amp;"),
             '<' => result.push_str("&lt;"),
@@ -309,5 +766,13 @@ fn escape_xml_for_content(text: &str) -> String {
             _ => result.push(c),
         }
     }
+    if spaces > 0 {
+        if at_start {
+            push_spaces(&mut result, spaces);
+        } else {
+            result.push(' ');
+            push_spaces(&mut result, spaces - 1);
+        }
+    }
     result
 }
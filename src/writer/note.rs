@@ -0,0 +1,196 @@
+use super::{ContentContext, escape_xml, generate_block_content};
+use crate::common::{HwpResult, LineStyle};
+use crate::model::{Document, Note, NoteKind, NoteNumbering, NoteShape, NumberFormat, Section};
+
+/// Take over the note shapes of a section, restarting numbering if asked
+pub(super) fn start_section(section: &Section, ctx: &mut ContentContext) {
+    ctx.footnote_shape = section.footnote_shape.clone();
+    ctx.endnote_shape = section.endnote_shape.clone();
+    if section.footnote_shape.numbering == NoteNumbering::RestartSection {
+        ctx.footnote_count = 0;
+    }
+    if section.endnote_shape.numbering == NoteNumbering::RestartSection {
+        ctx.endnote_count = 0;
+    }
+}
+
+/// Generate a `text:note` with its citation and body.
+/// No whitespace is added since the note sits inside a paragraph.
+pub(super) fn generate_note(
+    xml: &mut String,
+    note: &Note,
+    ctx: &mut ContentContext,
+) -> HwpResult<()> {
+    let (class, id_prefix, count, shape) = match note.kind {
+        NoteKind::Footnote => {
+            ctx.footnote_count += 1;
+            ("footnote", "ftn", ctx.footnote_count, &ctx.footnote_shape)
+        }
+        NoteKind::Endnote => {
+            ctx.endnote_count += 1;
+            ("endnote", "edn", ctx.endnote_count, &ctx.endnote_shape)
+        }
+    };
+
+    // Symbols have no ODF numbering format and are written as labels
+    let number = shape.start_number.max(1) as u32 + count - 1;
+    let citation = match (shape.number_format, shape.user_symbol) {
+        (NumberFormat::UserChar, Some(symbol)) => {
+            let label = escape_xml(&symbol.to_string());
+            format!(
+                r#"<text:note-citation text:label="{0}">{0}</text:note-citation>"#,
+                label
+            )
+        }
+        (NumberFormat::Symbol, _) => {
+            let label = shape.number_format.format(number);
+            format!(
+                r#"<text:note-citation text:label="{0}">{0}</text:note-citation>"#,
+                label
+            )
+        }
+        (format, _) => format!(
+            "<text:note-citation>{}</text:note-citation>",
+            format.format(number)
+        ),
+    };
+
+    // Counts restart with sections and number changes; IDs must not
+    ctx.note_id_count += 1;
+    xml.push_str(&format!(
+        r#"<text:note text:id="{}{}" text:note-class="{}">{}<text:note-body>"#,
+        id_prefix, ctx.note_id_count, class, citation
+    ));
+    for block in &note.blocks {
+        generate_block_content(xml, block, ctx)?;
+    }
+    xml.push_str("</text:note-body></text:note>");
+    Ok(())
+}
+
+/// `text:notes-configuration` for footnotes and endnotes.
+/// ODF has one configuration per document, taken from the first section.
+pub(super) fn notes_configuration_xml(doc: &Document) -> String {
+    let default = Section::new();
+    let section = doc.sections.first().unwrap_or(&default);
+
+    let footnote = &section.footnote_shape;
+    let endnote = &section.endnote_shape;
+    format!(
+        r#"
+    <text:notes-configuration text:note-class="footnote"{} text:footnotes-position="page" text:start-numbering-at="{}"/>
+    <text:notes-configuration text:note-class="endnote"{} text:start-numbering-at="{}"/>"#,
+        numbering_attrs(footnote),
+        start_numbering_at(footnote.numbering),
+        numbering_attrs(endnote),
+        start_numbering_at(endnote.numbering)
+    )
+}
+
+/// Number format, prefix/suffix and start value (ODF counts from 0)
+fn numbering_attrs(shape: &NoteShape) -> String {
+    let mut xml = format!(
+        r#" style:num-format="{}" text:start-value="{}""#,
+        shape.number_format.to_odt_str(),
+        shape.start_number.saturating_sub(1)
+    );
+    if let Some(prefix) = shape.prefix {
+        xml.push_str(&format!(
+            r#" style:num-prefix="{}""#,
+            escape_xml(&prefix.to_string())
+        ));
+    }
+    if let Some(suffix) = shape.suffix {
+        xml.push_str(&format!(
+            r#" style:num-suffix="{}""#,
+            escape_xml(&suffix.to_string())
+        ));
+    }
+    xml
+}
+
+/// ODF has no per-section restart; "chapter" restarts at outline chapters
+/// instead. Citations carry their own text, so the numbers shown still
+/// restart with each section.
+fn start_numbering_at(numbering: NoteNumbering) -> &'static str {
+    match numbering {
+        NoteNumbering::Continue => "document",
+        NoteNumbering::RestartSection => "chapter",
+        NoteNumbering::RestartPage => "page",
+    }
+}
+
+/// `style:footnote-sep` for a section's page layout
+pub(super) fn footnote_sep_xml(section: &Section) -> String {
+    let shape = &section.footnote_shape;
+    let line = &shape.separator_line;
+
+    // Negative lengths select the default separator of a third of the body
+    let body_width = section.layout_width().0
+        - section.margin_left.0
+        - section.margin_right.0
+        - section.margin_gutter.0;
+    let rel_width = if shape.separator_length.0 > 0 && body_width > 0 {
+        (shape.separator_length.0 as i64 * 100 / body_width as i64).clamp(1, 100)
+    } else {
+        33
+    };
+
+    format!(
+        r#"
+        <style:footnote-sep style:width="{:.2}mm" style:distance-before-sep="{:.2}mm" style:distance-after-sep="{:.2}mm" style:line-style="{}" style:adjustment="left" style:rel-width="{}%" style:color="{}"/>
+      "#,
+        if line.style == LineStyle::None {
+            0.0
+        } else {
+            line.width as f64 / 20.0
+        },
+        shape.separator_margin_top.to_mm(),
+        shape.separator_margin_bottom.to_mm(),
+        match line.style {
+            LineStyle::None => "none",
+            LineStyle::Dotted => "dotted",
+            LineStyle::Dashed | LineStyle::DashDot | LineStyle::DashDotDot => "dash",
+            _ => "solid",
+        },
+        rel_width,
+        line.color.to_hex()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_note_citation_follows_shape() {
+        let doc = Document::new();
+        let mut section = Section::new();
+        section.footnote_shape.number_format = NumberFormat::RomanLower;
+        section.footnote_shape.start_number = 3;
+
+        let mut ctx = ContentContext::new(&doc);
+        start_section(&section, &mut ctx);
+        let note = Note {
+            id: 1,
+            kind: NoteKind::Footnote,
+            number: 0,
+            blocks: Vec::new(),
+        };
+        let mut xml = String::new();
+        generate_note(&mut xml, &note, &mut ctx).unwrap();
+        generate_note(&mut xml, &note, &mut ctx).unwrap();
+
+        assert!(xml.contains(r#"text:id="ftn2""#));
+        assert!(xml.contains("<text:note-citation>iii</text:note-citation>"));
+        assert!(xml.contains("<text:note-citation>iv</text:note-citation>"));
+
+        // A restarted section repeats citations but not IDs
+        section.footnote_shape.numbering = NoteNumbering::RestartSection;
+        start_section(&section, &mut ctx);
+        xml.clear();
+        generate_note(&mut xml, &note, &mut ctx).unwrap();
+        assert!(xml.contains(r#"text:id="ftn3""#));
+        assert!(xml.contains("<text:note-citation>iii</text:note-citation>"));
+    }
+}
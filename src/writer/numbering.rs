@@ -0,0 +1,138 @@
+use super::{ContentContext, escape_xml};
+use crate::model::{AutoNumber, NewNumber, NumberFormat, NumberKind, Section};
+
+/// Caption numbers become ODF sequences with these names
+const SEQUENCES: [(NumberKind, &str); 3] = [
+    (NumberKind::Figure, "Figure"),
+    (NumberKind::Table, "Table"),
+    (NumberKind::Equation, "Equation"),
+];
+
+fn sequence_name(kind: NumberKind) -> Option<&'static str> {
+    SEQUENCES
+        .iter()
+        .find(|(k, _)| *k == kind)
+        .map(|(_, name)| *name)
+}
+
+/// `text:sequence-decls` at the start of the body text
+pub(super) fn sequence_decls_xml() -> String {
+    let mut xml = String::from("\n      <text:sequence-decls>");
+    for (_, name) in SEQUENCES {
+        xml.push_str(&format!(
+            r#"
+        <text:sequence-decl text:display-outline-level="0" text:name="{}"/>"#,
+            name
+        ));
+    }
+    xml.push_str("\n      </text:sequence-decls>");
+    xml
+}
+
+/// Apply a section's starting numbers to the caption sequences
+pub(super) fn start_section(section: &Section, ctx: &mut ContentContext) {
+    let starts = &section.start_numbers;
+    for (kind, start) in [
+        (NumberKind::Figure, starts.figure),
+        (NumberKind::Table, starts.table),
+        (NumberKind::Equation, starts.equation),
+    ] {
+        if start != 0 {
+            ctx.next_numbers.insert(kind, start as u32);
+        }
+    }
+}
+
+/// Restart numbering from a new number control. Page numbers are restarted
+/// by the paragraph style instead.
+pub(super) fn apply_new_number(new_number: &NewNumber, ctx: &mut ContentContext) {
+    let number = new_number.number as u32;
+    match new_number.kind {
+        NumberKind::Page => {}
+        NumberKind::Footnote => {
+            let start = ctx.footnote_shape.start_number.max(1) as u32;
+            ctx.footnote_count = number.saturating_sub(start);
+        }
+        NumberKind::Endnote => {
+            let start = ctx.endnote_shape.start_number.max(1) as u32;
+            ctx.endnote_count = number.saturating_sub(start);
+        }
+        kind => {
+            ctx.next_numbers.insert(kind, number);
+        }
+    }
+}
+
+/// Generate an automatic number with its decorations
+pub(super) fn generate_auto_number(
+    xml: &mut String,
+    number: &AutoNumber,
+    ctx: &mut ContentContext,
+) {
+    let text = |n: u32| escape_xml(&number_text(number.format, number.user_symbol, n));
+
+    let value = match number.kind {
+        NumberKind::Page => format!(
+            r#"<text:page-number style:num-format="{}" text:select-page="current">{}</text:page-number>"#,
+            number.format.to_odt_str(),
+            text(number.number.max(1) as u32)
+        ),
+        // Inside a note the number is the note's own number
+        NumberKind::Footnote => {
+            text(ctx.footnote_shape.start_number.max(1) as u32 + ctx.footnote_count.max(1) - 1)
+        }
+        NumberKind::Endnote => {
+            text(ctx.endnote_shape.start_number.max(1) as u32 + ctx.endnote_count.max(1) - 1)
+        }
+        kind => {
+            let name = sequence_name(kind).unwrap_or("Figure");
+            let (value, formula) = match ctx.next_numbers.remove(&kind) {
+                Some(value) => (value, format!("ooow:{}", value)),
+                None => {
+                    let value = ctx.numbers.get(&kind).copied().unwrap_or(0) + 1;
+                    (value, format!("ooow:{}+1", name))
+                }
+            };
+            ctx.numbers.insert(kind, value);
+            ctx.sequence_ref_count += 1;
+            format!(
+                r#"<text:sequence text:ref-name="ref{0}{1}" text:name="{0}" text:formula="{2}" style:num-format="{3}">{4}</text:sequence>"#,
+                name,
+                ctx.sequence_ref_count,
+                formula,
+                number.format.to_odt_str(),
+                text(value)
+            )
+        }
+    };
+
+    let decorate = |c: Option<char>| c.map(|c| escape_xml(&c.to_string())).unwrap_or_default();
+    let content = format!(
+        "{}{}{}",
+        decorate(number.prefix),
+        value,
+        decorate(number.suffix)
+    );
+
+    if number.superscript {
+        let style = ctx.automatic_style(
+            "text",
+            "AN",
+            r#"<style:text-properties style:text-position="super 58%"/>"#,
+        );
+        xml.push_str(&format!(
+            r#"<text:span text:style-name="{}">{}</text:span>"#,
+            style, content
+        ));
+    } else {
+        xml.push_str(&content);
+    }
+}
+
+/// Number text in the given format; user symbols repeat the symbol
+pub(super) fn number_text(format: NumberFormat, user_symbol: Option<char>, n: u32) -> String {
+    match (format, user_symbol) {
+        (NumberFormat::UserChar, Some(symbol)) => symbol.to_string(),
+        (format, _) => format.format(n),
+    }
+}
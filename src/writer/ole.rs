@@ -0,0 +1,146 @@
+use super::chart::chart_xml;
+use super::picture::media_type;
+use super::{ContentContext, border_xml, escape_xml, frame};
+use crate::common::{HwpResult, LineStyle};
+use crate::model::{BinData, Document, OLE};
+
+/// Media type LibreOffice gives embedded OLE compound files
+const OLE_MEDIA_TYPE: &str = "application/vnd.sun.star.oleobject";
+
+fn object_path(bin_data: &BinData) -> String {
+    format!("Object BIN{:04X}", bin_data.id)
+}
+
+fn replacement_path(bin_data: &BinData) -> String {
+    format!("ObjectReplacements/Object BIN{:04X}", bin_data.id)
+}
+
+/// Whether the item holds a compound file, i.e. its storage could be opened
+fn has_storage(bin_data: &BinData) -> bool {
+    bin_data.data.starts_with(&[0xD0, 0xCF, 0x11, 0xE0])
+}
+
+/// Embedded OLE objects and their replacement images stored in the
+/// package: path, media type and contents
+pub(super) fn package_ole_objects(doc: &Document) -> Vec<(String, &'static str, &[u8])> {
+    let mut files = Vec::new();
+    for bin_data in doc.bin_data.iter().filter(|b| b.is_ole()) {
+        // Charts are written as chart documents instead
+        if has_storage(bin_data) && bin_data.chart.is_none() {
+            files.push((
+                object_path(bin_data),
+                OLE_MEDIA_TYPE,
+                bin_data.data.as_slice(),
+            ));
+        }
+        if let Some(preview) = &bin_data.preview
+            && let Some(media_type) = media_type(&preview.extension)
+        {
+            files.push((
+                replacement_path(bin_data),
+                media_type,
+                preview.data.as_slice(),
+            ));
+        }
+    }
+    files
+}
+
+/// Generate an OLE object as a frame holding the object and the picture
+/// shown in its place. Without its storage only the picture is kept, and
+/// charts only in HWP's chart format stay OLE objects with their picture.
+pub(super) fn generate_ole(xml: &mut String, ole: &OLE, ctx: &mut ContentContext) -> HwpResult<()> {
+    write_ole(xml, ole, "", ctx)
+}
+
+/// Write an OLE object's frame; `transform` is an optional extra
+/// draw:transform attribute
+pub(super) fn write_ole(
+    xml: &mut String,
+    ole: &OLE,
+    transform: &str,
+    ctx: &mut ContentContext,
+) -> HwpResult<()> {
+    let Some(bin_data) = ctx.doc.bin_data(ole.bindata_id) else {
+        return Ok(());
+    };
+    let mut content = String::new();
+    if let Some(chart) = &bin_data.chart {
+        // Charts become editable chart objects instead
+        let name = format!("Object {}", ctx.objects.len() + 1);
+        ctx.objects.push((
+            name.clone(),
+            "application/vnd.oasis.opendocument.chart",
+            chart_xml(chart, ole.placement.width, ole.placement.height),
+        ));
+        content.push_str(&format!(
+            r#"<draw:object xlink:href="./{}" xlink:type="simple" xlink:show="embed" xlink:actuate="onLoad"/>"#,
+            name
+        ));
+    } else if has_storage(bin_data) {
+        content.push_str(&format!(
+            r#"<draw:object-ole xlink:href="./{}" xlink:type="simple" xlink:show="embed" xlink:actuate="onLoad"/>"#,
+            escape_xml(&object_path(bin_data))
+        ));
+    }
+    if bin_data
+        .preview
+        .as_ref()
+        .is_some_and(|preview| media_type(&preview.extension).is_some())
+    {
+        content.push_str(&format!(
+            r#"<draw:image xlink:href="./{}" xlink:type="simple" xlink:show="embed" xlink:actuate="onLoad"/>"#,
+            escape_xml(&replacement_path(bin_data))
+        ));
+    }
+    if content.is_empty() {
+        return Ok(());
+    }
+    ctx.ole_count += 1;
+
+    let mut properties = String::new();
+    if ole.border.style != LineStyle::None && ole.border.width > 0 {
+        properties.push_str(&format!(r#" fo:border="{}""#, border_xml(&ole.border)));
+    }
+    frame::generate_frame(
+        xml,
+        &ole.placement,
+        &format!("OLE{}", ctx.ole_count),
+        transform,
+        &properties,
+        &content,
+        ctx,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{BinDataKind, OlePreview};
+
+    #[test]
+    fn test_package_storage_object() {
+        let mut doc = Document::new();
+        doc.bin_data.push(BinData {
+            id: 1,
+            kind: BinDataKind::Storage,
+            extension: "ole".to_string(),
+            link_path: String::new(),
+            compressed: None,
+            data: vec![0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1],
+            preview: Some(OlePreview {
+                extension: "emf".to_string(),
+                data: vec![1, 0, 0, 0],
+            }),
+            chart: None,
+        });
+
+        let files = package_ole_objects(&doc);
+        let paths: Vec<&str> = files.iter().map(|(path, _, _)| path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec!["Object BIN0001", "ObjectReplacements/Object BIN0001"]
+        );
+        assert_eq!(files[0].1, OLE_MEDIA_TYPE);
+    }
+}
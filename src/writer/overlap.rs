@@ -0,0 +1,101 @@
+use super::{ContentContext, escape_text_content};
+use crate::model::{CharOverlap, OverlapBorder};
+
+/// Generate overlapping characters. Where Unicode has the enclosed
+/// character (circled digits and letters, ...) it replaces the overlap;
+/// otherwise the characters are combined into the width of one.
+pub(super) fn generate_char_overlap(
+    xml: &mut String,
+    overlap: &CharOverlap,
+    ctx: &mut ContentContext,
+) {
+    if let Some(c) = enclosed_char(&overlap.chars, overlap.border) {
+        xml.push_str(&format!(
+            r#"<text:span text:style-name="T{}">{}</text:span>"#,
+            overlap.char_shape_id, c
+        ));
+        return;
+    }
+    if overlap.chars.chars().count() < 2 {
+        xml.push_str(&format!(
+            r#"<text:span text:style-name="T{}">{}</text:span>"#,
+            overlap.char_shape_id,
+            escape_text_content(&overlap.chars)
+        ));
+        return;
+    }
+
+    let name = format!("T{}C", overlap.char_shape_id);
+    ctx.add_style(&name, || {
+        format!(
+            r#"
+    <style:style style:name="{}" style:family="text" style:parent-style-name="T{}">
+      <style:text-properties style:text-combine="letters"/>
+    </style:style>"#,
+            name, overlap.char_shape_id
+        )
+    });
+    xml.push_str(&format!(
+        r#"<text:span text:style-name="{}">{}</text:span>"#,
+        name,
+        escape_text_content(&overlap.chars)
+    ));
+}
+
+/// Unicode enclosed character for characters in a border, if there is one
+fn enclosed_char(chars: &str, border: OverlapBorder) -> Option<char> {
+    const CONSONANTS: &str = "ㄱㄴㄷㄹㅁㅂㅅㅇㅈㅊㅋㅌㅍㅎ";
+    const SYLLABLES: &str = "가나다라마바사아자차카타파하";
+
+    let text = chars.trim();
+    let number = Some(text)
+        .filter(|t| !t.is_empty() && t.len() <= 2 && t.bytes().all(|b| b.is_ascii_digit()))
+        .and_then(|t| t.parse::<u32>().ok());
+    let mut iter = text.chars();
+    let single = iter.next().filter(|_| iter.next().is_none());
+    let from = |first: u32, base: char| single.map(|c| first + c as u32 - base as u32);
+
+    let code = match (border, number, single) {
+        (OverlapBorder::Circle, Some(0), _) => 0x24EA,
+        (OverlapBorder::Circle, Some(n @ 1..=20), _) => 0x2460 + n - 1,
+        (OverlapBorder::Circle, Some(n @ 21..=35), _) => 0x3251 + n - 21,
+        (OverlapBorder::Circle, Some(n @ 36..=50), _) => 0x32B1 + n - 36,
+        (OverlapBorder::Circle, None, Some('A'..='Z')) => from(0x24B6, 'A')?,
+        (OverlapBorder::Circle, None, Some('a'..='z')) => from(0x24D0, 'a')?,
+        (OverlapBorder::Circle, None, Some(c)) => {
+            if let Some(i) = CONSONANTS.chars().position(|k| k == c) {
+                0x3260 + i as u32
+            } else {
+                0x326E + SYLLABLES.chars().position(|k| k == c)? as u32
+            }
+        }
+        (OverlapBorder::InvertedCircle, Some(0), _) => 0x24FF,
+        (OverlapBorder::InvertedCircle, Some(n @ 1..=10), _) => 0x2776 + n - 1,
+        (OverlapBorder::InvertedCircle, Some(n @ 11..=20), _) => 0x24EB + n - 11,
+        (OverlapBorder::InvertedCircle, None, Some('A'..='Z')) => from(0x1F150, 'A')?,
+        (OverlapBorder::Rectangle, None, Some('A'..='Z')) => from(0x1F130, 'A')?,
+        (OverlapBorder::InvertedRectangle, None, Some('A'..='Z')) => from(0x1F170, 'A')?,
+        _ => return None,
+    };
+    char::from_u32(code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_enclosed_char() {
+        assert_eq!(enclosed_char("3", OverlapBorder::Circle), Some('③'));
+        assert_eq!(enclosed_char("21", OverlapBorder::Circle), Some('㉑'));
+        assert_eq!(enclosed_char("가", OverlapBorder::Circle), Some('㉮'));
+        assert_eq!(enclosed_char("ㄴ", OverlapBorder::Circle), Some('㉡'));
+        assert_eq!(
+            enclosed_char("12", OverlapBorder::InvertedCircle),
+            Some('⓬')
+        );
+        assert_eq!(enclosed_char("B", OverlapBorder::Rectangle), Some('🄱'));
+        assert_eq!(enclosed_char("각", OverlapBorder::Circle), None);
+        assert_eq!(enclosed_char("5", OverlapBorder::Triangle), None);
+    }
+}
@@ -0,0 +1,783 @@
+use super::vertical::writing_mode;
+use super::{
+    ContentContext, border_fill_properties, escape_xml, generate_block_content, numbering,
+};
+use crate::common::{HwpResult, HwpUnit, LineStyle};
+use crate::model::{
+    AutoNumber, BindingType, Block, ColumnDef, ColumnType, Document, HeaderFooter, Inline,
+    NumberKind, PageApply, PageBorderFill, PageNumberPlace, PageNumberPosition, PageOverride,
+    Paragraph, Section,
+};
+
+/// Page layout of a section as written to styles.xml
+#[derive(Debug, Clone, PartialEq)]
+struct PageLayout {
+    page_usage: Option<&'static str>,
+    properties: String,
+    elements: String, // Column and footnote separator elements
+    header_style: String,
+    footer_style: String,
+}
+
+/// Master page using a page layout, with the headers and footers of a section
+struct MasterPage {
+    layout: usize,
+    section: Option<usize>,
+    one_page: Option<PageOverride>, // One-page variant: the section's first page or a later override
+    next: Option<usize>,            // Master page used after this one
+}
+
+/// Page layouts and master pages for all sections.
+/// Sections with identical page setup and no headers or footers share one
+/// master page; sections with headers, footers or page numbers get their own.
+/// A section whose first page differs (hidden header, odd/even start) starts
+/// with a one-page master that hands over to the section's master. Page
+/// hiding and odd/even adjust controls on a later page get the same kind of
+/// master, applied by the paragraph that starts that page.
+pub(super) struct PageStyles {
+    layouts: Vec<PageLayout>,
+    masters: Vec<MasterPage>,
+    section_masters: Vec<(usize, usize)>, // First page and body master pages
+    override_masters: Vec<Vec<(PageOverride, usize)>>, // Later pages of each section
+}
+
+impl PageStyles {
+    pub(super) fn collect(doc: &Document) -> Self {
+        let mut styles = PageStyles {
+            layouts: Vec::new(),
+            masters: Vec::new(),
+            section_masters: Vec::with_capacity(doc.sections.len()),
+            override_masters: Vec::with_capacity(doc.sections.len()),
+        };
+
+        for (section_idx, section) in doc.sections.iter().enumerate() {
+            let layout = styles.add_layout(page_layout(doc, section, None));
+            let shared = !has_page_areas(section);
+            let existing = styles
+                .masters
+                .iter()
+                .position(|m| shared && m.section.is_none() && m.layout == layout);
+            let body = match existing {
+                Some(idx) => idx,
+                None => styles.add_master(MasterPage {
+                    layout,
+                    section: (!shared).then_some(section_idx),
+                    one_page: None,
+                    next: None,
+                }),
+            };
+
+            let first_page = section_first_page(section);
+            let first = if changes_page(section, &first_page) {
+                styles.add_one_page_master(doc, section_idx, section, first_page, body)
+            } else {
+                body
+            };
+            styles.section_masters.push((first, body));
+
+            let mut overrides: Vec<(PageOverride, usize)> = Vec::new();
+            for block in &section.blocks {
+                let Block::Paragraph(para) = block else {
+                    continue;
+                };
+                let Some(page) = page_override(para) else {
+                    continue;
+                };
+                if changes_page(section, &page) && !overrides.iter().any(|(p, _)| *p == page) {
+                    let master = styles.add_one_page_master(doc, section_idx, section, page, body);
+                    overrides.push((page, master));
+                }
+            }
+            styles.override_masters.push(overrides);
+        }
+
+        // Documents without sections still need a default page
+        if styles.masters.is_empty() {
+            let layout = styles.add_layout(page_layout(doc, &Section::new(), None));
+            styles.add_master(MasterPage {
+                layout,
+                section: None,
+                one_page: None,
+                next: None,
+            });
+        }
+
+        styles
+    }
+
+    fn add_one_page_master(
+        &mut self,
+        doc: &Document,
+        section_idx: usize,
+        section: &Section,
+        page: PageOverride,
+        next: usize,
+    ) -> usize {
+        let layout = self.add_layout(page_layout(doc, section, Some(&page)));
+        self.add_master(MasterPage {
+            layout,
+            section: Some(section_idx),
+            one_page: Some(page),
+            next: Some(next),
+        })
+    }
+
+    fn add_layout(&mut self, layout: PageLayout) -> usize {
+        match self.layouts.iter().position(|l| *l == layout) {
+            Some(idx) => idx,
+            None => {
+                self.layouts.push(layout);
+                self.layouts.len() - 1
+            }
+        }
+    }
+
+    fn add_master(&mut self, master: MasterPage) -> usize {
+        self.masters.push(master);
+        self.masters.len() - 1
+    }
+
+    /// Master page starting a section
+    pub(super) fn master_name(&self, section_idx: usize) -> String {
+        master_page_name(self.section_masters.get(section_idx).map_or(0, |m| m.0))
+    }
+
+    /// Master page of a section's pages after the first
+    pub(super) fn body_master_name(&self, section_idx: usize) -> String {
+        master_page_name(self.section_masters.get(section_idx).map_or(0, |m| m.1))
+    }
+
+    /// One-page masters for page overrides later in a section
+    pub(super) fn override_master_names(&self, section_idx: usize) -> Vec<(PageOverride, String)> {
+        self.override_masters
+            .get(section_idx)
+            .into_iter()
+            .flatten()
+            .map(|&(page, idx)| (page, master_page_name(idx)))
+            .collect()
+    }
+
+    /// Page layouts for the automatic styles of styles.xml
+    pub(super) fn page_layouts_xml(&self) -> String {
+        let mut xml = String::new();
+        for (idx, layout) in self.layouts.iter().enumerate() {
+            xml.push_str(&format!(
+                r#"
+    <style:page-layout style:name="pm{}"{}>
+      <style:page-layout-properties{}>{}</style:page-layout-properties>{}{}
+    </style:page-layout>"#,
+                idx + 1,
+                layout
+                    .page_usage
+                    .map(|usage| format!(r#" style:page-usage="{}""#, usage))
+                    .unwrap_or_default(),
+                layout.properties,
+                layout.elements,
+                layout.header_style,
+                layout.footer_style
+            ));
+        }
+        xml
+    }
+
+    /// Master pages for the master styles of styles.xml.
+    /// Header and footer content uses the automatic styles of `ctx`.
+    pub(super) fn master_pages_xml(
+        &self,
+        doc: &Document,
+        ctx: &mut ContentContext,
+    ) -> HwpResult<String> {
+        let mut xml = String::new();
+        for (idx, master) in self.masters.iter().enumerate() {
+            let next = master
+                .next
+                .map(|next| format!(r#" style:next-style-name="{}""#, master_page_name(next)))
+                .unwrap_or_default();
+            let section = master.section.and_then(|i| doc.sections.get(i));
+            let Some(section) = section else {
+                xml.push_str(&format!(
+                    r#"
+    <style:master-page style:name="{}" style:page-layout-name="pm{}"{}/>"#,
+                    master_page_name(idx),
+                    master.layout + 1,
+                    next
+                ));
+                continue;
+            };
+
+            xml.push_str(&format!(
+                r#"
+    <style:master-page style:name="{}" style:page-layout-name="pm{}"{}>"#,
+                master_page_name(idx),
+                master.layout + 1,
+                next
+            ));
+            for footer in [false, true] {
+                let area = PageArea::new(section, footer, master.one_page.as_ref());
+                area.write(&mut xml, if footer { "footer" } else { "header" }, ctx)?;
+            }
+            xml.push_str("\n    </style:master-page>");
+        }
+        Ok(xml)
+    }
+}
+
+/// Whether a section needs its own master page for headers, footers or
+/// page numbers
+fn has_page_areas(section: &Section) -> bool {
+    !section.headers.is_empty() || !section.footers.is_empty() || page_number(section).is_some()
+}
+
+/// Page hiding and odd/even adjust of a section's first page
+fn section_first_page(section: &Section) -> PageOverride {
+    PageOverride {
+        hide_header: section.hide_first_header,
+        hide_footer: section.hide_first_footer,
+        hide_page_number: section.hide_first_page_number,
+        parity: section.first_page_parity,
+    }
+}
+
+/// Page hiding and odd/even adjust controls of a paragraph starting a page.
+/// ODF can only switch master pages where a page starts, so controls in
+/// other paragraphs are dropped.
+pub(super) fn page_override(para: &Paragraph) -> Option<PageOverride> {
+    if !para.page_break {
+        return None;
+    }
+    para.inlines.iter().fold(None, |page, inline| match inline {
+        Inline::PageOverride(other) => {
+            let mut page = page.unwrap_or_default();
+            page.merge(other);
+            Some(page)
+        }
+        _ => page,
+    })
+}
+
+/// Whether a page override changes anything the section's pages show
+fn changes_page(section: &Section, page: &PageOverride) -> bool {
+    page.parity != PageApply::Both
+        || (page.hide_header && !section.headers.is_empty())
+        || (page.hide_footer && !section.footers.is_empty())
+        || (page.hide_page_number && page_number(section).is_some())
+}
+
+/// Page number shown by the section's page number position, if any
+fn page_number(section: &Section) -> Option<&PageNumberPosition> {
+    section
+        .page_number_position
+        .as_ref()
+        .filter(|p| p.place != PageNumberPlace::None)
+}
+
+/// Header or footer applying to the given pages; later definitions win
+fn select_header_footer(list: &[HeaderFooter], pages: PageApply) -> Option<&HeaderFooter> {
+    list.iter().rev().find(|hf| hf.apply_to == pages)
+}
+
+/// Content of the header or footer area of a master page
+struct PageArea<'a> {
+    odd: Option<&'a HeaderFooter>,
+    even: Option<&'a HeaderFooter>,
+    page_number: Option<&'a PageNumberPosition>,
+}
+
+impl<'a> PageArea<'a> {
+    /// Area of a section's pages, or of a one-page master with `one_page`
+    fn new(section: &'a Section, footer: bool, one_page: Option<&PageOverride>) -> Self {
+        let (list, hide) = match one_page {
+            Some(page) if footer => (&section.footers, page.hide_footer),
+            Some(page) => (&section.headers, page.hide_header),
+            None if footer => (&section.footers, false),
+            None => (&section.headers, false),
+        };
+        let page_number = page_number(section)
+            .filter(|p| p.place.is_bottom() == footer)
+            .filter(|_| !one_page.is_some_and(|page| page.hide_page_number));
+
+        let both = select_header_footer(list, PageApply::Both);
+        let mut odd = select_header_footer(list, PageApply::Odd).or(both);
+        let mut even = select_header_footer(list, PageApply::Even).or(both);
+        if let Some(page) = one_page {
+            // The page shows only the variant for its own side
+            let own = match page.parity {
+                PageApply::Even => even,
+                _ => odd,
+            };
+            (odd, even) = if hide { (None, None) } else { (own, own) };
+        }
+
+        PageArea {
+            odd,
+            even,
+            page_number,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.odd.is_none() && self.even.is_none() && self.page_number.is_none()
+    }
+
+    /// Odd and even pages differ for one-sided content and for page
+    /// numbers placed on the outside or inside edge
+    fn differs(&self) -> bool {
+        let same = match (self.odd, self.even) {
+            (Some(odd), Some(even)) => std::ptr::eq(odd, even),
+            (None, None) => true,
+            _ => false,
+        };
+        let mirrored = self.page_number.is_some_and(|p| {
+            matches!(
+                p.place,
+                PageNumberPlace::OutsideTop
+                    | PageNumberPlace::OutsideBottom
+                    | PageNumberPlace::InsideTop
+                    | PageNumberPlace::InsideBottom
+            )
+        });
+        !same || mirrored
+    }
+
+    /// Emit `style:header`/`style:footer` for odd (right) pages and the
+    /// `-left` variant for even pages when they differ
+    fn write(&self, xml: &mut String, element: &str, ctx: &mut ContentContext) -> HwpResult<()> {
+        if self.is_empty() {
+            return Ok(());
+        }
+
+        let mut variants = vec![(element.to_string(), self.odd, false)];
+        if self.differs() {
+            variants.push((format!("{}-left", element), self.even, true));
+        }
+
+        for (name, header_footer, left) in variants {
+            xml.push_str(&format!("\n      <style:{}>", name));
+            if let Some(header_footer) = header_footer {
+                for block in &header_footer.blocks {
+                    generate_block_content(xml, block, ctx)?;
+                }
+            }
+            if let Some(page_number) = self.page_number {
+                page_number_xml(xml, page_number, left, ctx);
+            }
+            xml.push_str(&format!("\n      </style:{}>", name));
+        }
+        Ok(())
+    }
+}
+
+/// Paragraph with the page number of a page number position
+fn page_number_xml(
+    xml: &mut String,
+    position: &PageNumberPosition,
+    left_page: bool,
+    ctx: &mut ContentContext,
+) {
+    // Outside is right on odd pages and left on even pages
+    let align = match (position.place, left_page) {
+        (PageNumberPlace::TopLeft | PageNumberPlace::BottomLeft, _) => "start",
+        (PageNumberPlace::TopCenter | PageNumberPlace::BottomCenter, _) => "center",
+        (PageNumberPlace::TopRight | PageNumberPlace::BottomRight, _) => "end",
+        (PageNumberPlace::OutsideTop | PageNumberPlace::OutsideBottom, false) => "end",
+        (PageNumberPlace::InsideTop | PageNumberPlace::InsideBottom, true) => "end",
+        _ => "start",
+    };
+    let style = ctx.automatic_style(
+        "paragraph",
+        "PN",
+        &format!(r#"<style:paragraph-properties fo:text-align="{}"/>"#, align),
+    );
+
+    let number = AutoNumber {
+        kind: NumberKind::Page,
+        format: position.format,
+        superscript: false,
+        number: 1,
+        user_symbol: position.user_symbol,
+        prefix: position.prefix,
+        suffix: position.suffix,
+    };
+    let dash = position.dash.map(|dash| escape_xml(&dash.to_string()));
+
+    xml.push_str(&format!("\n      <text:p text:style-name=\"{}\">", style));
+    if let Some(dash) = &dash {
+        xml.push_str(&format!("{} ", dash));
+    }
+    numbering::generate_auto_number(xml, &number, ctx);
+    if let Some(dash) = &dash {
+        xml.push_str(&format!(" {}", dash));
+    }
+    xml.push_str("</text:p>");
+}
+
+/// The first master page is the document default
+fn master_page_name(idx: usize) -> String {
+    if idx == 0 {
+        "Standard".to_string()
+    } else {
+        format!("MP{}", idx)
+    }
+}
+
+/// Build the page layout of a section.
+/// Without header/footer content the header and footer areas are folded
+/// into the page margins so the body keeps its position on the page.
+fn page_layout(doc: &Document, section: &Section, one_page: Option<&PageOverride>) -> PageLayout {
+    let mut margin_left = section.margin_left.0;
+    let mut margin_top = section.margin_top.0;
+    let mut margin_right = section.margin_right.0;
+    let mut margin_bottom = section.margin_bottom.0;
+
+    // Header and footer areas sit between the margins and the body
+    let mut header_style = String::new();
+    if PageArea::new(section, false, one_page).is_empty() {
+        margin_top += section.margin_header.0;
+    } else {
+        header_style = header_footer_style("header", section.margin_header);
+    }
+    let mut footer_style = String::new();
+    if PageArea::new(section, true, one_page).is_empty() {
+        margin_bottom += section.margin_footer.0;
+    } else {
+        footer_style = header_footer_style("footer", section.margin_footer);
+    }
+
+    // Gutter goes to the binding edge
+    match section.binding {
+        BindingType::TopFlip => margin_top += section.margin_gutter.0,
+        _ => margin_left += section.margin_gutter.0,
+    }
+
+    // The page border sits in the margins, `spacing` away from the body or
+    // from the paper edge; padding keeps the body in place
+    let mut border = String::new();
+    if let Some(page_border_fill) = page_border_fill(section, one_page.map(|page| page.parity))
+        && let Some(border_fill) = doc.border_fill(page_border_fill.border_fill_id)
+    {
+        let spacing = &page_border_fill.spacing;
+        border = border_fill_properties(border_fill);
+        for (side, margin, spacing) in [
+            ("left", &mut margin_left, spacing.left),
+            ("right", &mut margin_right, spacing.right),
+            ("top", &mut margin_top, spacing.top),
+            ("bottom", &mut margin_bottom, spacing.bottom),
+        ] {
+            // The margin keeps the paper edge side, padding the body side
+            let edge = if page_border_fill.relative_to_paper {
+                spacing.0.clamp(0, *margin)
+            } else {
+                (*margin - spacing.0).max(0)
+            };
+            border.push_str(&format!(
+                r#" fo:padding-{}="{:.2}mm""#,
+                side,
+                HwpUnit(*margin - edge).to_mm()
+            ));
+            *margin = edge;
+        }
+    }
+
+    let properties = format!(
+        r#" fo:page-width="{:.2}mm" fo:page-height="{:.2}mm" style:print-orientation="{}" fo:margin-left="{:.2}mm" fo:margin-right="{:.2}mm" fo:margin-top="{:.2}mm" fo:margin-bottom="{:.2}mm"{}{}{}"#,
+        section.layout_width().to_mm(),
+        section.layout_height().to_mm(),
+        if section.landscape {
+            "landscape"
+        } else {
+            "portrait"
+        },
+        HwpUnit(margin_left).to_mm(),
+        HwpUnit(margin_right).to_mm(),
+        HwpUnit(margin_top).to_mm(),
+        HwpUnit(margin_bottom).to_mm(),
+        border,
+        layout_grid(section),
+        writing_mode(section.text_direction)
+    );
+
+    PageLayout {
+        page_usage: match one_page.map(|page| page.parity) {
+            Some(PageApply::Odd) => Some("right"),
+            Some(PageApply::Even) => Some("left"),
+            _ if section.binding == BindingType::DoubleSided => Some("mirrored"),
+            _ => None,
+        },
+        properties,
+        elements: columns_xml(&section.columns).trim_end().to_string()
+            + &super::note::footnote_sep_xml(section),
+        header_style,
+        footer_style,
+    }
+}
+
+/// Page border/fill of the given pages (both pages for a section's body
+/// pages), falling back to the one for both pages
+fn page_border_fill(section: &Section, parity: Option<PageApply>) -> Option<&PageBorderFill> {
+    // Stored for both, even and odd pages in that order
+    let idx = match parity {
+        Some(PageApply::Even) => 1,
+        Some(PageApply::Odd) => 2,
+        _ => 0,
+    };
+    section
+        .page_border_fills
+        .get(idx)
+        .or(section.page_border_fills.first())
+}
+
+/// Line grid (세로 줄맞춤) and character grid (가로 줄맞춤) of the body,
+/// used for alignment only like HWP's, so neither shown nor printed
+fn layout_grid(section: &Section) -> String {
+    let (lines, chars) = (section.grid_vertical, section.grid_horizontal);
+    if lines.0 <= 0 && chars.0 <= 0 {
+        return String::new();
+    }
+
+    let mut xml = format!(
+        r#" style:layout-grid-mode="{}""#,
+        if chars.0 > 0 { "both" } else { "line" }
+    );
+    if lines.0 > 0 {
+        let body = section.layout_height().0 - section.margin_top.0 - section.margin_bottom.0;
+        xml.push_str(&format!(
+            r#" style:layout-grid-base-height="{:.2}mm" style:layout-grid-ruby-height="0mm" style:layout-grid-lines="{}""#,
+            lines.to_mm(),
+            (body / lines.0).max(1)
+        ));
+    }
+    if chars.0 > 0 {
+        xml.push_str(&format!(
+            r#" style:layout-grid-base-width="{:.2}mm""#,
+            chars.to_mm()
+        ));
+    }
+    xml.push_str(r#" style:layout-grid-snap-to="true" style:layout-grid-display="false" style:layout-grid-print="false""#);
+    xml
+}
+
+/// Fixed-height header or footer area
+fn header_footer_style(element: &str, height: HwpUnit) -> String {
+    format!(
+        r#"
+      <style:{0}-style>
+        <style:header-footer-properties svg:height="{1:.2}mm" fo:margin-left="0mm" fo:margin-right="0mm"/>
+      </style:{0}-style>"#,
+        element,
+        height.to_mm()
+    )
+}
+
+/// `style:columns` element for a column definition (empty for a single column)
+pub(super) fn columns_xml(columns: &ColumnDef) -> String {
+    if columns.count <= 1 {
+        return String::new();
+    }
+
+    let mut xml = format!(
+        r#"
+        <style:columns fo:column-count="{}" fo:column-gap="{:.2}mm">"#,
+        columns.count,
+        columns.gap.to_mm()
+    );
+
+    if columns.separator.style != LineStyle::None {
+        xml.push_str(&format!(
+            r#"
+          <style:column-sep style:style="{}" style:width="{:.2}mm" style:color="{}" style:height="100%"/>"#,
+            column_sep_style(columns.separator.style),
+            columns.separator.width as f64 / 20.0,
+            columns.separator.color.to_hex()
+        ));
+    }
+
+    // Individual widths are relative; the gap is split between neighbouring columns
+    if !columns.same_width && columns.widths.len() == columns.count as usize {
+        let half_gap = columns.gap.to_mm() / 2.0;
+        let last = columns.widths.len() - 1;
+        for (idx, width) in columns.widths.iter().enumerate() {
+            xml.push_str(&format!(
+                r#"
+          <style:column style:rel-width="{}*" fo:start-indent="{:.2}mm" fo:end-indent="{:.2}mm"/>"#,
+                width.0.max(1),
+                if idx == 0 { 0.0 } else { half_gap },
+                if idx == last { 0.0 } else { half_gap }
+            ));
+        }
+    }
+
+    xml.push_str("\n        </style:columns>\n      ");
+    xml
+}
+
+/// Column separator style (ODF supports fewer line styles than HWP)
+fn column_sep_style(style: LineStyle) -> &'static str {
+    match style {
+        LineStyle::None => "none",
+        LineStyle::Dotted => "dotted",
+        LineStyle::Dashed => "dashed",
+        LineStyle::DashDot | LineStyle::DashDotDot => "dot-dashed",
+        _ => "solid",
+    }
+}
+
+/// Section properties for a column layout change inside the body
+pub(super) fn section_properties_xml(columns: &ColumnDef) -> String {
+    // Normal columns fill one after another; distributed columns are balanced
+    let balance = if columns.column_type == ColumnType::Normal {
+        r#" text:dont-balance-text-columns="true""#
+    } else {
+        ""
+    };
+    // A single column is explicit so it does not take the page's columns
+    let columns = if columns.count > 1 {
+        columns_xml(columns)
+    } else {
+        r#"<style:columns fo:column-count="1" fo:column-gap="0mm"/>"#.to_string()
+    };
+    format!(
+        r#"
+      <style:section-properties{}>{}</style:section-properties>"#,
+        balance, columns
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::{Color, Margin};
+    use crate::model::{Border, BorderFill, FillType, TextRun};
+
+    fn header(apply_to: PageApply, text: &str) -> HeaderFooter {
+        let mut para = Paragraph::new(0);
+        para.inlines.push(Inline::Text(TextRun {
+            text: text.to_string(),
+            char_shape_id: 0,
+            change_id: None,
+        }));
+        HeaderFooter {
+            apply_to,
+            text_width: HwpUnit(0),
+            text_height: HwpUnit(0),
+            blocks: vec![Block::Paragraph(para)],
+        }
+    }
+
+    #[test]
+    fn test_odd_page_header_leaves_even_pages_empty() {
+        let mut doc = Document::new();
+        let mut section = Section::new();
+        section.headers.push(header(PageApply::Odd, "Odd"));
+        doc.sections.push(section);
+
+        let styles = PageStyles::collect(&doc);
+        let mut ctx = ContentContext::new(&doc);
+        let xml = styles.master_pages_xml(&doc, &mut ctx).unwrap();
+        let header = xml.find("<style:header>").unwrap();
+        let left = xml.find("<style:header-left>").unwrap();
+        assert!(xml[header..left].contains("Odd"));
+        assert!(xml[left..].starts_with("<style:header-left>\n      </style:header-left>"));
+        assert!(!xml.contains("<style:footer"));
+        // Only the header area stays out of the margins
+        let layout = &styles.layouts[0];
+        assert!(!layout.header_style.is_empty());
+        assert!(layout.footer_style.is_empty());
+    }
+
+    #[test]
+    fn test_empty_page_areas_fold_into_margins() {
+        let doc = Document::new();
+        let layout = page_layout(&doc, &Section::new(), None);
+        // 20mm top and 15mm bottom margins, each with a 15mm area added
+        assert!(
+            layout
+                .properties
+                .contains(r#" fo:margin-top="35.00mm" fo:margin-bottom="30.00mm""#)
+        );
+        assert!(layout.header_style.is_empty());
+        assert!(layout.footer_style.is_empty());
+    }
+
+    #[test]
+    fn test_sections_without_page_areas_share_standard() {
+        let mut doc = Document::new();
+        doc.sections.push(Section::new());
+        doc.sections.push(Section::new());
+
+        let styles = PageStyles::collect(&doc);
+        assert_eq!(styles.masters.len(), 1);
+        assert_eq!(styles.master_name(0), "Standard");
+        assert_eq!(styles.master_name(1), "Standard");
+        assert_eq!(styles.body_master_name(1), "Standard");
+
+        // A header gives its section a master page of its own
+        doc.sections[1]
+            .headers
+            .push(header(PageApply::Both, "Both"));
+        let styles = PageStyles::collect(&doc);
+        assert_eq!(styles.master_name(0), "Standard");
+        assert_eq!(styles.master_name(1), "MP1");
+    }
+
+    #[test]
+    fn test_page_border_fill_and_grid() {
+        let mut doc = Document::new();
+        doc.border_fills.push(BorderFill {
+            id: 1,
+            left: Border {
+                style: LineStyle::Solid,
+                width: 2,
+                color: Color(0),
+            },
+            right: Border::none(),
+            top: Border::none(),
+            bottom: Border::none(),
+            diagonal: Border::none(),
+            fill_type: FillType::Solid,
+            fill_color: Color(0),
+            background_color: Color(0xFFFF00),
+        });
+        // Border 10mm from the body, body 30mm from the left edge
+        let mut section = Section::new();
+        section.page_border_fills.push(PageBorderFill {
+            relative_to_paper: false,
+            include_header: true,
+            include_footer: true,
+            fill_area: 0,
+            spacing: Margin::new(HwpUnit(2835), HwpUnit(0), HwpUnit(0), HwpUnit(0)),
+            border_fill_id: 1,
+        });
+        section.grid_vertical = HwpUnit(2835);
+
+        let layout = page_layout(&doc, &section, None);
+        assert!(layout.properties.contains(
+            r#" fo:margin-left="20.00mm" fo:margin-right="30.00mm" fo:margin-top="35.00mm""#
+        ));
+        assert!(
+            layout
+                .properties
+                .contains(r#" fo:border-left="0.10mm solid #000000" fo:border-right="none""#)
+        );
+        assert!(
+            layout
+                .properties
+                .contains(r##" fo:background-color="#00FFFF""##)
+        );
+        assert!(
+            layout
+                .properties
+                .contains(r#" fo:padding-left="10.00mm" fo:padding-right="0.00mm""#)
+        );
+        assert!(
+            layout.properties.contains(
+                r#" style:layout-grid-mode="line" style:layout-grid-base-height="10.00mm""#
+            )
+        );
+
+        // Measured from the paper edge, the border moves outward
+        section.page_border_fills[0].relative_to_paper = true;
+        let layout = page_layout(&doc, &section, None);
+        assert!(layout.properties.contains(r#" fo:margin-left="10.00mm""#));
+        assert!(layout.properties.contains(r#" fo:padding-left="20.00mm""#));
+    }
+}
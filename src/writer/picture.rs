@@ -0,0 +1,188 @@
+use super::{ContentContext, border_xml, escape_xml, frame};
+use crate::common::{HwpResult, LineStyle};
+use crate::model::{BinData, BinDataKind, Document, ImageEffect, Picture};
+
+/// Media type of an image by file extension
+pub(super) fn media_type(extension: &str) -> Option<&'static str> {
+    match extension {
+        "jpg" | "jpeg" => Some("image/jpeg"),
+        "png" => Some("image/png"),
+        "gif" => Some("image/gif"),
+        "bmp" => Some("image/bmp"),
+        "tif" | "tiff" => Some("image/tiff"),
+        "svg" => Some("image/svg+xml"),
+        "wmf" => Some("image/x-wmf"),
+        "emf" => Some("image/x-emf"),
+        _ => None,
+    }
+}
+
+/// Package path of an embedded image
+fn picture_path(bin_data: &BinData) -> String {
+    format!("Pictures/BIN{:04X}.{}", bin_data.id, bin_data.extension)
+}
+
+/// Embedded images stored in the package: path, media type and contents
+pub(super) fn package_pictures(doc: &Document) -> Vec<(String, &'static str, &[u8])> {
+    doc.bin_data
+        .iter()
+        .filter(|b| b.kind == BinDataKind::Embedding && !b.data.is_empty())
+        .filter_map(|b| {
+            Some((
+                picture_path(b),
+                media_type(&b.extension)?,
+                b.data.as_slice(),
+            ))
+        })
+        .collect()
+}
+
+/// Image reference for a picture, or None if its data cannot be found
+fn picture_href(picture: &Picture, doc: &Document) -> Option<String> {
+    let bin_data = doc.bin_data(picture.bindata_id)?;
+    match bin_data.kind {
+        BinDataKind::Link if !bin_data.link_path.is_empty() => {
+            let path = bin_data.link_path.replace('\\', "/");
+            // Windows drive paths become file URLs
+            if path.as_bytes().get(1) == Some(&b':') {
+                Some(format!("file:///{}", path))
+            } else {
+                Some(path)
+            }
+        }
+        BinDataKind::Embedding if !bin_data.data.is_empty() => {
+            media_type(&bin_data.extension).map(|_| picture_path(bin_data))
+        }
+        _ => None,
+    }
+}
+
+/// Generate a picture as a frame holding the image
+pub(super) fn generate_picture(
+    xml: &mut String,
+    picture: &Picture,
+    ctx: &mut ContentContext,
+) -> HwpResult<()> {
+    write_picture(xml, picture, "", ctx)
+}
+
+/// Write a picture's frame; `transform` is an optional extra
+/// draw:transform attribute
+pub(super) fn write_picture(
+    xml: &mut String,
+    picture: &Picture,
+    transform: &str,
+    ctx: &mut ContentContext,
+) -> HwpResult<()> {
+    let Some(href) = picture_href(picture, ctx.doc) else {
+        return Ok(());
+    };
+    ctx.picture_count += 1;
+
+    // Older files may leave the object size empty; use the image extent
+    let mut placement = picture.placement.clone();
+    if placement.width.0 <= 0 {
+        placement.width = picture.rect.width();
+    }
+    if placement.height.0 <= 0 {
+        placement.height = picture.rect.height();
+    }
+
+    let image = format!(
+        r#"<draw:image xlink:href="{}" xlink:type="simple" xlink:show="embed" xlink:actuate="onLoad"/>"#,
+        escape_xml(&href)
+    );
+    frame::generate_frame(
+        xml,
+        &placement,
+        &format!("Image{}", ctx.picture_count),
+        transform,
+        &image_properties(picture),
+        &image,
+        ctx,
+    )
+}
+
+/// Border, inner margins, crop and image adjustments of a picture
+fn image_properties(picture: &Picture) -> String {
+    let mut props = String::new();
+    let padding = &picture.margin;
+    props.push_str(&format!(
+        r#" fo:padding-left="{:.2}mm" fo:padding-right="{:.2}mm" fo:padding-top="{:.2}mm" fo:padding-bottom="{:.2}mm""#,
+        padding.left.to_mm(),
+        padding.right.to_mm(),
+        padding.top.to_mm(),
+        padding.bottom.to_mm()
+    ));
+    if picture.border.style != LineStyle::None && picture.border.width > 0 {
+        props.push_str(&format!(r#" fo:border="{}""#, border_xml(&picture.border)));
+    }
+
+    let crop = &picture.crop;
+    if [crop.left, crop.top, crop.right, crop.bottom]
+        .iter()
+        .any(|edge| edge.0 > 0)
+    {
+        props.push_str(&format!(
+            r#" fo:clip="rect({:.2}mm, {:.2}mm, {:.2}mm, {:.2}mm)""#,
+            crop.top.to_mm(),
+            crop.right.to_mm(),
+            crop.bottom.to_mm(),
+            crop.left.to_mm()
+        ));
+    }
+
+    if picture.brightness != 0 {
+        props.push_str(&format!(r#" draw:luminance="{}%""#, picture.brightness));
+    }
+    if picture.contrast != 0 {
+        props.push_str(&format!(r#" draw:contrast="{}%""#, picture.contrast));
+    }
+    let color_mode = match picture.effect {
+        ImageEffect::RealPicture | ImageEffect::Pattern => "standard",
+        ImageEffect::Grayscale => "greyscale",
+        ImageEffect::BlackWhite => "mono",
+    };
+    props.push_str(&format!(r#" draw:color-mode="{}""#, color_mode));
+    props
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::{Color, HwpUnit, Margin, Rect};
+    use crate::model::{Border, ObjectPlacement};
+
+    #[test]
+    fn test_image_properties_crop_and_effect() {
+        let mut picture = Picture {
+            id: 1,
+            bindata_id: 1,
+            filename: String::new(),
+            rect: Rect::new(HwpUnit(0), HwpUnit(0), HwpUnit(7200), HwpUnit(7200)),
+            crop: Margin::zero(),
+            margin: Margin::zero(),
+            border: Border {
+                style: LineStyle::None,
+                width: 0,
+                color: Color(0),
+            },
+            brightness: 0,
+            contrast: 0,
+            effect: ImageEffect::RealPicture,
+            placement: ObjectPlacement::new(),
+        };
+        let props = image_properties(&picture);
+        assert!(!props.contains("fo:clip"));
+        assert!(props.contains(r#"draw:color-mode="standard""#));
+
+        // Crop 1 inch off the right; a grey, brightened image
+        picture.crop.right = HwpUnit(7200);
+        picture.brightness = 20;
+        picture.effect = ImageEffect::Grayscale;
+        let props = image_properties(&picture);
+        assert!(props.contains(r#"fo:clip="rect(0.00mm, 25.40mm, 0.00mm, 0.00mm)""#));
+        assert!(props.contains(r#"draw:luminance="20%""#));
+        assert!(props.contains(r#"draw:color-mode="greyscale""#));
+    }
+}
@@ -0,0 +1,47 @@
+use super::{ContentContext, escape_text_content, escape_xml};
+use crate::model::{Dutmal, DutmalAlign, DutmalPosition};
+
+/// Generate a dutmal as ruby text over (or under) its base text, the
+/// dutmal scaled by its size ratio
+pub(super) fn generate_dutmal(xml: &mut String, dutmal: &Dutmal, ctx: &mut ContentContext) {
+    // ODF has no ruby between the lines; a centered dutmal goes above
+    let position = match dutmal.position {
+        DutmalPosition::Below => "below",
+        DutmalPosition::Above | DutmalPosition::Center => "above",
+    };
+    let align = match dutmal.align {
+        DutmalAlign::Left => "left",
+        DutmalAlign::Right => "right",
+        DutmalAlign::Center => "center",
+        DutmalAlign::Distribute => "distribute-letter",
+        DutmalAlign::Justify | DutmalAlign::Divide => "distribute-space",
+    };
+    let ruby_style = ctx.automatic_style(
+        "ruby",
+        "Ru",
+        &format!(
+            r#"<style:ruby-properties style:ruby-position="{}" style:ruby-align="{}"/>"#,
+            position, align
+        ),
+    );
+
+    let text_style = format!("T{}R{}", dutmal.char_shape_id, dutmal.size_ratio);
+    ctx.add_style(&text_style, || {
+        format!(
+            r#"
+    <style:style style:name="{}" style:family="text" style:parent-style-name="T{}">
+      <style:text-properties fo:font-size="{2}%" style:font-size-asian="{2}%"/>
+    </style:style>"#,
+            text_style, dutmal.char_shape_id, dutmal.size_ratio
+        )
+    });
+
+    xml.push_str(&format!(
+        r#"<text:ruby text:style-name="{}"><text:ruby-base><text:span text:style-name="T{}">{}</text:span></text:ruby-base><text:ruby-text text:style-name="{}">{}</text:ruby-text></text:ruby>"#,
+        ruby_style,
+        dutmal.char_shape_id,
+        escape_text_content(&dutmal.base),
+        text_style,
+        escape_xml(&dutmal.text)
+    ));
+}
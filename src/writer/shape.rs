@@ -0,0 +1,747 @@
+use super::frame::{frame_attributes, generate_frame, placement_properties};
+use super::ole::write_ole;
+use super::picture::write_picture;
+use super::vertical::{in_direction, writing_mode};
+use super::{ContentContext, generate_block_content};
+use crate::common::{HwpResult, HwpUnit, LineStyle, Position, Rect, VAlignment};
+use crate::model::{
+    ArcKind, ArrowStyle, CurveSegment, Fill, FillType, GroupChild, GroupObject, OLE, ObjectAlign,
+    ObjectPlacement, Picture, Shape, ShapeGroup, ShapeLine, ShapeMatrix, ShapeType, TextBox,
+};
+
+/// Marker and dash definitions referenced by shape styles (office:styles)
+pub(super) fn shape_styles_xml() -> String {
+    let mut xml = String::new();
+    for (name, view_box, path) in [
+        ("Arrow", "0 0 20 30", "M10 0l-10 30h20z"),
+        ("Spear", "0 0 20 30", "M10 0l-10 30h4l6-22 6 22h4z"),
+        ("ConcaveArrow", "0 0 20 30", "M10 0l-10 30 10-8 10 8z"),
+        ("Diamond", "0 0 20 20", "M10 0l10 10-10 10-10-10z"),
+        (
+            "Circle",
+            "0 0 20 20",
+            "M20 10a10 10 0 1 1-20 0a10 10 0 1 1 20 0z",
+        ),
+        ("Square", "0 0 20 20", "M0 0h20v20h-20z"),
+    ] {
+        xml.push_str(&format!(
+            r#"
+    <draw:marker draw:name="{}" svg:viewBox="{}" svg:d="{}"/>"#,
+            name, view_box, path
+        ));
+    }
+    // Dash and gap lengths relative to the line width
+    for (name, dots) in [
+        ("Dot", r#"draw:dots1="1" draw:dots1-length="100%""#),
+        ("Dash", r#"draw:dots1="1" draw:dots1-length="300%""#),
+        (
+            "DashDot",
+            r#"draw:dots1="1" draw:dots1-length="300%" draw:dots2="1" draw:dots2-length="100%""#,
+        ),
+        (
+            "DashDotDot",
+            r#"draw:dots1="1" draw:dots1-length="300%" draw:dots2="2" draw:dots2-length="100%""#,
+        ),
+    ] {
+        xml.push_str(&format!(
+            r#"
+    <draw:stroke-dash draw:name="{}" draw:style="rect" {} draw:distance="200%"/>"#,
+            name, dots
+        ));
+    }
+    xml
+}
+
+/// Generate a drawing shape as the matching ODF draw element
+pub(super) fn generate_shape(
+    xml: &mut String,
+    shape: &Shape,
+    ctx: &mut ContentContext,
+) -> HwpResult<()> {
+    write_shape(xml, shape, &shape.placement, "", ctx)
+}
+
+/// Generate a group as draw:g. Children are positioned by their matrices
+/// composed with the group's scale and offset, all in HWPUNIT.
+pub(super) fn generate_group(
+    xml: &mut String,
+    group: &ShapeGroup,
+    ctx: &mut ContentContext,
+) -> HwpResult<()> {
+    ctx.shape_count += 1;
+    let placement = &group.placement;
+    let style = ctx.automatic_style(
+        "graphic",
+        "gr",
+        &format!(
+            "<style:graphic-properties{}/>",
+            placement_properties(placement)
+        ),
+    );
+    let anchor = if placement.treat_as_char {
+        "as-char"
+    } else {
+        "paragraph"
+    };
+    xml.push_str(&format!(
+        r#"<draw:g draw:style-name="{}" draw:name="Group{}" text:anchor-type="{}" draw:z-index="{}">"#,
+        style,
+        ctx.shape_count,
+        anchor,
+        placement.z_order.max(0)
+    ));
+
+    let scale = |size: HwpUnit, original: HwpUnit| {
+        if original.0 > 0 && size.0 > 0 {
+            size.0 as f64 / original.0 as f64
+        } else {
+            1.0
+        }
+    };
+    let (x, y) = if placement.treat_as_char {
+        (0.0, 0.0)
+    } else {
+        (placement.offset_x.0 as f64, placement.offset_y.0 as f64)
+    };
+    let matrix = ShapeMatrix {
+        a: scale(placement.width, group.rect.width()),
+        c: x,
+        e: scale(placement.height, group.rect.height()),
+        f: y,
+        ..ShapeMatrix::identity()
+    };
+    generate_group_children(xml, &group.children, &matrix, placement, ctx)?;
+    xml.push_str("</draw:g>");
+    Ok(())
+}
+
+fn generate_group_children(
+    xml: &mut String,
+    children: &[GroupChild],
+    parent: &ShapeMatrix,
+    group_placement: &ObjectPlacement,
+    ctx: &mut ContentContext,
+) -> HwpResult<()> {
+    for child in children {
+        let matrix = parent.multiply(&child.matrix);
+        match &child.object {
+            GroupObject::Shape(shape) => {
+                let (placement, transform) = child_placement(&shape.rect, &matrix, group_placement);
+                write_shape(xml, shape, &placement, &transform, ctx)?;
+            }
+            GroupObject::Picture(picture) => {
+                let (placement, transform) =
+                    child_placement(&picture.rect, &matrix, group_placement);
+                let picture = Picture {
+                    placement,
+                    ..picture.clone()
+                };
+                write_picture(xml, &picture, &transform, ctx)?;
+            }
+            GroupObject::Ole(ole) => {
+                let rect = Rect::new(
+                    HwpUnit(0),
+                    HwpUnit(0),
+                    ole.placement.width,
+                    ole.placement.height,
+                );
+                let (placement, transform) = child_placement(&rect, &matrix, group_placement);
+                let ole = OLE {
+                    placement,
+                    ..ole.clone()
+                };
+                write_ole(xml, &ole, &transform, ctx)?;
+            }
+            GroupObject::Group(group) => {
+                ctx.shape_count += 1;
+                xml.push_str(&format!(r#"<draw:g draw:name="Group{}">"#, ctx.shape_count));
+                generate_group_children(xml, &group.children, &matrix, group_placement, ctx)?;
+                xml.push_str("</draw:g>");
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Position and size of a grouped object's extent under `matrix`, with a
+/// draw:transform attribute when the object is rotated. Children share
+/// the group's anchor and wrapping.
+fn child_placement(
+    rect: &Rect,
+    matrix: &ShapeMatrix,
+    group_placement: &ObjectPlacement,
+) -> (ObjectPlacement, String) {
+    let scale_x = matrix.a.hypot(matrix.d);
+    let scale_y = if scale_x > 0.0 {
+        (matrix.a * matrix.e - matrix.b * matrix.d).abs() / scale_x
+    } else {
+        0.0
+    };
+    let rotation = matrix.d.atan2(matrix.a);
+    let (x, y) = matrix.apply(0.0, 0.0);
+
+    let mut placement = ObjectPlacement {
+        treat_as_char: false,
+        vert_align: ObjectAlign::Start,
+        horz_align: ObjectAlign::Start,
+        width: HwpUnit((rect.width().0 as f64 * scale_x).round() as i32),
+        height: HwpUnit((rect.height().0 as f64 * scale_y).round() as i32),
+        caption: None,
+        ..group_placement.clone()
+    };
+    if rotation.abs() < 1e-6 {
+        placement.offset_x = HwpUnit(x.round() as i32);
+        placement.offset_y = HwpUnit(y.round() as i32);
+        return (placement, String::new());
+    }
+
+    // Rotate about the object's origin, then move it into place. ODF angles
+    // are in radians and turn counterclockwise.
+    placement.offset_x = HwpUnit(0);
+    placement.offset_y = HwpUnit(0);
+    let transform = format!(
+        r#" draw:transform="rotate({:.6}) translate({:.2}mm {:.2}mm)""#,
+        -rotation,
+        HwpUnit(x.round() as i32).to_mm(),
+        HwpUnit(y.round() as i32).to_mm()
+    );
+    (placement, transform)
+}
+
+/// Write a shape at `placement`; `transform` is an optional extra
+/// draw:transform attribute
+fn write_shape(
+    xml: &mut String,
+    shape: &Shape,
+    placement: &ObjectPlacement,
+    transform: &str,
+    ctx: &mut ContentContext,
+) -> HwpResult<()> {
+    ctx.shape_count += 1;
+
+    // A rectangle with text is a text box; a frame keeps the text flowing
+    // like body text. Rounded corners are dropped.
+    if let (ShapeType::Rectangle { .. }, Some(text)) = (&shape.shape_type, &shape.text) {
+        let properties = format!(
+            "{}{}{}",
+            frame_border(&shape.line),
+            frame_background(&shape.fill),
+            text_area_properties(text)
+        );
+        let content = text_box_xml(text, ctx)?;
+        let name = format!("Shape{}", ctx.shape_count);
+        return generate_frame(xml, placement, &name, transform, &properties, &content, ctx);
+    }
+
+    let style = ctx.automatic_style(
+        "graphic",
+        "gr",
+        &format!(
+            "<style:graphic-properties{}{}{}{}/>",
+            placement_properties(placement),
+            stroke_properties(&shape.line),
+            fill_properties(&shape.fill),
+            shape
+                .text
+                .as_ref()
+                .map_or(String::new(), text_area_properties)
+        ),
+    );
+    let common = format!(
+        r#"draw:style-name="{}" draw:name="Shape{}"{}"#,
+        style, ctx.shape_count, transform
+    );
+    let frame = frame_attributes(placement, placement.width, placement.height);
+    // Points are in the shape's original coordinates
+    let view_box = format!(
+        r#"svg:viewBox="0 0 {} {}""#,
+        shape.rect.width().0.max(1),
+        shape.rect.height().0.max(1)
+    );
+
+    let (element, attrs) = match &shape.shape_type {
+        ShapeType::Line { start, end } => {
+            // Lines are placed by their end points rather than a frame
+            let (x, y) = if placement.treat_as_char {
+                (HwpUnit(0), HwpUnit(0))
+            } else {
+                (placement.offset_x, placement.offset_y)
+            };
+            let scale = |value: HwpUnit, original: HwpUnit, size: HwpUnit| {
+                if original.0 > 0 {
+                    value.to_mm() * size.0 as f64 / original.0 as f64
+                } else {
+                    value.to_mm()
+                }
+            };
+            let anchor = if placement.treat_as_char {
+                "as-char"
+            } else {
+                "paragraph"
+            };
+            let attrs = format!(
+                r#"{} text:anchor-type="{}" svg:x1="{:.2}mm" svg:y1="{:.2}mm" svg:x2="{:.2}mm" svg:y2="{:.2}mm" draw:z-index="{}""#,
+                common,
+                anchor,
+                x.to_mm() + scale(start.x, shape.rect.width(), placement.width),
+                y.to_mm() + scale(start.y, shape.rect.height(), placement.height),
+                x.to_mm() + scale(end.x, shape.rect.width(), placement.width),
+                y.to_mm() + scale(end.y, shape.rect.height(), placement.height),
+                placement.z_order.max(0)
+            );
+            ("draw:line", attrs)
+        }
+        ShapeType::Rectangle { curvature } => {
+            let attrs = format!(
+                r#"{}{} draw:corner-radius="{:.2}mm""#,
+                common,
+                frame,
+                corner_radius(placement.width, placement.height, *curvature).to_mm()
+            );
+            ("draw:rect", attrs)
+        }
+        ShapeType::Ellipse {
+            center,
+            axis1,
+            axis2,
+            arc,
+        } => {
+            let arc_attrs = arc.map_or(String::new(), |arc| {
+                let angle = |point: Position| ellipse_angle(point, *center, *axis1, *axis2);
+                format!(
+                    r#" draw:kind="{}" draw:start-angle="{:.2}" draw:end-angle="{:.2}""#,
+                    match arc.kind {
+                        ArcKind::Arc => "arc",
+                        ArcKind::Pie => "section",
+                        ArcKind::Chord => "cut",
+                    },
+                    angle(arc.start),
+                    angle(arc.end)
+                )
+            });
+            ("draw:ellipse", format!("{}{}{}", common, frame, arc_attrs))
+        }
+        ShapeType::Arc {
+            kind,
+            center,
+            axis1,
+            axis2,
+        } => {
+            let attrs = format!(
+                r#"{}{} {} svg:d="{}""#,
+                common,
+                frame,
+                view_box,
+                arc_path(*kind, *center, *axis1, *axis2)
+            );
+            ("draw:path", attrs)
+        }
+        ShapeType::Polygon { points } => {
+            let points: Vec<String> = points
+                .iter()
+                .map(|p| format!("{},{}", p.x.0, p.y.0))
+                .collect();
+            let attrs = format!(
+                r#"{}{} {} draw:points="{}""#,
+                common,
+                frame,
+                view_box,
+                points.join(" ")
+            );
+            ("draw:polygon", attrs)
+        }
+        ShapeType::Curve { points, segments } => {
+            let attrs = format!(
+                r#"{}{} {} svg:d="{}""#,
+                common,
+                frame,
+                view_box,
+                curve_path(points, segments)
+            );
+            ("draw:path", attrs)
+        }
+    };
+
+    // No pretty-printing here: shapes sit inside text:p. Other shapes hold
+    // their text paragraphs directly.
+    match &shape.text {
+        Some(text) => {
+            xml.push_str(&format!("<{} {}>", element, attrs));
+            in_direction(ctx, text.text_direction, |ctx| {
+                text.content
+                    .iter()
+                    .try_for_each(|block| generate_block_content(xml, block, ctx))
+            })?;
+            xml.push_str(&format!("</{}>", element));
+        }
+        None => xml.push_str(&format!("<{} {}/>", element, attrs)),
+    }
+    Ok(())
+}
+
+/// Generate a standalone text box as a character-sized frame
+pub(super) fn generate_text_box(
+    xml: &mut String,
+    text: &TextBox,
+    ctx: &mut ContentContext,
+) -> HwpResult<()> {
+    ctx.shape_count += 1;
+    let placement = ObjectPlacement {
+        treat_as_char: true,
+        width: text.rect.width(),
+        height: text.rect.height(),
+        ..ObjectPlacement::new()
+    };
+    let content = text_box_xml(text, ctx)?;
+    let name = format!("Shape{}", ctx.shape_count);
+    generate_frame(
+        xml,
+        &placement,
+        &name,
+        "",
+        &text_area_properties(text),
+        &content,
+        ctx,
+    )
+}
+
+fn text_box_xml(text: &TextBox, ctx: &mut ContentContext) -> HwpResult<String> {
+    let mut xml = String::from("<draw:text-box>");
+    in_direction(ctx, text.text_direction, |ctx| {
+        text.content
+            .iter()
+            .try_for_each(|block| generate_block_content(&mut xml, block, ctx))
+    })?;
+    xml.push_str("</draw:text-box>");
+    Ok(xml)
+}
+
+/// Inner margins and vertical alignment of a shape's text
+fn text_area_properties(text: &TextBox) -> String {
+    let margin = &text.margin;
+    format!(
+        r#" fo:padding-left="{:.2}mm" fo:padding-right="{:.2}mm" fo:padding-top="{:.2}mm" fo:padding-bottom="{:.2}mm" draw:textarea-vertical-align="{}"{}"#,
+        margin.left.to_mm(),
+        margin.right.to_mm(),
+        margin.top.to_mm(),
+        margin.bottom.to_mm(),
+        match text.v_align {
+            VAlignment::Top => "top",
+            VAlignment::Center => "middle",
+            VAlignment::Bottom => "bottom",
+        },
+        writing_mode(text.text_direction)
+    )
+}
+
+/// Shape outline as a frame border
+fn frame_border(line: &ShapeLine) -> String {
+    let style = match line.style {
+        LineStyle::None => return r#" fo:border="none""#.to_string(),
+        LineStyle::Dotted => "dotted",
+        LineStyle::Dashed | LineStyle::DashDot | LineStyle::DashDotDot => "dashed",
+        LineStyle::Double => "double",
+        LineStyle::Solid | LineStyle::Wave => "solid",
+    };
+    // Zero thickness is a hairline
+    format!(
+        r#" fo:border="{:.2}mm {} {}""#,
+        line.width.to_mm().max(0.05),
+        style,
+        line.color.to_hex()
+    )
+}
+
+/// Shape fill as a frame background
+fn frame_background(fill: &Fill) -> String {
+    match fill.fill_type {
+        FillType::None | FillType::Image => r#" fo:background-color="transparent""#.to_string(),
+        FillType::Solid | FillType::Pattern | FillType::Gradient => format!(
+            r#" fo:background-color="{}""#,
+            fill.background_color.to_hex()
+        ),
+    }
+}
+
+/// Stroke and arrowhead properties of a shape outline
+fn stroke_properties(line: &ShapeLine) -> String {
+    let (stroke, dash) = match line.style {
+        LineStyle::None => ("none", None),
+        LineStyle::Dotted => ("dash", Some("Dot")),
+        LineStyle::Dashed => ("dash", Some("Dash")),
+        LineStyle::DashDot => ("dash", Some("DashDot")),
+        LineStyle::DashDotDot => ("dash", Some("DashDotDot")),
+        LineStyle::Solid | LineStyle::Double | LineStyle::Wave => ("solid", None),
+    };
+    let mut props = format!(r#" draw:stroke="{}""#, stroke);
+    if let Some(dash) = dash {
+        props.push_str(&format!(r#" draw:stroke-dash="{}""#, dash));
+    }
+    props.push_str(&format!(
+        r#" svg:stroke-width="{:.2}mm" svg:stroke-color="{}""#,
+        line.width.to_mm(),
+        line.color.to_hex()
+    ));
+
+    for (end, arrow, size) in [
+        ("start", line.start_arrow, line.start_arrow_size),
+        ("end", line.end_arrow, line.end_arrow_size),
+    ] {
+        let name = match arrow {
+            ArrowStyle::None => continue,
+            ArrowStyle::Arrow => "Arrow",
+            ArrowStyle::Spear => "Spear",
+            ArrowStyle::ConcaveArrow => "ConcaveArrow",
+            ArrowStyle::Diamond => "Diamond",
+            ArrowStyle::Circle => "Circle",
+            ArrowStyle::Box => "Square",
+        };
+        // Small, medium and large arrowhead widths
+        let width = [2.0, 3.0, 4.5][(size as usize / 3).min(2)];
+        props.push_str(&format!(
+            r#" draw:marker-{0}="{1}" draw:marker-{0}-width="{2:.2}mm""#,
+            end, name, width
+        ));
+    }
+    props
+}
+
+/// Area fill properties; patterns, gradients and images fall back to the
+/// face color
+fn fill_properties(fill: &Fill) -> String {
+    match fill.fill_type {
+        FillType::None | FillType::Image => r#" draw:fill="none""#.to_string(),
+        FillType::Solid | FillType::Pattern | FillType::Gradient => format!(
+            r#" draw:fill="solid" draw:fill-color="{}""#,
+            fill.background_color.to_hex()
+        ),
+    }
+}
+
+/// Corner radius of a rounded rectangle; curvature is a percentage of the
+/// shorter side, so 50 makes the short ends semicircles
+fn corner_radius(width: HwpUnit, height: HwpUnit, curvature: u8) -> HwpUnit {
+    HwpUnit((width.0.min(height.0) as f64 * curvature.min(50) as f64 / 100.0) as i32)
+}
+
+/// Angle of a point on an ellipse in degrees, counterclockwise from the
+/// first axis as ODF expects
+fn ellipse_angle(point: Position, center: Position, axis1: Position, axis2: Position) -> f64 {
+    let radius = |axis: Position| {
+        let (dx, dy) = (
+            (axis.x.0 - center.x.0) as f64,
+            (axis.y.0 - center.y.0) as f64,
+        );
+        dx.hypot(dy).max(1.0)
+    };
+    let x = (point.x.0 - center.x.0) as f64 / radius(axis1);
+    let y = (center.y.0 - point.y.0) as f64 / radius(axis2);
+    y.atan2(x).to_degrees().rem_euclid(360.0)
+}
+
+/// SVG path of an elliptical arc from the end of one axis to the other
+fn arc_path(kind: ArcKind, center: Position, axis1: Position, axis2: Position) -> String {
+    let (dx1, dy1) = (
+        (axis1.x.0 - center.x.0) as f64,
+        (axis1.y.0 - center.y.0) as f64,
+    );
+    let (dx2, dy2) = (
+        (axis2.x.0 - center.x.0) as f64,
+        (axis2.y.0 - center.y.0) as f64,
+    );
+    let rotation = dy1.atan2(dx1).to_degrees();
+    // With y pointing down, a positive cross product turns clockwise
+    let sweep = (dx1 * dy2 - dy1 * dx2 > 0.0) as u8;
+    let arc = format!(
+        "A {:.0} {:.0} {:.2} 0 {} {} {}",
+        dx1.hypot(dy1),
+        dx2.hypot(dy2),
+        rotation,
+        sweep,
+        axis2.x.0,
+        axis2.y.0
+    );
+    match kind {
+        ArcKind::Arc => format!("M {} {} {}", axis1.x.0, axis1.y.0, arc),
+        ArcKind::Pie => format!(
+            "M {} {} L {} {} {} Z",
+            center.x.0, center.y.0, axis1.x.0, axis1.y.0, arc
+        ),
+        ArcKind::Chord => format!("M {} {} {} Z", axis1.x.0, axis1.y.0, arc),
+    }
+}
+
+/// SVG path of a curve: straight segments, or cubic Bezier segments whose
+/// next two points are control points
+fn curve_path(points: &[Position], segments: &[CurveSegment]) -> String {
+    let Some(first) = points.first() else {
+        return String::new();
+    };
+    let mut d = format!("M {} {}", first.x.0, first.y.0);
+    let mut idx = 1;
+    while idx < points.len() {
+        let segment = segments.get(idx - 1).copied().unwrap_or(CurveSegment::Line);
+        if segment == CurveSegment::Curve && idx + 2 < points.len() {
+            let (c1, c2, end) = (points[idx], points[idx + 1], points[idx + 2]);
+            d.push_str(&format!(
+                " C {} {} {} {} {} {}",
+                c1.x.0, c1.y.0, c2.x.0, c2.y.0, end.x.0, end.y.0
+            ));
+            idx += 3;
+        } else {
+            d.push_str(&format!(" L {} {}", points[idx].x.0, points[idx].y.0));
+            idx += 1;
+        }
+    }
+    d
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::{Color, Margin};
+    use crate::model::{Document, TextDirection};
+
+    fn pos(x: i32, y: i32) -> Position {
+        Position::new(HwpUnit(x), HwpUnit(y))
+    }
+
+    #[test]
+    fn test_arc_and_curve_paths() {
+        // Quarter pie from the right to the bottom of a circle around (0, 0)
+        assert_eq!(
+            arc_path(ArcKind::Pie, pos(0, 0), pos(100, 0), pos(0, 100)),
+            "M 0 0 L 100 0 A 100 100 0.00 0 1 0 100 Z"
+        );
+        assert_eq!(
+            curve_path(
+                &[pos(0, 0), pos(10, 0), pos(20, 10), pos(20, 20), pos(0, 20)],
+                &[
+                    CurveSegment::Curve,
+                    CurveSegment::Curve,
+                    CurveSegment::Curve,
+                    CurveSegment::Line
+                ]
+            ),
+            "M 0 0 C 10 0 20 10 20 20 L 0 20"
+        );
+        // A point straight above the center sits at 90 degrees
+        assert_eq!(
+            ellipse_angle(pos(50, 0), pos(50, 25), pos(100, 25), pos(50, 50)),
+            90.0
+        );
+    }
+
+    #[test]
+    fn test_rotated_text_box_in_group() {
+        let doc = Document::new();
+        let mut ctx = ContentContext::new(&doc);
+        let rect = Rect::new(HwpUnit(0), HwpUnit(0), HwpUnit(1000), HwpUnit(500));
+        let shape = Shape {
+            id: 0,
+            shape_type: ShapeType::Rectangle { curvature: 0 },
+            rect,
+            line: ShapeLine {
+                style: LineStyle::Solid,
+                width: HwpUnit(0),
+                color: Color(0),
+                start_arrow: ArrowStyle::None,
+                end_arrow: ArrowStyle::None,
+                start_arrow_size: 0,
+                end_arrow_size: 0,
+            },
+            fill: Fill::none(),
+            text: Some(TextBox {
+                id: 0,
+                rect,
+                margin: Margin::zero(),
+                v_align: VAlignment::Center,
+                text_direction: TextDirection::Horizontal,
+                content: Vec::new(),
+            }),
+            placement: ObjectPlacement::new(),
+        };
+        // Quarter turn clockwise within the group
+        let group = ShapeGroup {
+            id: 0,
+            rect,
+            children: vec![GroupChild {
+                object: GroupObject::Shape(shape),
+                matrix: ShapeMatrix {
+                    a: 0.0,
+                    b: -1.0,
+                    d: 1.0,
+                    e: 0.0,
+                    ..ShapeMatrix::identity()
+                },
+            }],
+            placement: ObjectPlacement::new(),
+        };
+
+        let mut xml = String::new();
+        generate_group(&mut xml, &group, &mut ctx).unwrap();
+        // The text box keeps its rotation
+        assert!(xml.contains(
+            r#"draw:name="Shape2" draw:transform="rotate(-1.570796) translate(0.00mm 0.00mm)""#
+        ));
+        assert!(xml.contains("<draw:text-box>"));
+    }
+
+    #[test]
+    fn test_corner_radius() {
+        assert_eq!(corner_radius(HwpUnit(1000), HwpUnit(400), 0), HwpUnit(0));
+        assert_eq!(corner_radius(HwpUnit(1000), HwpUnit(400), 20), HwpUnit(80));
+        // 50% rounds the short ends into semicircles, and no further
+        assert_eq!(corner_radius(HwpUnit(1000), HwpUnit(400), 50), HwpUnit(200));
+        assert_eq!(
+            corner_radius(HwpUnit(1000), HwpUnit(400), 100),
+            HwpUnit(200)
+        );
+    }
+
+    #[test]
+    fn test_child_placement_scaled_and_rotated() {
+        let rect = Rect::new(HwpUnit(0), HwpUnit(0), HwpUnit(1000), HwpUnit(500));
+        let group = ObjectPlacement::new();
+
+        // Group scaled by 2, child moved by (100, 200) within the group
+        let parent = ShapeMatrix {
+            a: 2.0,
+            e: 2.0,
+            ..ShapeMatrix::identity()
+        };
+        let child = ShapeMatrix {
+            c: 100.0,
+            f: 200.0,
+            ..ShapeMatrix::identity()
+        };
+        let (placement, transform) = child_placement(&rect, &parent.multiply(&child), &group);
+        assert_eq!(
+            (placement.offset_x, placement.offset_y),
+            (HwpUnit(200), HwpUnit(400))
+        );
+        assert_eq!(
+            (placement.width, placement.height),
+            (HwpUnit(2000), HwpUnit(1000))
+        );
+        assert!(transform.is_empty());
+
+        // Quarter turn clockwise on the page
+        let rotated = ShapeMatrix {
+            a: 0.0,
+            b: -1.0,
+            d: 1.0,
+            e: 0.0,
+            ..child
+        };
+        let (placement, transform) = child_placement(&rect, &rotated, &group);
+        assert_eq!(
+            (placement.width, placement.height),
+            (HwpUnit(1000), HwpUnit(500))
+        );
+        assert_eq!(
+            transform,
+            r#" draw:transform="rotate(-1.570796) translate(0.35mm 0.71mm)""#
+        );
+    }
+}
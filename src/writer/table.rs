@@ -0,0 +1,375 @@
+use super::vertical::{in_direction, writing_mode};
+use super::{ContentContext, border_fill_properties, generate_block_content};
+use crate::common::{HwpResult, HwpUnit, Margin};
+use crate::model::{CaptionSide, FillType, ObjectAlign, Table, TableCell, TablePageBreak};
+use std::collections::HashMap;
+
+/// Generate a table with column definitions, merged cells and cell styles
+pub(super) fn generate_table(
+    xml: &mut String,
+    table: &Table,
+    ctx: &mut ContentContext,
+) -> HwpResult<()> {
+    // Captions become paragraphs above or below the table
+    let caption = table.placement.caption.as_ref();
+    if let Some(caption) = caption.filter(|c| c.side == CaptionSide::Top) {
+        for block in &caption.blocks {
+            generate_block_content(xml, block, ctx)?;
+        }
+    }
+
+    ctx.table_count += 1;
+    let name = format!("Table{}", ctx.table_count);
+    let rows = table
+        .rows
+        .max(table.cells.iter().map(|c| c.row + 1).max().unwrap_or(0));
+    let cols = table
+        .cols
+        .max(table.cells.iter().map(|c| c.col + 1).max().unwrap_or(0));
+    let col_widths = column_widths(table, cols);
+
+    add_table_style(&name, table, &col_widths, ctx);
+    xml.push_str(&format!(
+        r#"
+      <table:table table:name="{0}" table:style-name="{0}">"#,
+        name
+    ));
+
+    for width in &col_widths {
+        let style = ctx.automatic_style(
+            "table-column",
+            "co",
+            &format!(
+                r#"<style:table-column-properties style:column-width="{:.2}mm"/>"#,
+                width.to_mm()
+            ),
+        );
+        xml.push_str(&format!(
+            r#"
+        <table:table-column table:style-name="{}"/>"#,
+            style
+        ));
+    }
+
+    // Cells by origin; positions inside a merged area become covered cells
+    let mut origins: HashMap<(usize, usize), &TableCell> = HashMap::new();
+    let mut covered = vec![vec![false; cols]; rows];
+    for cell in &table.cells {
+        origins.insert((cell.row, cell.col), cell);
+        let col_end = (cell.col + cell.col_span).min(cols);
+        for (r, covered_row) in covered
+            .iter_mut()
+            .enumerate()
+            .take(cell.row + cell.row_span)
+            .skip(cell.row)
+        {
+            for (c, is_covered) in covered_row
+                .iter_mut()
+                .enumerate()
+                .take(col_end)
+                .skip(cell.col)
+            {
+                *is_covered |= (r, c) != (cell.row, cell.col);
+            }
+        }
+    }
+
+    for (row, covered_row) in covered.iter().enumerate() {
+        let header = row == 0 && table.repeat_header;
+        if header {
+            xml.push_str("\n        <table:table-header-rows>");
+        }
+
+        let row_style = ctx.automatic_style(
+            "table-row",
+            "ro",
+            &format!(
+                r#"<style:table-row-properties style:min-row-height="{:.2}mm"/>"#,
+                row_height(table, row).to_mm()
+            ),
+        );
+        xml.push_str(&format!(
+            r#"
+        <table:table-row table:style-name="{}">"#,
+            row_style
+        ));
+
+        for (col, &is_covered) in covered_row.iter().enumerate() {
+            if let Some(cell) = origins.get(&(row, col)) {
+                generate_cell(xml, table, cell, ctx)?;
+            } else if is_covered {
+                xml.push_str("\n          <table:covered-table-cell/>");
+            } else {
+                xml.push_str("\n          <table:table-cell/>");
+            }
+        }
+
+        xml.push_str("\n        </table:table-row>");
+        if header {
+            xml.push_str("\n        </table:table-header-rows>");
+        }
+    }
+
+    xml.push_str("\n      </table:table>");
+
+    if let Some(caption) = caption.filter(|c| c.side != CaptionSide::Top) {
+        for block in &caption.blocks {
+            generate_block_content(xml, block, ctx)?;
+        }
+    }
+    Ok(())
+}
+
+/// Add the table's own style, including a pending master page switch
+fn add_table_style(name: &str, table: &Table, col_widths: &[HwpUnit], ctx: &mut ContentContext) {
+    let width: i32 = col_widths.iter().map(|w| w.0).sum();
+    let placement = &table.placement;
+    let width = if placement.width.0 > 0 {
+        placement.width.0
+    } else {
+        width
+    };
+    // Tables stay in the text flow; a floating table keeps its horizontal alignment
+    let align = match placement.horz_align {
+        ObjectAlign::Center if !placement.treat_as_char => "center",
+        ObjectAlign::End | ObjectAlign::Outside if !placement.treat_as_char => "right",
+        _ => "left",
+    };
+
+    // A table opening a section carries the master page switch
+    let (master_page, page_number) = match ctx.section_start.take() {
+        Some(start) => (
+            format!(r#" style:master-page-name="{}""#, start.master_page),
+            if start.page_number != 0 {
+                format!(r#" style:page-number="{}""#, start.page_number)
+            } else {
+                String::new()
+            },
+        ),
+        None => (String::new(), String::new()),
+    };
+
+    // Cell spacing cannot be expressed in ODF; spaced tables keep separate borders
+    let border_model = if table.cell_spacing.0 > 0 {
+        "separating"
+    } else {
+        "collapsing"
+    };
+    let may_break = table.page_break != TablePageBreak::None;
+    // Table borders are drawn by the cells; the table keeps its background
+    let background = ctx
+        .doc
+        .border_fill(table.border_fill_id)
+        .map(|border_fill| match border_fill.fill_type {
+            FillType::Solid | FillType::Pattern | FillType::Gradient => format!(
+                r#" fo:background-color="{}""#,
+                border_fill.background_color.to_hex()
+            ),
+            FillType::None | FillType::Image => String::new(),
+        })
+        .unwrap_or_default();
+
+    ctx.add_style(name, || {
+        format!(
+            r#"
+    <style:style style:name="{}" style:family="table"{}>
+      <style:table-properties style:width="{:.2}mm" table:align="{}" fo:margin-left="{:.2}mm" fo:margin-right="{:.2}mm" fo:margin-top="{:.2}mm" fo:margin-bottom="{:.2}mm" table:border-model="{}" style:may-break-between-rows="{}"{}{}/>
+    </style:style>"#,
+            name,
+            master_page,
+            HwpUnit(width).to_mm(),
+            align,
+            placement.outer_margin.left.to_mm(),
+            placement.outer_margin.right.to_mm(),
+            placement.outer_margin.top.to_mm(),
+            placement.outer_margin.bottom.to_mm(),
+            border_model,
+            may_break,
+            background,
+            page_number
+        )
+    });
+}
+
+/// Generate one cell with its spans and automatic style
+fn generate_cell(
+    xml: &mut String,
+    table: &Table,
+    cell: &TableCell,
+    ctx: &mut ContentContext,
+) -> HwpResult<()> {
+    // Cells without their own margins use the table's inner margins
+    let margin = if cell.margin == Margin::zero() {
+        table.inner_margin
+    } else {
+        cell.margin
+    };
+
+    let mut properties = format!(
+        r#" fo:padding-left="{:.2}mm" fo:padding-right="{:.2}mm" fo:padding-top="{:.2}mm" fo:padding-bottom="{:.2}mm" style:vertical-align="{}""#,
+        margin.left.to_mm(),
+        margin.right.to_mm(),
+        margin.top.to_mm(),
+        margin.bottom.to_mm(),
+        cell.v_align.to_odt_str()
+    );
+    if let Some(border_fill) = ctx.doc.border_fill(cell_border_fill_id(table, cell)) {
+        properties.push_str(&border_fill_properties(border_fill));
+    }
+    properties.push_str(writing_mode(cell.text_direction));
+    let style = ctx.automatic_style(
+        "table-cell",
+        "ce",
+        &format!(r#"<style:table-cell-properties{}/>"#, properties),
+    );
+
+    let mut spans = String::new();
+    if cell.col_span > 1 {
+        spans.push_str(&format!(
+            r#" table:number-columns-spanned="{}""#,
+            cell.col_span
+        ));
+    }
+    if cell.row_span > 1 {
+        spans.push_str(&format!(
+            r#" table:number-rows-spanned="{}""#,
+            cell.row_span
+        ));
+    }
+
+    xml.push_str(&format!(
+        r#"
+          <table:table-cell table:style-name="{}" office:value-type="string"{}>"#,
+        style, spans
+    ));
+    in_direction(ctx, cell.text_direction, |ctx| {
+        cell.content
+            .iter()
+            .try_for_each(|content| generate_block_content(xml, content, ctx))
+    })?;
+    xml.push_str("\n          </table:table-cell>");
+    Ok(())
+}
+
+/// Border fill of a cell: the last zone covering the cell overrides the
+/// cell's own, and cells without one use the table's
+fn cell_border_fill_id(table: &Table, cell: &TableCell) -> u32 {
+    let zone = table.zones.iter().rev().find(|zone| {
+        (zone.start_row..=zone.end_row).contains(&cell.row)
+            && (zone.start_col..=zone.end_col).contains(&cell.col)
+    });
+    match zone {
+        Some(zone) => zone.border_fill_id,
+        None if cell.border_fill_id == 0 => table.border_fill_id,
+        None => cell.border_fill_id,
+    }
+}
+
+/// Work out column widths from the cell widths.
+/// Single-column cells fix their column; merged cells share what is left
+/// among the columns still unknown.
+fn column_widths(table: &Table, cols: usize) -> Vec<HwpUnit> {
+    let mut widths: Vec<Option<i32>> = vec![None; cols];
+
+    for cell in table
+        .cells
+        .iter()
+        .filter(|c| c.col_span == 1 && c.col < cols)
+    {
+        let width = widths[cell.col].unwrap_or(0).max(cell.width.0);
+        widths[cell.col] = Some(width);
+    }
+
+    let mut spanning: Vec<&TableCell> = table.cells.iter().filter(|c| c.col_span > 1).collect();
+    spanning.sort_by_key(|c| c.col_span);
+    for cell in spanning {
+        let range = cell.col.min(cols)..(cell.col + cell.col_span).min(cols);
+        let known: i32 = widths[range.clone()].iter().flatten().sum();
+        let unknown = widths[range.clone()].iter().filter(|w| w.is_none()).count();
+        if unknown > 0 {
+            let share = ((cell.width.0 - known) / unknown as i32).max(0);
+            for width in widths[range].iter_mut().filter(|w| w.is_none()) {
+                *width = Some(share);
+            }
+        }
+    }
+
+    // Fall back to an even split of the remaining table width
+    let known: i32 = widths.iter().flatten().sum();
+    let unknown = widths.iter().filter(|w| w.is_none()).count();
+    let share = if unknown > 0 {
+        ((table.placement.width.0 - known) / unknown as i32).max(0)
+    } else {
+        0
+    };
+    widths
+        .into_iter()
+        .map(|w| HwpUnit(w.filter(|&w| w > 0).unwrap_or(share)))
+        .collect()
+}
+
+/// Row height from the cells that occupy only this row
+fn row_height(table: &Table, row: usize) -> HwpUnit {
+    HwpUnit(
+        table
+            .cells
+            .iter()
+            .filter(|c| c.row == row && c.row_span == 1)
+            .map(|c| c.height.0)
+            .max()
+            .unwrap_or(0),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::VAlignment;
+    use crate::model::{TableZone, TextDirection};
+
+    fn cell(col: usize, col_span: usize, width: i32) -> TableCell {
+        TableCell {
+            row: 0,
+            col,
+            row_span: 1,
+            col_span,
+            width: HwpUnit(width),
+            height: HwpUnit(0),
+            margin: Margin::zero(),
+            border_fill_id: 0,
+            v_align: VAlignment::Top,
+            text_direction: TextDirection::Horizontal,
+            content: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_column_widths_from_merged_cells() {
+        let mut table = Table::new(1);
+        table.placement.width = HwpUnit(9000);
+        // Column 0 is known, column 1 comes from the merged cell, column 2 from the table width
+        table.cells = vec![cell(0, 1, 2000), cell(0, 2, 5000)];
+
+        let widths: Vec<i32> = column_widths(&table, 3).iter().map(|w| w.0).collect();
+        assert_eq!(widths, vec![2000, 3000, 4000]);
+    }
+
+    #[test]
+    fn test_cell_border_fill_from_zone_and_table() {
+        let mut table = Table::new(1);
+        table.border_fill_id = 2;
+        table.zones.push(TableZone {
+            start_row: 0,
+            start_col: 1,
+            end_row: 0,
+            end_col: 2,
+            border_fill_id: 5,
+        });
+        let mut own = cell(0, 1, 1000);
+        own.border_fill_id = 3;
+
+        assert_eq!(cell_border_fill_id(&table, &cell(0, 1, 1000)), 2);
+        assert_eq!(cell_border_fill_id(&table, &own), 3);
+        assert_eq!(cell_border_fill_id(&table, &cell(2, 1, 1000)), 5);
+    }
+}
@@ -0,0 +1,117 @@
+use super::{ContentContext, escape_text_content, escape_xml};
+use crate::model::{ChangeKind, TextRun};
+
+/// Generate a text run that belongs to a tracked change. Inserted and
+/// reformatted text stays in place between change marks; deleted text
+/// moves into its changed region and leaves a single mark behind.
+pub(super) fn generate_changed_run(
+    xml: &mut String,
+    run: &TextRun,
+    change_id: u32,
+    ctx: &mut ContentContext,
+) {
+    let span = format!(
+        r#"<text:span text:style-name="T{}">{}</text:span>"#,
+        run.char_shape_id,
+        escape_text_content(&run.text)
+    );
+    let Some(change) = ctx.doc.track_change(change_id) else {
+        xml.push_str(&span);
+        return;
+    };
+    // Header and footer content has no changed regions to refer to, so
+    // there the changes show as accepted
+    if !ctx.track_changes {
+        if change.kind != ChangeKind::Deletion {
+            xml.push_str(&span);
+        }
+        return;
+    }
+
+    let name = format!("ct{}", ctx.changed_regions.len() + 1);
+    let info = change_info(ctx, change.author_id, &change.date);
+    let region = match change.kind {
+        ChangeKind::Insertion => format!("<text:insertion>{}</text:insertion>", info),
+        ChangeKind::Format => format!("<text:format-change>{}</text:format-change>", info),
+        ChangeKind::Deletion => {
+            format!(
+                "<text:deletion>{}<text:p>{}</text:p></text:deletion>",
+                info, span
+            )
+        }
+    };
+    ctx.changed_regions.push(format!(
+        r#"<text:changed-region xml:id="{0}" text:id="{0}">{1}</text:changed-region>"#,
+        name, region
+    ));
+
+    if change.kind == ChangeKind::Deletion {
+        xml.push_str(&format!(r#"<text:change text:change-id="{}"/>"#, name));
+    } else {
+        xml.push_str(&format!(
+            r#"<text:change-start text:change-id="{0}"/>{1}<text:change-end text:change-id="{0}"/>"#,
+            name, span
+        ));
+    }
+}
+
+/// `text:tracked-changes` listing the changed regions of the body text
+pub(super) fn tracked_changes_xml(ctx: &ContentContext) -> String {
+    if ctx.changed_regions.is_empty() {
+        return String::new();
+    }
+    format!(
+        r#"
+      <text:tracked-changes text:track-changes="true">{}</text:tracked-changes>"#,
+        ctx.changed_regions.concat()
+    )
+}
+
+/// Author and time of a change
+fn change_info(ctx: &ContentContext, author_id: u32, date: &str) -> String {
+    let author = (author_id as usize)
+        .checked_sub(1)
+        .and_then(|i| ctx.doc.track_change_authors.get(i))
+        .map_or("", String::as_str);
+    let mut xml = format!(
+        "<office:change-info><dc:creator>{}</dc:creator>",
+        escape_xml(author)
+    );
+    if !date.is_empty() {
+        xml.push_str(&format!("<dc:date>{}</dc:date>", date));
+    }
+    xml.push_str("</office:change-info>");
+    xml
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Document, TrackChange};
+
+    #[test]
+    fn test_deleted_run_moves_to_region() {
+        let mut doc = Document::new();
+        doc.track_change_authors.push("Kim".to_string());
+        doc.track_changes.push(TrackChange {
+            id: 1,
+            kind: ChangeKind::Deletion,
+            author_id: 1,
+            date: "2024-03-05T14:20:00".to_string(),
+        });
+        let mut ctx = ContentContext::new(&doc);
+        ctx.track_changes = true;
+        let run = TextRun {
+            text: "old".to_string(),
+            char_shape_id: 2,
+            change_id: Some(1),
+        };
+
+        let mut xml = String::new();
+        generate_changed_run(&mut xml, &run, 1, &mut ctx);
+        assert_eq!(xml, r#"<text:change text:change-id="ct1"/>"#);
+        let regions = tracked_changes_xml(&ctx);
+        assert!(regions.contains("<dc:creator>Kim</dc:creator>"));
+        assert!(regions.contains(r#"<text:span text:style-name="T2">old</text:span>"#));
+    }
+}
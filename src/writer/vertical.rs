@@ -0,0 +1,96 @@
+use super::{ContentContext, escape_text_content};
+use crate::model::{TextDirection, TextRun};
+
+/// `style:writing-mode` for vertical text; horizontal text keeps the default
+pub(super) fn writing_mode(direction: TextDirection) -> &'static str {
+    if direction.is_vertical() {
+        r#" style:writing-mode="tb-rl""#
+    } else {
+        ""
+    }
+}
+
+/// Generate content in a text area of the given direction, so text runs
+/// know whether to stand Latin letters upright
+pub(super) fn in_direction<T>(
+    ctx: &mut ContentContext,
+    direction: TextDirection,
+    generate: impl FnOnce(&mut ContentContext) -> T,
+) -> T {
+    let outer = std::mem::replace(
+        &mut ctx.upright_latin,
+        direction == TextDirection::VerticalUpright,
+    );
+    let result = generate(ctx);
+    ctx.upright_latin = outer;
+    result
+}
+
+/// Generate a text run of vertical text with upright Latin letters.
+/// Vertical text lays Latin letters on their side, so each letter and
+/// digit gets a span rotated back upright.
+pub(super) fn generate_upright_run(xml: &mut String, run: &TextRun, ctx: &mut ContentContext) {
+    let name = format!("T{}U", run.char_shape_id);
+    ctx.add_style(&name, || {
+        format!(
+            r#"
+    <style:style style:name="{}" style:family="text" style:parent-style-name="T{}">
+      <style:text-properties style:text-rotation-angle="90" style:text-rotation-scale="fixed"/>
+    </style:style>"#,
+            name, run.char_shape_id
+        )
+    });
+
+    let mut segment = String::new();
+    let flush = |xml: &mut String, segment: &mut String| {
+        if !segment.is_empty() {
+            xml.push_str(&format!(
+                r#"<text:span text:style-name="T{}">{}</text:span>"#,
+                run.char_shape_id,
+                escape_text_content(segment)
+            ));
+            segment.clear();
+        }
+    };
+    for c in run.text.chars() {
+        if c.is_ascii_alphanumeric() {
+            flush(xml, &mut segment);
+            xml.push_str(&format!(
+                r#"<text:span text:style-name="{}">{}</text:span>"#,
+                name, c
+            ));
+        } else {
+            segment.push(c);
+        }
+    }
+    flush(xml, &mut segment);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Document;
+
+    #[test]
+    fn test_upright_latin_letters() {
+        let doc = Document::new();
+        let mut ctx = ContentContext::new(&doc);
+        let run = TextRun {
+            text: "가A1, 나".to_string(),
+            char_shape_id: 4,
+            change_id: None,
+        };
+        let mut xml = String::new();
+        generate_upright_run(&mut xml, &run, &mut ctx);
+        assert_eq!(
+            xml,
+            concat!(
+                r#"<text:span text:style-name="T4">가</text:span>"#,
+                r#"<text:span text:style-name="T4U">A</text:span>"#,
+                r#"<text:span text:style-name="T4U">1</text:span>"#,
+                r#"<text:span text:style-name="T4">, 나</text:span>"#
+            )
+        );
+        assert!(ctx.auto_styles["T4U"].contains(r#"style:text-rotation-angle="90""#));
+    }
+}